@@ -5,3 +5,453 @@ view = View
 welcome = Welcome to COSMIC! ✨
 page-id = Page { $num }
 git-description = Git commit {$hash} on {$date}
+no-page = Nothing to show
+no-page-description = No page is currently selected.
+show-first-page = Show the first page
+pause-when-idle = Pause when idle
+idle-timeout = Pause after
+idle-minutes = { $minutes ->
+    [one] { $minutes } minute
+   *[other] { $minutes } minutes
+}
+file = File
+export-report = Export report…
+report-title = Activity report
+report-timer = Stopwatch
+report-elapsed = Elapsed
+report-counter = Counter
+report-game = Guessing game
+report-attempts = Attempts this round
+lap-id = Lap { $num }
+lap = Lap
+lap-total = Total { $time }
+use-passphrase = Generate a passphrase
+wordlist = Word list
+wordlist-bundled = Bundled
+wordlist-imported = { $name } ({ $words } words)
+import-wordlist = Import word list…
+wordlist-invalid = The word list could not be imported: { $reason }
+regenerate-password = Regenerate password
+copy-password = Copy password
+require-strength = Require a minimum strength
+min-strength = Minimum strength
+entropy-bits = { $bits } bits
+strength-reached = Reached the minimum strength after { $attempts ->
+    [one] { $attempts } attempt
+   *[other] { $attempts } attempts
+}
+strength-not-reached = Could not reach the minimum strength in { $attempts } attempts
+campaign = Campaign
+levels = Levels
+level-id = Level { $num }
+free-play = Free play
+next-level = Next level
+attempts-left = { $attempts ->
+    [one] { $attempts } attempt left
+   *[other] { $attempts } attempts left
+}
+level-start = Find a number from 1 to { $upper } in { $attempts } attempts!
+out-of-attempts = Out of attempts! The number was { $secret }.
+daily-challenge = Daily challenge
+daily-id = Daily challenge for { $date }
+daily-start = Today's number from 1 to 100 is the same for everyone. Guess it!
+daily-calendar = Solved this month
+daily-solved = { $day } ✓{ $attempts }
+daily-played = { $attempts ->
+    [one] You solved today's challenge in one attempt. Come back tomorrow for a new number!
+   *[other] You solved today's challenge in { $attempts } attempts. Come back tomorrow for a new number!
+}
+show-numpad = Show number pad
+counter-id = Counter { $num }
+group-id = Group { $num }
+group-name = Group name
+subtotal = Subtotal
+grand-total = Grand total
+add-group = Add group
+goal = Goal (leave empty for none)
+goal-progress = { $value } of { $goal }
+goal-reached = { $name } reached its goal!
+always-sign = Always show sign
+red-negative = Red when negative
+thousands-separators = Thousands separators
+auto-reset = Reset automatically
+reset-never = Never
+reset-daily = Daily
+reset-weekly = Weekly
+monday = Monday
+tuesday = Tuesday
+wednesday = Wednesday
+thursday = Thursday
+friday = Friday
+saturday = Saturday
+sunday = Sunday
+stopwatch = Stopwatch
+countdown = Countdown
+alarms = Alarms
+start = Start
+pause = Pause
+reset = Reset
+countdown-length = Length (H:MM:SS)
+countdown-finished = The countdown has finished!
+alarm-ringing = Alarm: { $time }
+add-alarm = Add alarm
+no-alarms = No alarms
+milestones = Announce every
+milestones-off = Never
+milestone-reached = The stopwatch has reached { $time }
+next-session = Next session
+session-label = Label
+session-tags = Tags
+session-tags-placeholder = Comma-separated tags
+session-history = Sessions
+all-sessions = All sessions
+untitled-session = Untitled session
+no-sessions = No sessions recorded
+import-sessions = Import sessions…
+sessions-imported = Imported {$added} {$added ->
+    [one] session
+    *[other] sessions
+}, skipped {$skipped} already recorded
+sessions-import-failed = Could not import sessions: {$reason}
+this-week = This week (hours)
+media-keys = Control with media keys
+media-keys-description = Play/Pause starts and stops the stopwatch, or the countdown on its tab
+show-overlay = Show on top of other windows
+show-overlay-description = Keeps the remaining time in the top-right corner of the screen
+import-theme = Import theme…
+theme = Theme
+no-theme = No theme imported
+theme-import-failed = Could not import theme: {$reason}
+theme-background = Background
+theme-primary = Primary
+theme-accent = Accent
+theme-success = Success
+theme-warning = Warning
+theme-destructive = Destructive
+theme-dark = Dark theme
+theme-light = Light theme
+apply-theme = Apply
+use-system-theme = Use system theme
+header-images = Header images
+choose-header-image = Choose header image…
+default-header-image = Use default header image
+high-contrast = High contrast
+reduce-motion = Reduce motion
+settings = Settings
+startup = Startup
+start-page = Start on page
+start-minimized = Start minimized
+resume-timers = Resume running timers
+resume-timers-description = Keep the stopwatch counting while the app is closed, if it was running
+password-length = Length: { $length } characters
+uppercase = Uppercase letters
+digits = Digits
+symbols = Symbols
+context-drawer = Context drawer
+drawer-width = Width
+drawer-width-default = Default
+drawer-width-px = { $width } px
+keyboard-shortcuts = Keyboard shortcuts
+global-shortcuts = Everywhere
+close = Close
+strength-none = Enter or generate a password to rate it
+strength-very-weak = Very weak
+strength-weak = Weak
+strength-fair = Fair
+strength-strong = Strong
+strength-very-strong = Very strong
+strength-rating = { $rating } ({ $bits } bits)
+your-activity = Your activity
+activity-local-only = These counts are kept on this device only and are never sent anywhere.
+launches = Launches
+games-played = Games played
+page-visits = Page visits
+clear-activity = Clear activity data
+undo = Undo
+removed-counter = Removed { $name }
+removed-group = Removed the group { $name }
+removed-alarm = Removed the alarm at { $time }
+select = Select
+select-all = Select all
+selected-count = { $count } selected
+export = Export
+delete = Delete
+cancel = Cancel
+laps-copied = Copied the selected laps
+export-sessions = Export sessions
+sessions-exported = Exported {$count} {$count ->
+    [one] session
+    *[other] sessions
+}
+sessions-export-failed = Could not export sessions: {$reason}
+password-history = Password history ({$count})
+no-passwords = No passwords yet
+no-password-history = Generated passwords are listed here until the app is closed
+generate-password = Generate password
+difficulty = Difficulty
+difficulty-easy = Easy ({ $low }–{ $high })
+difficulty-normal = Normal ({ $low }–{ $high })
+difficulty-hard = Hard ({ $low }–{ $high })
+difficulty-custom = Custom
+range-from = From
+range-to = To
+apply = Apply
+invalid-range = ❌ Enter two whole numbers, the first smaller than the second!
+free-play-start = A number from { $low } to { $high } is hidden. Guess it!
+new-game = A new number from { $low } to { $high } has been chosen. Guess it!
+counter-name = Counter name
+best-attempts = Best: {$attempts} {$attempts ->
+    [one] attempt
+    *[other] attempts
+}
+no-best-attempts = No best result yet at this difficulty
+new-best = New best: won in {$attempts} {$attempts ->
+    [one] attempt
+    *[other] attempts
+}!
+clear-game-records = Clear game records
+search-sessions = Search sessions
+no-matching-sessions = No sessions match the search
+search-passwords = Search passwords
+no-matching-passwords = No passwords match the search
+filter-results = {$shown} of {$total}
+guess-correct = {$guess} ✓
+guess-hot = {$guess} · hot
+guess-warm = {$guess} · warm
+guess-cold = {$guess} · cold
+previous-page = Newer
+next-page = Older
+page-of = Page {$page} of {$pages}
+report-export-failed = Could not export the report: {$reason}
+header-image-failed = Could not set the header image: {$reason}
+timed-challenge = 60-second challenge
+challenge-start = Find my number from 1 to 100 within { $seconds } seconds!
+challenge-time-left = { $seconds ->
+    [one] 1 second left
+    *[other] { $seconds } seconds left
+}
+challenge-time-up = ⌛ Time's up! My number was { $secret }.
+challenge-won = Challenge complete
+challenge-won-summary = You found the number in { $seconds } seconds with { $attempts ->
+    [one] 1 guess
+    *[other] { $attempts } guesses
+}.
+challenge-lost = Out of time
+challenge-lost-summary = The number was { $secret }. You made { $attempts ->
+    [one] 1 guess
+    *[other] { $attempts } guesses
+}.
+play-again = Play again
+appearance = Appearance
+theme-mode = Theme
+theme-system = Match the system
+theme-light = Light
+theme-dark = Dark
+watch = Watch
+watch-format = Stopwatch format
+watch-format-seconds = Seconds
+watch-format-clock = Hours, minutes, and seconds
+password-defaults = Password defaults
+language = Language
+language-system = Match the system
+export-page-image = Export page as image…
+page-image-failed = Could not export the page image: {$reason}
+command-palette = Command palette…
+search-commands = Type a command or page
+no-matching-commands = No commands match
+go-to-page = Go to { $page }
+print = Print…
+print-report = Print report…
+print-failed = Could not print: {$reason}
+explicit-feedback = Explicit feedback
+explicit-feedback-description = Add symbols to color-coded ratings and hints
+quit = Quit
+saved-to = Saved to { $path }
+password-copied = Password copied
+game-won = You found the number in { $attempts ->
+    [one] one attempt
+   *[other] { $attempts } attempts
+}
+notifications = Notifications
+desktop-notifications = Desktop notifications
+desktop-notifications-description = Also announce won games and finished countdowns outside the app
+sound-silent = Silent
+sound-chime = Chime
+sound-bell = Bell
+sound-beeps = Beeps
+sound-file = Sound file…
+sound-timer-finished = Countdown finished
+sound-alarm = Alarm
+sound-goal-reached = Goal reached
+sound-game-won = Game won
+choose-sound-file = Choose a sound file
+sound-failed = Could not play the sound: {$reason}
+menu-keys-hint = Arrow keys move, Enter chooses, Escape closes
+new-window = New window
+theme-modes = Dark and light
+theme-sample-body = Text on the background.
+theme-sample-suggested = Suggested
+theme-sample-standard = Standard
+theme-sample-destructive = Destructive
+theme-sample-option = Option
+page-per-workspace = Remember the page on each workspace
+page-per-workspace-description = Moving the window to another workspace shows the page last used there.
+when-finished = When finished
+run-command = Run a command
+run-command-description = Runs through the shell each time a countdown finishes, for example to pause media or lock the screen.
+run-command-sandboxed = Runs inside the app's sandbox, where most programs on your system are not available. Prefix it with flatpak-spawn --host to run it outside, if the app is allowed to.
+command-placeholder = Command
+set-command = Set
+confirm-command = Run this command?
+confirm-command-body = Each time a countdown finishes, the app will run { $command } with your permissions. Only set commands you trust.
+run-when-finished = Run when finished
+command-failed = The countdown command failed: { $reason }
+round-resumed = Picking up the round you left.
+play-prompt = Your guess (q to quit):
+play-higher = ⏫ My number is higher!
+play-lower = ⏬ My number is less!
+play-not-a-number = ❌ Enter a number!
+play-gave-up = The number was { $secret }.
+edit = Edit
+redo = Redo
+counter-step = Step
+counter-step-placeholder = 1
+counter-bounds = Limits
+counter-min = Minimum
+counter-max = Maximum
+backup = Backup
+backup-description = All settings, counters, sessions, and records in one file
+back-up = Back up…
+restore-backup = Restore…
+restore = Restore
+confirm-restore = Restore this backup?
+confirm-restore-body = The backup from { $created }, made with version { $version }, replaces your current settings and data. A copy of them is kept in case the restore fails.
+backup-restored = Backup restored
+backup-failed = Could not back up: { $reason }
+restore-failed = Could not restore the backup: { $reason }
+config-conflict = Settings changed elsewhere
+config-conflict-body = Another instance of the app changed some of the same settings you just changed. Keep your changes, or use the other instance's?
+keep-mine = Keep mine
+use-theirs = Use theirs
+counter-history = History
+counter-history-empty = No changes recorded yet
+export-history = Export history…
+history-export-failed = Could not export the history: { $reason }
+transferring = Transferring…
+transferring-percent = Transferring… { $percent }%
+counter-value = Value
+counter-value-invalid = Enter a whole number within the counter's bounds
+reset-page = Reset page
+open-in-window = Open in new window
+pin-page = Pin
+unpin-page = Unpin
+speech = Speech
+spoken-countdown = Speak the countdown
+spoken-countdown-description = Counts down the last ten seconds aloud and says when the countdown ends
+speech-unavailable = This build of the app cannot speak
+speech-voice = Voice
+speech-rate = Speaking rate
+voice-default = Default
+voice-male = Male
+voice-female = Female
+voice-child = Child
+leaderboard = Leaderboard
+leaderboard-empty = Nobody on this computer has won at this difficulty yet
+leaderboard-entry = { $rank }. { $name }: { $attempts } { $attempts ->
+    [one] attempt
+    *[other] attempts
+} on { $date }
+leaderboard-record = A place on the leaderboard
+leaderboard-record-body = Winning in { $attempts } { $attempts ->
+    [one] attempt
+    *[other] attempts
+} puts you among the best on this computer. Everyone who uses it can see the name you enter.
+player-name = Your name
+leaderboard-save = Add to leaderboard
+leaderboard-skip = Skip
+leaderboard-saved = Added to the leaderboard
+leaderboard-save-failed = Failed to save the leaderboard: { $reason }
+anonymous-player = Anonymous
+hide-page = Hide from navigation
+navigation = Navigation
+restore-nav-description = Pages in their usual order, unpinned, and all shown
+restore-defaults = Restore defaults
+nav-badge = { $name } · { $status }
+nav-badge-attempts = { $attempts } { $attempts ->
+    [one] try
+    *[other] tries
+}
+spell-out = Spell out
+hide-spelling = Hide spelling
+copy-spelling = Copy spelling
+spelling-copied = Spelling copied
+spelled-uppercase = Capital { $word }
+spelled-lowercase = { $word }
+spelled-space = Space
+symbol-exclamation = Exclamation mark
+symbol-quote = Quotation mark
+symbol-hash = Hash
+symbol-dollar = Dollar
+symbol-percent = Percent
+symbol-ampersand = Ampersand
+symbol-apostrophe = Apostrophe
+symbol-open-parenthesis = Opening parenthesis
+symbol-close-parenthesis = Closing parenthesis
+symbol-asterisk = Asterisk
+symbol-plus = Plus
+symbol-comma = Comma
+symbol-hyphen = Hyphen
+symbol-period = Period
+symbol-slash = Slash
+symbol-colon = Colon
+symbol-semicolon = Semicolon
+symbol-less-than = Less-than sign
+symbol-equals = Equals sign
+symbol-greater-than = Greater-than sign
+symbol-question = Question mark
+symbol-at = At sign
+symbol-open-bracket = Opening square bracket
+symbol-backslash = Backslash
+symbol-close-bracket = Closing square bracket
+symbol-caret = Caret
+symbol-underscore = Underscore
+symbol-backtick = Backtick
+symbol-open-brace = Opening curly brace
+symbol-pipe = Vertical bar
+symbol-close-brace = Closing curly brace
+symbol-tilde = Tilde
+condensed-nav = Condensed navigation
+condensed-nav-description = Show only the icons of the pages in the nav bar
+auto-condense-nav = Condense navigation in narrow windows
+auto-condense-nav-description = Show only the icons while the window is too narrow for the page names
+quick-settings = Quick settings
+sounds = Sounds
+start-stopwatch = Start stopwatch
+stop-stopwatch = Stop stopwatch
+all-settings = All settings…
+clipboard-history = Clipboard history
+follow-clipboard = Follow the clipboard
+follow-clipboard-description = Keep what is copied in any app, until this app closes. Nothing is saved to disk.
+clipboard-unavailable = The clipboard cannot be followed: { $reason }
+clipboard-empty = Nothing copied yet
+clipboard-empty-description = Copies made in any app appear here while the clipboard is followed. Passwords are left out.
+clipboard-entry-details = Copied at { $time }, { $characters } { $characters ->
+    [one] character
+    *[other] characters
+}
+clear-clipboard-history = Clear history
+clipboard-copied = Copied to the clipboard
+pop-out = Open in a new window
+move-to-drawer = Move back to drawer
+dropped-files = Dropped files
+drop-files-hint = Drop files onto this window to see their size and type.
+no-dropped-files = No files dropped yet
+drop-files-now = Drop to add { $files } { $files ->
+    [one] file
+    *[other] files
+}
+dropped-file-details = { $path } · { $size } · { $mime }
+directory = Folder
+clear-dropped-files = Clear list
+dropped-file-failed = Could not read the dropped file: { $reason }
+guess-out-of-range = ❌ Guess a number from { $low } to { $high }!
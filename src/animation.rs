@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Time-based animation helpers.
+//!
+//! Animations are driven by `window::frames()` while any are running, and
+//! views read their progress when drawing. When the user prefers reduced
+//! motion, animations still run for their usual duration so that transient
+//! feedback is shown for as long, but nothing moves.
+
+use crate::contrast;
+use cosmic::iced::{Border, Padding};
+use cosmic::widget;
+use cosmic::Element;
+use std::f32::consts::TAU;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How far the shake animation moves its content, in pixels.
+const SHAKE_AMPLITUDE: f32 = 8.0;
+/// Number of back-and-forth movements in the shake animation.
+const SHAKE_CYCLES: f32 = 3.0;
+/// Length of the shake animation.
+pub const SHAKE_DURATION: Duration = Duration::from_millis(400);
+
+/// The user asked for animations to stay still.
+static REDUCED_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether animations should avoid moving anything.
+pub fn set_reduced_motion(reduced: bool) {
+    REDUCED_MOTION.store(reduced, Ordering::Relaxed);
+}
+
+/// Whether animations should avoid moving anything.
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(Ordering::Relaxed)
+}
+
+/// An animation running for a fixed duration from the moment it was created.
+#[derive(Clone, Copy, Debug)]
+pub struct Animation {
+    started: Instant,
+    duration: Duration,
+}
+
+impl Animation {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            started: Instant::now(),
+            duration,
+        }
+    }
+
+    /// How far through the animation is, from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        (self.started.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.started.elapsed() >= self.duration
+    }
+}
+
+/// Horizontal offset of a damped shake at `progress`.
+fn shake_offset(progress: f32) -> f32 {
+    SHAKE_AMPLITUDE * (1.0 - progress) * (progress * SHAKE_CYCLES * TAU).sin()
+}
+
+/// Shakes `content` sideways and flashes a fading error border around it
+/// while `animation` is running, and otherwise leaves it as it is.
+pub fn shaking<'a, Message: 'static>(
+    content: impl Into<Element<'a, Message>>,
+    animation: Option<&Animation>,
+) -> Element<'a, Message> {
+    let Some(animation) = animation.filter(|animation| !animation.is_finished()) else {
+        return content.into();
+    };
+
+    let progress = animation.progress();
+    let offset = if reduced_motion() { 0.0 } else { shake_offset(progress) };
+
+    // Only the side the content moves away from is padded, pushing it over.
+    widget::container(content)
+        .padding(Padding {
+            left: offset.max(0.0),
+            right: (-offset).max(0.0),
+            ..Padding::ZERO
+        })
+        .class(cosmic::theme::Container::custom(move |theme| {
+            let cosmic = theme.cosmic();
+            let high_contrast = contrast::is_high_contrast(theme);
+            let mut color: cosmic::iced::Color = cosmic.destructive_color().into();
+
+            // A fading outline is hard to see in high contrast, so keep it solid and wider.
+            if !high_contrast {
+                color.a = 1.0 - progress;
+            }
+
+            widget::container::Style {
+                border: Border {
+                    color,
+                    width: if high_contrast { 3.0 } else { 2.0 },
+                    radius: cosmic.corner_radii.radius_s.into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
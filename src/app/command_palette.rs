@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The command palette, which finds pages and menu actions by name.
+
+use super::{AppModel, MenuAction, Message, OVERLAY_WIDTH, Page, overlay};
+use crate::fl;
+use crate::palette::Palette;
+use cosmic::iced::Length;
+use cosmic::prelude::*;
+use cosmic::widget;
+use std::sync::LazyLock;
+
+/// Most matches listed in the command palette at once.
+const PALETTE_ROWS: usize = 8;
+
+/// Identifies the command palette's search field, to focus it when the palette opens.
+pub(super) static PALETTE_INPUT: LazyLock<widget::Id> = LazyLock::new(|| widget::Id::new("command-palette"));
+
+impl AppModel {
+    /// Every command the palette can run from the active page: its pages, then the menu actions.
+    pub(super) fn palette_commands(&self) -> Vec<(String, Command)> {
+        let active = self.nav.active_data::<Page>().copied();
+
+        let pages = Page::ALL
+            .into_iter()
+            .map(|page| (fl!("go-to-page", page = self.page_names[page.index()].as_str()), Command::Page(page)));
+
+        let actions = MenuAction::ALL
+            .into_iter()
+            .filter(|action| !matches!(action, MenuAction::CommandPalette | MenuAction::ShowPage(_)))
+            .filter(|action| action.page().is_none_or(|page| Some(page) == active))
+            .map(|action| (action.label(), Command::Action(action)));
+
+        pages.chain(actions).collect()
+    }
+
+    /// The command palette: a search field over the commands matching it.
+    pub(super) fn palette_view(&self, palette: &Palette) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let matches = palette.filter(self.palette_commands());
+        let selected = palette.selected(matches.len());
+
+        let input = widget::text_input(fl!("search-commands"), palette.query())
+            .id(PALETTE_INPUT.clone())
+            .on_input(Message::PaletteInput)
+            .on_submit(move |_| Message::PaletteRun(selected));
+
+        if matches.is_empty() {
+            return widget::column::with_capacity(2)
+                .push(input)
+                .push(widget::text::body(fl!("no-matching-commands")))
+                .spacing(space_s)
+                .width(Length::Fill)
+                .max_width(OVERLAY_WIDTH)
+                .apply(overlay);
+        }
+
+        // Keep the highlighted match in view as it moves past the shown rows.
+        let first = selected.saturating_sub(PALETTE_ROWS - 1);
+
+        let list = matches
+            .into_iter()
+            .enumerate()
+            .skip(first)
+            .take(PALETTE_ROWS)
+            .fold(widget::column::with_capacity(PALETTE_ROWS), |list, (idx, (label, _))| {
+                let class = if idx == selected {
+                    cosmic::theme::Button::Suggested
+                } else {
+                    cosmic::theme::Button::Text
+                };
+
+                list.push(
+                    widget::button::custom(widget::text::body(label))
+                        .width(Length::Fill)
+                        .class(class)
+                        .on_press(Message::PaletteRun(idx)),
+                )
+            });
+
+        widget::column::with_capacity(2)
+            .push(input)
+            .push(list)
+            .spacing(space_s)
+            .width(Length::Fill)
+            .max_width(OVERLAY_WIDTH)
+            .apply(overlay)
+    }
+}
+
+/// An entry of the command palette.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Command {
+    Page(Page),
+    Action(MenuAction),
+}
+
+impl Command {
+    pub(super) fn message(self) -> Message {
+        match self {
+            Command::Page(page) => Message::ActivatePage(page),
+            Command::Action(action) => action.message(),
+        }
+    }
+}
@@ -0,0 +1,508 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The header menus, and the actions they run.
+//!
+//! The same menus make up the menu bar and the menu opened from the keyboard,
+//! and their actions are what key bindings and the command palette run.
+
+use super::{AppModel, ContextPage, Message, OVERLAY_WIDTH, Page, overlay};
+use crate::fl;
+use crate::menu_keys::{self, MenuFocus};
+use crate::pages;
+use crate::shortcut;
+use cosmic::iced::Length;
+use cosmic::iced::keyboard::{Key, key::Named};
+use cosmic::prelude::*;
+use cosmic::widget::menu::key_bind::{KeyBind, Modifier};
+use cosmic::widget::{self, menu};
+use std::collections::{BTreeMap, HashMap};
+
+impl AppModel {
+    /// The menu bar of the header, with each menu's entries and their shortcuts.
+    pub(super) fn menu_bar(&self) -> Element<'_, Message> {
+        let trees = self
+            .menus()
+            .into_iter()
+            .map(|(title, entries)| {
+                let items = entries
+                    .into_iter()
+                    .map(|entry| match entry {
+                        MenuEntry::Action(label, action) => menu::Item::Button(label, None, action),
+                        MenuEntry::Toggle(label, checked, action) => menu::Item::CheckBox(label, None, checked, action),
+                        MenuEntry::Divider => menu::Item::Divider,
+                    })
+                    .collect();
+
+                menu::Tree::with_children(menu::root(title).apply(Element::from), menu::items(&self.key_binds, items))
+            })
+            .collect();
+
+        menu::bar(trees).into()
+    }
+
+    /// The header menus, by title, as shown in the menu bar and opened from the keyboard.
+    fn menus(&self) -> Vec<(String, Vec<MenuEntry>)> {
+        let action = |action: MenuAction| MenuEntry::Action(action.label(), action);
+
+        vec![
+            (
+                fl!("file"),
+                vec![
+                    action(MenuAction::NewWindow),
+                    MenuEntry::Divider,
+                    action(MenuAction::ImportSessions),
+                    action(MenuAction::ExportReport),
+                    action(MenuAction::PrintReport),
+                    MenuEntry::Divider,
+                    action(MenuAction::ClearGameRecords),
+                    MenuEntry::Divider,
+                    action(MenuAction::Quit),
+                ],
+            ),
+            (fl!("edit"), vec![action(MenuAction::Undo), action(MenuAction::Redo)]),
+            (
+                fl!("view"),
+                vec![
+                    MenuEntry::Toggle(
+                        fl!("header-images"),
+                        !self.config.hide_header_images,
+                        MenuAction::ToggleHeaderImages,
+                    ),
+                    action(MenuAction::ChooseHeaderImage),
+                    action(MenuAction::ResetHeaderImage),
+                    MenuEntry::Divider,
+                    MenuEntry::Toggle(fl!("high-contrast"), self.config.high_contrast, MenuAction::ToggleHighContrast),
+                    MenuEntry::Toggle(fl!("reduce-motion"), self.config.reduce_motion, MenuAction::ToggleReduceMotion),
+                    MenuEntry::Toggle(fl!("condensed-nav"), self.config.condensed_nav, MenuAction::ToggleCondensedNav),
+                    action(MenuAction::ImportTheme),
+                    action(MenuAction::ExportPageImage),
+                    action(MenuAction::Settings),
+                    action(MenuAction::Activity),
+                    action(MenuAction::Shortcuts),
+                    action(MenuAction::CommandPalette),
+                    action(MenuAction::About),
+                ],
+            ),
+        ]
+    }
+
+    /// The labels of each menu's entries, for moving through them from the keyboard.
+    pub(super) fn menu_labels(&self) -> Vec<Vec<Option<String>>> {
+        self.menus()
+            .into_iter()
+            .map(|(_, entries)| entries.into_iter().map(MenuEntry::label).collect())
+            .collect()
+    }
+
+    /// The menu whose title starts with `letter`.
+    pub(super) fn menu_with_mnemonic(&self, letter: char) -> Option<usize> {
+        let titles = self.menus().into_iter().map(|(title, _)| title).collect::<Vec<_>>();
+        menu_keys::menu_with_mnemonic(titles.iter().map(String::as_str), letter)
+    }
+
+    /// The action of the entry `focus` is on, if it has one.
+    pub(super) fn menu_action(&self, focus: MenuFocus) -> Option<MenuAction> {
+        let (_, entries) = self.menus().into_iter().nth(focus.menu)?;
+        entries.into_iter().nth(focus.entry)?.action()
+    }
+
+    /// The menu opened from the keyboard: the menu titles, then the open menu's entries.
+    pub(super) fn menu_view(&self, focus: MenuFocus) -> Element<'_, Message> {
+        let menus = self.menus();
+        let highlight = |on: bool| {
+            if on {
+                cosmic::theme::Button::Suggested
+            } else {
+                cosmic::theme::Button::Text
+            }
+        };
+
+        let titles = menus
+            .iter()
+            .enumerate()
+            .fold(widget::row::with_capacity(menus.len()), |row, (idx, (title, _))| {
+                row.push(
+                    widget::button::custom(widget::text::heading(title.clone()))
+                        .class(highlight(idx == focus.menu))
+                        .on_press(Message::OpenMenu(idx)),
+                )
+            })
+            .spacing(cosmic::theme::spacing().space_xxs);
+
+        let entries = menus.into_iter().nth(focus.menu).map(|(_, entries)| entries).unwrap_or_default();
+
+        let list = entries
+            .into_iter()
+            .enumerate()
+            .fold(widget::column::new(), |list, (idx, entry)| match entry {
+                MenuEntry::Divider => list.push(widget::divider::horizontal::light()),
+                MenuEntry::Action(label, _) | MenuEntry::Toggle(label, false, _) => list.push(
+                    widget::button::custom(widget::text::body(label))
+                        .width(Length::Fill)
+                        .class(highlight(idx == focus.entry))
+                        .on_press(Message::ActivateMenuEntry(focus.menu, idx)),
+                ),
+                MenuEntry::Toggle(label, true, _) => list.push(
+                    widget::button::custom(widget::text::body(format!("✓ {label}")))
+                        .width(Length::Fill)
+                        .class(highlight(idx == focus.entry))
+                        .on_press(Message::ActivateMenuEntry(focus.menu, idx)),
+                ),
+            });
+
+        widget::column::with_capacity(3)
+            .push(titles)
+            .push(list)
+            .push(widget::text::caption(fl!("menu-keys-hint")))
+            .spacing(cosmic::theme::spacing().space_s)
+            .width(Length::Fill)
+            .max_width(OVERLAY_WIDTH)
+            .apply(overlay)
+    }
+}
+
+/// The default key bindings, with `overrides` from the config applied.
+///
+/// Overrides name an action and give its shortcut as text, which replaces
+/// the action's default and takes the key from any other action using it.
+/// Unknown actions and unreadable shortcuts are skipped with a warning.
+pub(super) fn key_binds(overrides: &BTreeMap<String, String>) -> HashMap<KeyBind, MenuAction> {
+    let mut binds = default_key_binds();
+
+    for (name, text) in overrides {
+        let Some(action) = MenuAction::ALL.into_iter().find(|action| action.name() == *name) else {
+            eprintln!("unknown action in key bindings: {name}");
+            continue;
+        };
+
+        let key_bind = match text.trim() {
+            "" => None,
+            text => match shortcut::parse(text) {
+                Some(key_bind) => Some(key_bind),
+                None => {
+                    eprintln!("unreadable shortcut for {name}: {text}");
+                    continue;
+                }
+            },
+        };
+
+        binds.retain(|_, bound| *bound != action);
+
+        if let Some(key_bind) = key_bind {
+            binds.insert(key_bind, action);
+        }
+    }
+
+    binds
+}
+
+/// The application's default key bindings.
+fn default_key_binds() -> HashMap<KeyBind, MenuAction> {
+    let page = |num: usize| {
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character(num.to_string().into()),
+            },
+            MenuAction::ShowPage(Page::ALL[num - 1]),
+        )
+    };
+
+    HashMap::from([
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("q".into()),
+            },
+            MenuAction::Quit,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("z".into()),
+            },
+            MenuAction::Undo,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl, Modifier::Shift],
+                key: Key::Character("z".into()),
+            },
+            MenuAction::Redo,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("n".into()),
+            },
+            MenuAction::NewWindow,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character(",".into()),
+            },
+            MenuAction::Settings,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![],
+                key: Key::Named(Named::F1),
+            },
+            MenuAction::About,
+        ),
+        page(1),
+        page(2),
+        page(3),
+        page(4),
+        page(5),
+        page(6),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("r".into()),
+            },
+            MenuAction::RegeneratePassword,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl, Modifier::Shift],
+                key: Key::Character("c".into()),
+            },
+            MenuAction::CopyPassword,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("k".into()),
+            },
+            MenuAction::CommandPalette,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("p".into()),
+            },
+            MenuAction::PrintReport,
+        ),
+        (
+            KeyBind {
+                modifiers: vec![Modifier::Ctrl],
+                key: Key::Character("b".into()),
+            },
+            MenuAction::ToggleCondensedNav,
+        ),
+    ])
+}
+
+/// An entry of a header menu.
+enum MenuEntry {
+    Action(String, MenuAction),
+    /// An action turning a setting on or off, shown with its current state.
+    Toggle(String, bool, MenuAction),
+    Divider,
+}
+
+impl MenuEntry {
+    /// The entry's label, unless it cannot be chosen.
+    fn label(self) -> Option<String> {
+        match self {
+            MenuEntry::Action(label, _) | MenuEntry::Toggle(label, _, _) => Some(label),
+            MenuEntry::Divider => None,
+        }
+    }
+
+    fn action(self) -> Option<MenuAction> {
+        match self {
+            MenuEntry::Action(_, action) | MenuEntry::Toggle(_, _, action) => Some(action),
+            MenuEntry::Divider => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MenuAction {
+    About,
+    Settings,
+    Activity,
+    Shortcuts,
+    ToggleHeaderImages,
+    ToggleHighContrast,
+    ToggleReduceMotion,
+    ToggleCondensedNav,
+    ChooseHeaderImage,
+    ResetHeaderImage,
+    ImportTheme,
+    ImportSessions,
+    ExportReport,
+    ExportPageImage,
+    PrintReport,
+    RegeneratePassword,
+    CopyPassword,
+    ClearGameRecords,
+    CommandPalette,
+    NewWindow,
+    Undo,
+    Redo,
+    Quit,
+    ShowPage(Page),
+}
+
+impl MenuAction {
+    /// Every action, to find them by name and to list them in the command palette.
+    pub const ALL: [MenuAction; 29] = [
+        MenuAction::About,
+        MenuAction::Settings,
+        MenuAction::Activity,
+        MenuAction::Shortcuts,
+        MenuAction::CommandPalette,
+        MenuAction::ToggleHeaderImages,
+        MenuAction::ToggleHighContrast,
+        MenuAction::ToggleReduceMotion,
+        MenuAction::ToggleCondensedNav,
+        MenuAction::ChooseHeaderImage,
+        MenuAction::ResetHeaderImage,
+        MenuAction::ImportTheme,
+        MenuAction::ImportSessions,
+        MenuAction::ExportReport,
+        MenuAction::ExportPageImage,
+        MenuAction::PrintReport,
+        MenuAction::RegeneratePassword,
+        MenuAction::CopyPassword,
+        MenuAction::ClearGameRecords,
+        MenuAction::NewWindow,
+        MenuAction::Undo,
+        MenuAction::Redo,
+        MenuAction::Quit,
+        MenuAction::ShowPage(Page::Page1),
+        MenuAction::ShowPage(Page::Page2),
+        MenuAction::ShowPage(Page::Page3),
+        MenuAction::ShowPage(Page::Page4),
+        MenuAction::ShowPage(Page::Page5),
+        MenuAction::ShowPage(Page::Page6),
+    ];
+
+    /// The action's name in the config's key bindings.
+    pub fn name(self) -> String {
+        let name = match self {
+            MenuAction::About => "about",
+            MenuAction::Settings => "settings",
+            MenuAction::Activity => "activity",
+            MenuAction::Shortcuts => "keyboard-shortcuts",
+            MenuAction::CommandPalette => "command-palette",
+            MenuAction::ToggleHeaderImages => "toggle-header-images",
+            MenuAction::ToggleHighContrast => "toggle-high-contrast",
+            MenuAction::ToggleReduceMotion => "toggle-reduce-motion",
+            MenuAction::ToggleCondensedNav => "toggle-condensed-nav",
+            MenuAction::ChooseHeaderImage => "choose-header-image",
+            MenuAction::ResetHeaderImage => "reset-header-image",
+            MenuAction::ImportTheme => "import-theme",
+            MenuAction::ImportSessions => "import-sessions",
+            MenuAction::ExportReport => "export-report",
+            MenuAction::ExportPageImage => "export-page-image",
+            MenuAction::PrintReport => "print-report",
+            MenuAction::RegeneratePassword => "regenerate-password",
+            MenuAction::CopyPassword => "copy-password",
+            MenuAction::ClearGameRecords => "clear-game-records",
+            MenuAction::NewWindow => "new-window",
+            MenuAction::Undo => "undo",
+            MenuAction::Redo => "redo",
+            MenuAction::Quit => "quit",
+            MenuAction::ShowPage(page) => return format!("page-{}", page.index() + 1),
+        };
+
+        name.to_string()
+    }
+
+    /// The page this action is limited to, if it is not global.
+    pub fn page(self) -> Option<Page> {
+        match self {
+            MenuAction::RegeneratePassword | MenuAction::CopyPassword => Some(Page::Page3),
+            MenuAction::About
+            | MenuAction::Settings
+            | MenuAction::Activity
+            | MenuAction::Shortcuts
+            | MenuAction::ToggleHeaderImages
+            | MenuAction::ToggleHighContrast
+            | MenuAction::ToggleReduceMotion
+            | MenuAction::ToggleCondensedNav
+            | MenuAction::ChooseHeaderImage
+            | MenuAction::ResetHeaderImage
+            | MenuAction::ImportTheme
+            | MenuAction::ImportSessions
+            | MenuAction::ExportReport
+            | MenuAction::PrintReport
+            | MenuAction::ExportPageImage
+            | MenuAction::ClearGameRecords
+            | MenuAction::CommandPalette
+            | MenuAction::NewWindow
+            | MenuAction::Undo
+            | MenuAction::Redo
+            | MenuAction::Quit
+            | MenuAction::ShowPage(_) => None,
+        }
+    }
+
+    /// Describes the action, as in the menu or the shortcut cheatsheet.
+    pub fn label(self) -> String {
+        match self {
+            MenuAction::About => fl!("about"),
+            MenuAction::Settings => fl!("settings"),
+            MenuAction::Activity => fl!("your-activity"),
+            MenuAction::Shortcuts => fl!("keyboard-shortcuts"),
+            MenuAction::ToggleHeaderImages => fl!("header-images"),
+            MenuAction::ToggleHighContrast => fl!("high-contrast"),
+            MenuAction::ToggleReduceMotion => fl!("reduce-motion"),
+            MenuAction::ToggleCondensedNav => fl!("condensed-nav"),
+            MenuAction::ChooseHeaderImage => fl!("choose-header-image"),
+            MenuAction::ResetHeaderImage => fl!("default-header-image"),
+            MenuAction::ImportTheme => fl!("import-theme"),
+            MenuAction::ImportSessions => fl!("import-sessions"),
+            MenuAction::ExportReport => fl!("export-report"),
+            MenuAction::PrintReport => fl!("print-report"),
+            MenuAction::ExportPageImage => fl!("export-page-image"),
+            MenuAction::RegeneratePassword => fl!("regenerate-password"),
+            MenuAction::ClearGameRecords => fl!("clear-game-records"),
+            MenuAction::CommandPalette => fl!("command-palette"),
+            MenuAction::CopyPassword => fl!("copy-password"),
+            MenuAction::NewWindow => fl!("new-window"),
+            MenuAction::Undo => fl!("undo"),
+            MenuAction::Redo => fl!("redo"),
+            MenuAction::Quit => fl!("quit"),
+            MenuAction::ShowPage(page) => fl!("go-to-page", page = fl!("page-id", num = page.index() + 1)),
+        }
+    }
+}
+
+impl menu::action::MenuAction for MenuAction {
+    type Message = Message;
+
+    fn message(&self) -> Self::Message {
+        match self {
+            MenuAction::About => Message::ToggleContextPage(ContextPage::About),
+            MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            MenuAction::Activity => Message::ToggleContextPage(ContextPage::Activity),
+            MenuAction::Shortcuts => Message::ToggleShortcuts,
+            MenuAction::ToggleHeaderImages => Message::ToggleHeaderImages,
+            MenuAction::ToggleHighContrast => Message::ToggleHighContrast,
+            MenuAction::ToggleReduceMotion => Message::ToggleReduceMotion,
+            MenuAction::ToggleCondensedNav => Message::ToggleCondensedNav,
+            MenuAction::ChooseHeaderImage => Message::ChooseHeaderImage,
+            MenuAction::ResetHeaderImage => Message::ResetHeaderImage,
+            MenuAction::ImportTheme => Message::ImportTheme,
+            MenuAction::ImportSessions => Message::Watch(pages::watch::Message::ImportSessions),
+            MenuAction::ExportReport => Message::ExportReport,
+            MenuAction::PrintReport => Message::PrintReport,
+            MenuAction::ExportPageImage => Message::ExportPageImage,
+            MenuAction::RegeneratePassword => Message::Password(pages::password::Message::Generate),
+            MenuAction::CopyPassword => Message::Password(pages::password::Message::Copy),
+            MenuAction::ClearGameRecords => Message::Game(pages::game::Message::ClearRecords),
+            MenuAction::CommandPalette => Message::TogglePalette,
+            MenuAction::NewWindow => Message::NewWindow,
+            MenuAction::Undo => Message::Undo,
+            MenuAction::Redo => Message::Redo,
+            MenuAction::Quit => Message::Quit,
+            MenuAction::ShowPage(page) => Message::ActivatePage(page),
+        }
+    }
+}
@@ -0,0 +1,2912 @@
+// SPDX-License-Identifier: GPL-3
+
+mod command_palette;
+mod menus;
+mod settings;
+
+pub use menus::MenuAction;
+
+use crate::animation;
+use crate::audio::{self, Sound, SoundEvent};
+use crate::backup;
+use crate::config::{AppTheme, Config, ConfigField};
+use crate::contrast;
+use crate::counters::{self, Counter};
+use crate::dbus;
+use crate::error::{Code, Error};
+use crate::filter;
+use crate::fl;
+use crate::headers;
+use crate::i18n;
+use crate::io;
+use crate::layout;
+use crate::icons;
+use crate::menu_keys::MenuFocus;
+use crate::notify;
+use crate::palette::Palette;
+use crate::print;
+use crate::pages::{self, Page as _};
+use crate::report::{self, Report};
+use crate::snapshot;
+use crate::speech;
+use crate::state::State;
+use crate::tasks::Tasks;
+use crate::timer;
+use crate::usage::{self, Usage};
+use crate::widgets;
+use crate::workspace;
+use cosmic::app::context_drawer;
+use cosmic::cosmic_theme::{self, ThemeBuilder};
+use cosmic::dialog::file_chooser;
+use cosmic::cosmic_config::{self, ConfigGet, CosmicConfigEntry};
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::keyboard::{Event as KeyEvent, Key, Modifiers, key::Named};
+use cosmic::iced::{Event, Length, Subscription, event, window};
+use cosmic::iced_futures;
+use cosmic::widget::menu::key_bind::KeyBind;
+use cosmic::widget::{self, about::About, menu, nav_bar};
+use cosmic::prelude::*;
+use futures_util::SinkExt;
+use i18n_embed::unic_langid::LanguageIdentifier;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock};
+
+const REPOSITORY: &str = env!("CARGO_PKG_REPOSITORY");
+const APP_ICON: &[u8] = include_bytes!("../../resources/icons/hicolor/scalable/apps/icon.svg");
+/// How long a removed item may still be brought back from its toast.
+const UNDO_GRACE: std::time::Duration = std::time::Duration::from_secs(10);
+/// Most changes kept for undoing, after which the oldest are forgotten.
+const UNDO_LIMIT: usize = 100;
+/// How long a configuration change waits to be written, so that a burst of
+/// changes is written together.
+const CONFIG_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+/// Share of a wide window the page content takes, leaving margins either side.
+const CONTENT_SHARE: f32 = 0.75;
+/// Width the content keeps until the window is too narrow for it, and widest it grows.
+/// Narrower windows shrink it and its rows wrap.
+const CONTENT_WIDTHS: (f32, f32) = (600.0, 1080.0);
+/// Widest the cheatsheet and command palette cards grow.
+const OVERLAY_WIDTH: f32 = 420.0;
+/// Largest theme file imported, well beyond what a theme's colors take.
+const MAX_THEME_SIZE: u64 = 1024 * 1024;
+
+/// Identifies the container of the page shown in the main window, to capture just the page as an image.
+static PAGE_CONTENT: LazyLock<widget::Id> = LazyLock::new(|| widget::Id::new("page-content"));
+
+/// The application model stores app-specific state used to describe its interface and
+/// drive its logic.
+pub struct AppModel {
+    /// Application state which is managed by the COSMIC runtime.
+    core: cosmic::Core,
+    /// Display a context drawer with the designated page if defined.
+    context_page: ContextPage,
+    /// The about page for this app.
+    about: About,
+    /// Contains items assigned to the nav bar panel.
+    nav: nav_bar::Model,
+    /// Key bindings for the application's menu bar.
+    key_binds: HashMap<KeyBind, MenuAction>,
+    /// Configuration data that persists between application runs.
+    config: Config,
+    /// Writes configuration changes back to disk.
+    config_handler: Option<cosmic_config::Config>,
+    /// The configuration as last read from or written to disk, telling changes
+    /// made here apart from those another instance made.
+    config_base: Config,
+    /// Fields changed both here and by another instance, left as the other wrote
+    /// them until the user chooses which to keep.
+    config_conflicts: Vec<ConfigField>,
+    /// Whether the configuration changed since it was last written to disk.
+    config_unsaved: bool,
+    /// Title of each page, in the order of `Page::ALL`.
+    page_names: Vec<String>,
+    /// Pages listed by the header bar's page switcher, in the nav bar's order and without hidden ones.
+    switcher_pages: Vec<Page>,
+    /// Titles of `switcher_pages`.
+    switcher_names: Vec<String>,
+    /// Labels for the context drawer width choices: the default, then each of `DRAWER_WIDTHS`.
+    drawer_width_labels: Vec<String>,
+    /// Labels for the app theme choices, in the order of `AppTheme::ALL`.
+    app_theme_labels: Vec<String>,
+    /// Labels for the stopwatch format choices, in the order of `WatchFormat::ALL`.
+    watch_format_labels: Vec<String>,
+    /// Labels for the sound choices: each of `Sound::BUILT_IN`, then a file.
+    sound_labels: Vec<String>,
+    /// Languages the app has translations for.
+    languages: Vec<LanguageIdentifier>,
+    /// Labels for the language choices: the system's, then each of `languages`.
+    language_labels: Vec<String>,
+    /// Background tasks and transfers started by each page, stopped when navigating away.
+    tasks: Tasks<Page>,
+    /// The file being read or written, shown with its progress until it is done.
+    transfer: Option<io::Transferred>,
+    /// Transient notifications shown over the page content.
+    toasts: widget::Toasts<Message>,
+    /// A theme imported from a file, previewed in the context drawer.
+    theme_preview: Option<(String, cosmic_theme::Theme)>,
+    /// The imported theme in dark and then light mode, for the split preview.
+    theme_modes: Option<[cosmic::Theme; 2]>,
+    /// State of the sample controls in the split preview, shared by both halves.
+    theme_sample_on: bool,
+    /// The imported theme has been applied in place of the system theme.
+    custom_theme: bool,
+    /// A backup chosen for restoring, waiting for the user to confirm.
+    restoring: Option<(PathBuf, backup::Manifest)>,
+    /// Broadcasts app events on the session bus, once connected.
+    dbus: Option<dbus::Sender>,
+    /// The keyboard shortcut cheatsheet is shown over the page.
+    show_shortcuts: bool,
+    /// The quick settings are open below their header button.
+    show_quick_settings: bool,
+    /// The command palette, while it is open.
+    palette: Option<Palette>,
+    /// The header menu opened from the keyboard, and its highlighted entry.
+    menu_focus: Option<MenuFocus>,
+    /// Width of the main window, which the page content grows with.
+    window_width: f32,
+    /// Windows narrower than the layout breakpoint, whose views use the narrow layout.
+    narrow_windows: HashSet<window::Id>,
+    /// Whether the nav bar's items were last labelled by their icons alone.
+    condensed: bool,
+    /// Counts changes to the window's size and position, so that only the
+    /// latest is saved once they settle.
+    geometry_generation: u64,
+    /// Name of the workspace the main window is on, once the compositor tells.
+    workspace: Option<String>,
+    /// Windows showing the stopwatch apart from the main window.
+    stopwatch_windows: Vec<window::Id>,
+    /// Windows showing one page apart from the main window, opened from the nav bar.
+    page_windows: Vec<(window::Id, Page)>,
+    /// Windows showing a context page popped out of the drawer.
+    drawer_windows: Vec<(window::Id, ContextPage)>,
+    /// The page being dragged to a new place in the settings' list of pages.
+    dragging_page: Option<Page>,
+    /// How the application has been used, shown on the activity page.
+    usage: Usage,
+    /// Removed items which may still be restored, oldest first, by their undo key.
+    pending_removals: Vec<(u64, pages::Removed)>,
+    /// Key given to the next entry of `pending_removals`.
+    next_removal: u64,
+    /// Changes that may be undone, oldest first.
+    undo: Vec<pages::Change>,
+    /// Changes undone that may be redone, the last undone at the end.
+    redo: Vec<pages::Change>,
+    /// Started with `--daemon`: keeps running without a window, and opens one when activated.
+    daemon: bool,
+    /// App ID given to the windows the app opens itself, identifying them to the desktop.
+    app_id: Option<String>,
+    /// Stopwatch, countdown, alarms, and recorded sessions.
+    watch: pages::WatchPage,
+    /// Grouped counters.
+    counters: pages::CountersPage,
+    /// Password and passphrase generator.
+    password: pages::PasswordPage,
+    clipboard: pages::ClipboardPage,
+    /// Files dropped onto a window.
+    files: pages::FilesPage,
+    /// Number guessing game.
+    game: pages::GamePage,
+}
+
+/// Messages emitted by the application and its widgets.
+#[derive(Debug, Clone)]
+pub enum Message {
+    Watch(pages::watch::Message),
+    Counters(pages::counters::Message),
+    Password(pages::password::Message),
+    Clipboard(pages::clipboard::Message),
+    Files(pages::files::Message),
+    Game(pages::game::Message),
+    /// Shows a toast, for tasks and widgets outside the pages, which use [`pages::Context::toast`].
+    ShowToast(String),
+    CloseToast(widget::ToastId),
+    CloseWindow(window::Id),
+    NavMenu(NavMenuAction),
+    DragPage(Page),
+    DragPageOver(Page),
+    DropPage,
+    /// Moves the page by the given number of places among the unpinned pages.
+    MovePage(Page, isize),
+    ShowInNav(Page, bool),
+    RestoreNav,
+    NewWindow,
+    WindowResized(window::Id, cosmic::iced::Size),
+    WindowMoved(window::Id, cosmic::iced::Point),
+    SaveWindowGeometry(u64),
+    WriteConfig,
+    Quit,
+    ActivatePage(Page),
+    CancelTask(Page),
+    TaskFinished(Page, u64),
+    Transferred(io::Transferred),
+    CancelTransfer,
+    LaunchUrl(String),
+    ExportReport,
+    ExportPageImage,
+    PageCaptured(window::Screenshot, Option<cosmic::iced::Rectangle>),
+    PageImageExported(Result<Option<PathBuf>, Error>),
+    ReportExported(Result<Option<PathBuf>, Error>),
+    PrintReport,
+    PrintActivity,
+    Printed(Result<(), Error>),
+    DbusReady(dbus::Sender),
+    ToggleHeaderImages,
+    ToggleHighContrast,
+    ToggleReduceMotion,
+    ToggleCondensedNav,
+    AutoCondenseNav(bool),
+    StartPage(usize),
+    StartMinimized(bool),
+    PagePerWorkspace(bool),
+    WorkspaceChanged(String),
+    ResumeTimers(bool),
+    DesktopNotifications(bool),
+    SelectSound(SoundEvent, usize),
+    SoundFileChosen(SoundEvent, Option<Result<PathBuf, Error>>),
+    PreviewSound(SoundEvent),
+    SoundPlayed(Result<(), Error>),
+    ContextDrawerWidth(u32),
+    AppTheme(AppTheme),
+    WatchFormat(timer::WatchFormat),
+    ExplicitFeedback(bool),
+    Language(Option<usize>),
+    ClearUsage,
+    UndoRemoval(u64),
+    ForgetRemoval(u64),
+    Undo,
+    Redo,
+    ChooseHeaderImage,
+    HeaderImageChosen(usize, Option<Result<PathBuf, Error>>),
+    ResetHeaderImage,
+    ImportTheme,
+    ThemeImported(Option<Result<(String, cosmic_theme::Theme), Error>>),
+    BackUp,
+    BackedUp(Result<Option<PathBuf>, Error>),
+    ChooseBackup,
+    BackupChosen(Option<Result<(PathBuf, backup::Manifest), Error>>),
+    ConfirmRestore,
+    CancelRestore,
+    Restored(Option<Result<(), Error>>),
+    ApplyTheme,
+    ThemeSample(bool),
+    UseSystemTheme,
+    ToggleContextPage(ContextPage),
+    /// Moves a context page out of the drawer into its own window.
+    PopOutContextPage(ContextPage),
+    /// Moves the context page shown in a window back into the drawer.
+    DockContextPage(window::Id),
+    ToggleShortcuts,
+    ToggleQuickSettings,
+    /// Leaves the quick settings for the settings drawer.
+    ShowAllSettings,
+    MuteSounds(bool),
+    OpenMenu(usize),
+    CloseMenu,
+    ActivateMenuEntry(usize, usize),
+    TogglePalette,
+    PaletteInput(String),
+    PaletteRun(usize),
+    Key(Modifiers, Key),
+    UpdateConfig(Config),
+    ConfigChanged(ConfigField),
+    /// Settles conflicting configuration changes, keeping this instance's if true.
+    ResolveConfigConflicts(bool),
+}
+
+/// Create a COSMIC application from the app model
+impl cosmic::Application for AppModel {
+    /// The async executor that will be used to run your application's commands.
+    type Executor = cosmic::executor::Default;
+
+    /// Data that your application receives to its init method.
+    type Flags = Flags;
+
+    /// Messages which the application and its widgets will emit.
+    type Message = Message;
+
+    /// Unique identifier in RDNN (reverse domain name notation) format.
+    const APP_ID: &'static str = "dev.mmurphy.Test";
+
+    fn core(&self) -> &cosmic::Core {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut cosmic::Core {
+        &mut self.core
+    }
+
+    /// Initializes the application with any given flags and startup commands.
+    fn init(
+        core: cosmic::Core,
+        flags: Self::Flags,
+    ) -> (Self, Task<cosmic::Action<Self::Message>>) {
+        let config_handler = cosmic_config::Config::new(Self::APP_ID, Config::VERSION).ok();
+
+        let config = config_handler
+            .as_ref()
+            .map(|context| match Config::get_entry(context) {
+                Ok(config) => config,
+                Err((_errors, config)) => {
+                    // for why in errors {
+                    //     tracing::error!(%why, "error loading app config");
+                    // }
+
+                    config
+                }
+            })
+            .unwrap_or_default();
+
+        // Switch to the chosen language before any labels are built.
+        if let Some(language) = config.language.as_deref().and_then(|tag| tag.parse().ok()) {
+            i18n::select(Some(&language));
+        }
+
+        // The nav bar's items are added by `arrange_nav`, which leaves out hidden pages.
+        let nav = nav_bar::Model::default();
+
+        let languages = i18n::available();
+
+        // Construct the app model with the runtime's core.
+        let mut app = AppModel {
+            core,
+            context_page: ContextPage::default(),
+            about: about(),
+            nav,
+            key_binds: menus::key_binds(&config.key_binds),
+            page_names: page_names(),
+            switcher_pages: Vec::new(),
+            switcher_names: Vec::new(),
+            drawer_width_labels: settings::drawer_width_labels(),
+            app_theme_labels: settings::app_theme_labels(),
+            watch_format_labels: settings::watch_format_labels(),
+            sound_labels: settings::sound_labels(),
+            language_labels: settings::language_labels(&languages),
+            languages,
+            watch: pages::WatchPage::new(&config),
+            counters: pages::CountersPage::new(),
+            password: pages::PasswordPage::new(),
+            clipboard: pages::ClipboardPage::new(),
+            files: pages::FilesPage::new(),
+            game: pages::GamePage::new(&config),
+            // Optional configuration file for an application.
+            config_base: config.clone(),
+            config_unsaved: false,
+            config_conflicts: Vec::new(),
+            config,
+            config_handler,
+            tasks: Tasks::default(),
+            transfer: None,
+            toasts: widget::Toasts::new(Message::CloseToast),
+            theme_preview: None,
+            theme_modes: None,
+            theme_sample_on: false,
+            custom_theme: false,
+            restoring: None,
+            dbus: None,
+            show_shortcuts: false,
+            show_quick_settings: false,
+            palette: None,
+            menu_focus: None,
+            window_width: 0.0,
+            narrow_windows: HashSet::new(),
+            condensed: false,
+            geometry_generation: 0,
+            workspace: None,
+            stopwatch_windows: Vec::new(),
+            page_windows: Vec::new(),
+            drawer_windows: Vec::new(),
+            dragging_page: None,
+            usage: Usage::load(),
+            pending_removals: Vec::new(),
+            next_removal: 0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            daemon: flags.daemon,
+            app_id: flags.app_id,
+        };
+
+        contrast::set_forced(app.config.high_contrast);
+        animation::set_reduced_motion(app.config.reduce_motion);
+
+        // Pick up where the pages were left. Versions before the state file
+        // kept the stopwatch's time in the configuration.
+        let state = State::load().unwrap_or_else(|| {
+            let mut state = State::default();
+
+            if let Some(handler) = &app.config_handler {
+                state.watch.stopwatch = handler.get::<timer::Saved>("stopwatch").unwrap_or_default();
+            }
+
+            state
+        });
+
+        app.watch.restore_state(&state, &app.config);
+        app.counters.restore_state(&state, &app.config);
+        app.password.restore_state(&state, &app.config);
+        app.game.restore_state(&state, &app.config);
+
+        // Start with a single counter, as before groups existed.
+        if app.config.counter_groups.is_empty() {
+            let mut group = counters::Group::new(fl!("group-id", num = 1));
+            group.counters.push(Counter::new(fl!("counter-id", num = 1)));
+            app.config.counter_groups.push(group);
+        }
+
+        // Written straight away, or the IDs would be given again at each launch.
+        if counters::assign_ids(&mut app.config.counter_groups) {
+            app.save_config();
+        }
+
+        app.arrange_nav();
+
+        // Open on the page asked for on the command line, or else the configured one.
+        let start = Page::ALL
+            .get(flags.page.unwrap_or(app.config.start_page))
+            .copied()
+            .unwrap_or(Page::Page1);
+
+        if let Some(id) = app.nav.iter().find(|&id| app.nav.data::<Page>(id) == Some(&start)) {
+            app.nav.activate(id);
+        }
+
+        app.usage.record(usage::Event::Launch);
+        app.usage.record(usage::Event::Visit(start.index()));
+
+        // Create a startup command that sets the window title.
+        let mut commands = vec![
+            app.update_title(),
+            pages::GamePage::load_leaderboard().map(|message| cosmic::Action::App(Message::Game(message))),
+        ];
+
+        // Restore the runtime's main window as it was left. Windows the app opens
+        // itself are opened that way.
+        if let Some(id) = app.core.main_window_id() {
+            if let Some((width, height)) = app.config.window_size {
+                commands.push(window::resize(id, cosmic::iced::Size::new(width as f32, height as f32)));
+            }
+
+            if let Some((x, y)) = app.config.window_position {
+                commands.push(window::move_to(id, cosmic::iced::Point::new(x as f32, y as f32)));
+            }
+        }
+
+        if let Some((width, _)) = app.config.window_size {
+            app.window_width = width as f32;
+
+            if let Some(id) = app.core.main_window_id().filter(|_| layout::is_narrow(app.window_width)) {
+                app.narrow_windows.insert(id);
+            }
+        }
+
+        // Without the usual app ID, the main window is opened here rather than by the runtime.
+        if app.app_id.is_some() && !app.daemon {
+            commands.push(app.open_window());
+        }
+
+        if app.config.app_theme != AppTheme::System {
+            commands.push(cosmic::command::set_theme(app.config.app_theme.theme()));
+        }
+
+        if app.config.start_minimized {
+            if let Some(id) = app.core.main_window_id() {
+                commands.push(window::minimize(id, true));
+            }
+        }
+
+        (app, Task::batch(commands))
+    }
+
+    /// Elements to pack at the start of the header bar.
+    fn header_start(&self) -> Vec<Element<'_, Self::Message>> {
+        let mut elements = vec![self.menu_bar()];
+
+        // Keep navigation reachable when the nav bar is hidden or condensed away.
+        if !self.core.nav_bar_active() {
+            let selected = self
+                .nav
+                .active_data::<Page>()
+                .and_then(|page| self.switcher_pages.iter().position(|p| p == page));
+            let pages = self.switcher_pages.clone();
+
+            elements.push(
+                widget::dropdown(&self.switcher_names, selected, move |idx| Message::ActivatePage(pages[idx])).into(),
+            );
+        }
+
+        elements
+    }
+
+    /// Elements to pack at the end of the header bar.
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        let quick_settings = widget::tooltip(
+            widget::button::icon(icons::handle("preferences-system-symbolic")).on_press(Message::ToggleQuickSettings),
+            widget::text::body(fl!("quick-settings")),
+            widget::tooltip::Position::Bottom,
+        );
+
+        let quick_settings = if self.show_quick_settings {
+            widget::popover(quick_settings)
+                .popup(self.quick_settings_view())
+                .position(widget::popover::Position::Bottom)
+                .on_close(Message::ToggleQuickSettings)
+                .into()
+        } else {
+            quick_settings.into()
+        };
+
+        if self.nav.active_data::<Page>() != Some(&Page::Page3) {
+            return vec![quick_settings];
+        }
+
+        vec![
+            quick_settings,
+            widget::tooltip(
+                widget::button::icon(icons::handle("view-refresh-symbolic"))
+                    .on_press(Message::Password(pages::password::Message::Generate)),
+                widget::text::body(fl!("regenerate-password")),
+                widget::tooltip::Position::Bottom,
+            )
+            .into(),
+            widget::tooltip(
+                widget::button::icon(icons::handle("edit-copy-symbolic"))
+                    .on_press(Message::Password(pages::password::Message::Copy)),
+                widget::text::body(fl!("copy-password")),
+                widget::tooltip::Position::Bottom,
+            )
+            .into(),
+        ]
+    }
+
+    /// Enables the COSMIC application to create a nav bar with this model.
+    fn nav_model(&self) -> Option<&nav_bar::Model> {
+        Some(&self.nav)
+    }
+
+    /// The menu shown when a nav item is right-clicked, with actions for its page.
+    fn nav_context_menu(&self, id: nav_bar::Id) -> Option<Vec<menu::Tree<cosmic::Action<Self::Message>>>> {
+        let page = *self.nav.data::<Page>(id)?;
+        let pin = if self.config.pinned_pages.contains(&page.index()) { fl!("unpin-page") } else { fl!("pin-page") };
+
+        Some(menu::items(
+            &HashMap::new(),
+            vec![
+                menu::Item::Button(fl!("reset-page"), None, NavMenuAction::Reset(page)),
+                menu::Item::Button(fl!("open-in-window"), None, NavMenuAction::OpenWindow(page)),
+                menu::Item::Divider,
+                menu::Item::Button(pin, None, NavMenuAction::Pin(page)),
+                menu::Item::Button(fl!("hide-page"), None, NavMenuAction::Hide(page)),
+            ],
+        ))
+    }
+
+    /// Display a context drawer if the context page is requested.
+    fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
+        if !self.core.window.show_context {
+            return None;
+        }
+
+        Some(match self.context_page {
+            ContextPage::About => context_drawer::about(
+                &self.about,
+                |url| Message::LaunchUrl(url.to_string()),
+                Message::ToggleContextPage(ContextPage::About),
+            ),
+            // A drawer of a chosen width is drawn by the view instead.
+            _ if self.config.context_drawer_width != 0 => return None,
+            page => context_drawer::context_drawer(
+                self.context_page_view(page, self.is_narrow(self.core.main_window_id())),
+                Message::ToggleContextPage(page),
+            )
+                .title(page.title())
+                .header_actions([pop_out_button(page)]),
+        })
+    }
+
+    /// Describes the interface based on the current state of the application model.
+    ///
+    /// Application events will be processed through the view. Any messages emitted by
+    /// events received by widgets will be passed to the update method.
+    fn view(&self) -> Element<'_, Self::Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let Some(page) = self.nav.active_data::<Page>() else {
+            let icon = icons::handle("dialog-information-symbolic");
+
+            let empty = widgets::empty_state(icon, fl!("no-page"))
+                .description(fl!("no-page-description"))
+                .action(fl!("show-first-page"), Message::ActivatePage(Page::Page1));
+
+            return widget::container(empty)
+                .height(Length::Fill)
+                .align_y(Vertical::Center)
+                .into();
+        };
+
+        let content = self.page_content(*page, self.is_narrow(self.core.main_window_id()));
+
+        let content: Element<_> = if self.config.hide_header_images {
+            content
+        } else {
+            let idx = page.index();
+
+            widget::column::with_capacity(2)
+                .push(headers::header(idx, self.config.header_images.get(&idx).map(PathBuf::as_path)))
+                .push(content)
+                .spacing(space_s)
+                .into()
+        };
+
+        let content = widget::container(content).id(PAGE_CONTENT.clone());
+
+        let content: Element<_> = match &self.transfer {
+            Some(transfer) => widget::column::with_capacity(2)
+                .push(transfer_view(transfer, self.tasks.owner(&transfer.cancel).copied()))
+                .push(content)
+                .spacing(space_s)
+                .into(),
+            None => content.into(),
+        };
+
+        let content = widget::container(content)
+            .width(Length::Fill)
+            .max_width((self.window_width * CONTENT_SHARE).clamp(CONTENT_WIDTHS.0, CONTENT_WIDTHS.1))
+            .height(Length::Fill)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .align_y(Vertical::Center);
+
+        let content = self.sized_drawer(content.into());
+
+        if let Some(focus) = self.menu_focus {
+            let content = widget::popover(content)
+                .popup(self.menu_view(focus))
+                .modal(true)
+                .on_close(Message::CloseMenu);
+
+            return widget::toaster(&self.toasts, content);
+        }
+
+        if let Some(palette) = &self.palette {
+            let content = widget::popover(content)
+                .popup(self.palette_view(palette))
+                .modal(true)
+                .on_close(Message::TogglePalette);
+
+            return widget::toaster(&self.toasts, content);
+        }
+
+        if self.show_shortcuts {
+            let content = widget::popover(content)
+                .popup(self.shortcuts_view())
+                .modal(true)
+                .on_close(Message::ToggleShortcuts);
+
+            return widget::toaster(&self.toasts, content);
+        }
+
+        widget::toaster(&self.toasts, content)
+    }
+
+    /// Closes stopwatch windows, while closing the window of a daemon only
+    /// detaches it, leaving the timers running.
+    fn on_close_requested(&self, id: window::Id) -> Option<Self::Message> {
+        // Closing the main window of an app that is not a daemon quits it.
+        if self.core.main_window_id() == Some(id) {
+            self.save_state();
+            self.flush_config();
+        }
+
+        let main = self.daemon && self.core.main_window_id() == Some(id);
+        let detached = self.stopwatch_windows.contains(&id)
+            || self.page_windows.iter().any(|(window, _)| *window == id)
+            || self.drawer_windows.iter().any(|(window, _)| *window == id);
+        (main || detached).then_some(Message::CloseWindow(id))
+    }
+
+    /// Brings the window forward when the app is launched again, opening one while
+    /// running as a daemon, and runs the action the launch asked for, such as
+    /// showing the page given with `--page`.
+    fn dbus_activation(&mut self, msg: cosmic::dbus_activation::Message) -> Task<cosmic::Action<Self::Message>> {
+        let window = match self.core.main_window_id() {
+            Some(id) => window::gain_focus(id),
+            None => self.open_window(),
+        };
+
+        let action = match msg.msg {
+            cosmic::dbus_activation::Details::ActivateAction { action, .. } => {
+                MenuAction::ALL.into_iter().find(|known| known.name() == action)
+            }
+            _ => None,
+        };
+
+        match action {
+            Some(action) => Task::batch([window, self.update(action.message())]),
+            None => window,
+        }
+    }
+
+    /// Shows the active page's dialog over the window.
+    fn dialog(&self) -> Option<Element<'_, Self::Message>> {
+        if let Some((_, manifest)) = &self.restoring {
+            return Some(self.restore_dialog(manifest));
+        }
+
+        if !self.config_conflicts.is_empty() {
+            return Some(self.config_conflict_dialog());
+        }
+
+        match self.nav.active_data::<Page>()? {
+            Page::Page1 => self.watch.dialog().map(|dialog| dialog.map(Message::Watch)),
+            Page::Page2 => self.counters.dialog().map(|dialog| dialog.map(Message::Counters)),
+            Page::Page3 => self.password.dialog().map(|dialog| dialog.map(Message::Password)),
+            Page::Page4 => self.game.dialog().map(|dialog| dialog.map(Message::Game)),
+            Page::Page5 => self.clipboard.dialog().map(|dialog| dialog.map(Message::Clipboard)),
+            Page::Page6 => self.files.dialog().map(|dialog| dialog.map(Message::Files)),
+        }
+    }
+
+    /// Describes the interface of windows other than the main window.
+    fn view_window(&self, id: window::Id) -> Element<'_, Self::Message> {
+        if self.stopwatch_windows.contains(&id) {
+            return self.watch.detached_view(&self.config).map(Message::Watch);
+        }
+
+        if let Some(&(_, page)) = self.page_windows.iter().find(|(window, _)| *window == id) {
+            return widget::container(self.page_content(page, self.is_narrow(Some(id))))
+                .padding(cosmic::theme::spacing().space_s)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+
+        if let Some(&(_, page)) = self.drawer_windows.iter().find(|(window, _)| *window == id) {
+            let space_s = cosmic::theme::spacing().space_s;
+            let header = widget::row::with_capacity(3)
+                .push(widget::text::title4(page.title()))
+                .push(widget::horizontal_space())
+                .push(widget::button::standard(fl!("move-to-drawer")).on_press(Message::DockContextPage(id)))
+                .align_y(Vertical::Center);
+
+            return widget::column::with_capacity(2)
+                .push(header)
+                .push(widget::scrollable(self.context_page_view(page, self.is_narrow(Some(id)))).height(Length::Fill))
+                .spacing(space_s)
+                .padding(space_s)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into();
+        }
+
+        match self.watch.overlay_view(id) {
+            Some(overlay) => overlay.map(Message::Watch),
+            None => widget::text::body("").into(),
+        }
+    }
+
+    /// Register subscriptions for this application.
+    ///
+    /// Subscriptions are long-running async tasks running in the background which
+    /// emit messages to the application through a channel. They can be dynamically
+    /// stopped and started conditionally based on application state, or persist
+    /// indefinitely.
+    fn subscription(&self) -> Subscription<Self::Message> {
+        Subscription::batch(vec![
+            // Watch for application configuration changes.
+            self.core()
+                .watch_config::<Config>(Self::APP_ID)
+                .map(|update| {
+                    // for why in update.errors {
+                    //     tracing::error!(?why, "app config error");
+                    // }
+
+                    Message::UpdateConfig(update.config)
+                }),
+            // Broadcast timer and counter events on the session bus.
+            dbus::serve().map(Message::DbusReady),
+            // Write configuration changes once they have had a moment to gather.
+            if self.config_unsaved {
+                Subscription::run(|| {
+                    iced_futures::stream::channel(1, |mut emitter| async move {
+                        loop {
+                            tokio::time::sleep(CONFIG_SAVE_DELAY).await;
+                            _ = emitter.send(Message::WriteConfig).await;
+                        }
+                    })
+                })
+            } else {
+                Subscription::none()
+            },
+            // Forward key presses not handled by a widget to the key bindings,
+            // and follow the windows' size and position.
+            event::listen_with(|event, status, window_id| match event {
+                Event::Keyboard(KeyEvent::KeyPressed { key, modifiers, .. })
+                    if status == event::Status::Ignored =>
+                {
+                    Some(Message::Key(modifiers, key))
+                }
+                Event::Window(window::Event::Resized(size)) => Some(Message::WindowResized(window_id, size)),
+                Event::Window(window::Event::Moved(position)) => Some(Message::WindowMoved(window_id, position)),
+                _ => None,
+            }),
+            // Follow the main window across workspaces to show the page last used on each.
+            if self.config.page_per_workspace && self.core.main_window_id().is_some() {
+                workspace::watch(self.app_id.clone().unwrap_or_else(|| Self::APP_ID.to_owned()), fl!("app-title"))
+                    .map(Message::WorkspaceChanged)
+            } else {
+                Subscription::none()
+            },
+            // Each page's own background work, such as timers and animations.
+            self.watch.subscription(&self.config).map(Message::Watch),
+            self.counters.subscription(&self.config).map(Message::Counters),
+            self.password.subscription(&self.config).map(Message::Password),
+            self.game.subscription(&self.config).map(Message::Game),
+            self.clipboard.subscription(&self.config).map(Message::Clipboard),
+            // Files dropped on a window go to the files page only while it is shown.
+            if self.nav.active_data::<Page>() == Some(&Page::Page6)
+                || self.page_windows.iter().any(|(_, page)| *page == Page::Page6)
+            {
+                self.files.subscription(&self.config).map(Message::Files)
+            } else {
+                Subscription::none()
+            },
+        ])
+    }
+
+    /// Handles messages emitted by the application and its widgets.
+    ///
+    /// Tasks may be returned for asynchronous execution of code in the background
+    /// on the application's async runtime.
+    fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
+        match message {
+            Message::Watch(message) => {
+                let (task, effects) = pages::update(&mut self.watch, message, &mut self.config);
+                self.refresh_badges();
+                return self.apply(Some(Page::Page1), effects, task.map(Message::Watch));
+            }
+            Message::Counters(message) => {
+                let (task, effects) = pages::update(&mut self.counters, message, &mut self.config);
+                return self.apply(Some(Page::Page2), effects, task.map(Message::Counters));
+            }
+            Message::Password(message) => {
+                let (task, effects) = pages::update(&mut self.password, message, &mut self.config);
+                return self.apply(Some(Page::Page3), effects, task.map(Message::Password));
+            }
+            Message::Game(message) => {
+                let (task, effects) = pages::update(&mut self.game, message, &mut self.config);
+                self.refresh_badges();
+                return self.apply(Some(Page::Page4), effects, task.map(Message::Game));
+            }
+            Message::Clipboard(message) => {
+                let (task, effects) = pages::update(&mut self.clipboard, message, &mut self.config);
+                return self.apply(Some(Page::Page5), effects, task.map(Message::Clipboard));
+            }
+            Message::Files(message) => {
+                let (task, effects) = pages::update(&mut self.files, message, &mut self.config);
+                return self.apply(Some(Page::Page6), effects, task.map(Message::Files));
+            }
+            Message::ShowToast(text) => return self.show(text).map(cosmic::Action::App),
+
+            Message::CloseToast(id) => {
+                self.toasts.remove(id);
+            }
+            Message::Quit => {
+                self.save_state();
+                self.flush_config();
+                return cosmic::iced::exit();
+            }
+
+            Message::CloseWindow(id) => {
+                if let Some(idx) = self.stopwatch_windows.iter().position(|&window| window == id) {
+                    self.stopwatch_windows.remove(idx);
+                } else if let Some(idx) = self.page_windows.iter().position(|(window, _)| *window == id) {
+                    self.page_windows.remove(idx);
+                } else if let Some(idx) = self.drawer_windows.iter().position(|(window, _)| *window == id) {
+                    self.drawer_windows.remove(idx);
+                } else {
+                    // The daemon keeps running, ready to open a window again when activated.
+                    self.core.set_main_window_id(None);
+                }
+
+                self.narrow_windows.remove(&id);
+                return window::close(id);
+            }
+
+            Message::NewWindow => return self.open_stopwatch_window(),
+
+            Message::NavMenu(action) => match action {
+                NavMenuAction::Reset(page) => {
+                    let messages = match page {
+                        Page::Page1 => vec![
+                            Message::Watch(pages::watch::Message::ResetWatch),
+                            Message::Watch(pages::watch::Message::ResetCountdown),
+                        ],
+                        Page::Page2 => vec![Message::Counters(pages::counters::Message::ResetAll)],
+                        Page::Page3 => vec![Message::Password(pages::password::Message::Generate)],
+                        Page::Page4 => vec![Message::Game(pages::game::Message::NewGame)],
+                        Page::Page5 => vec![Message::Clipboard(pages::clipboard::Message::Clear)],
+                        Page::Page6 => vec![Message::Files(pages::files::Message::Clear)],
+                    };
+
+                    return Task::batch(messages.into_iter().map(|message| self.update(message)));
+                }
+                NavMenuAction::OpenWindow(page) => return self.open_page_window(page),
+                NavMenuAction::Pin(page) => {
+                    let idx = page.index();
+
+                    if let Some(pos) = self.config.pinned_pages.iter().position(|&pinned| pinned == idx) {
+                        self.config.pinned_pages.remove(pos);
+                    } else {
+                        self.config.pinned_pages.push(idx);
+                    }
+
+                    self.arrange_nav();
+                    self.save_config();
+                }
+                NavMenuAction::Hide(page) => return self.update(Message::ShowInNav(page, false)),
+            },
+
+            Message::DragPage(page) => {
+                self.dragging_page = Some(page);
+            }
+
+            // The dragged page takes the place of each page it passes over.
+            Message::DragPageOver(target) => {
+                let Some(page) = self.dragging_page.filter(|&page| page != target) else {
+                    return Task::none();
+                };
+
+                if let Some(to) = self.page_order().iter().position(|&other| other == target) {
+                    self.move_page(page, to);
+                }
+            }
+
+            Message::DropPage => {
+                if self.dragging_page.take().is_some() {
+                    self.save_config();
+                }
+            }
+
+            Message::MovePage(page, offset) => {
+                let order = self.page_order();
+
+                let to = order
+                    .iter()
+                    .position(|&other| other == page)
+                    .and_then(|from| from.checked_add_signed(offset))
+                    .filter(|&to| to < order.len());
+
+                if let Some(to) = to {
+                    self.move_page(page, to);
+                    self.save_config();
+                }
+            }
+
+            Message::ShowInNav(page, show) => {
+                let idx = page.index();
+
+                if show {
+                    self.config.hidden_pages.retain(|&hidden| hidden != idx);
+                } else if !self.config.hidden_pages.contains(&idx) {
+                    // At least one page always stays in the nav bar.
+                    if self.config.hidden_pages.len() + 1 >= Page::ALL.len() {
+                        return Task::none();
+                    }
+
+                    self.config.hidden_pages.push(idx);
+                }
+
+                self.arrange_nav();
+                self.save_config();
+                return self.update_title();
+            }
+
+            Message::RestoreNav => {
+                self.config.pinned_pages.clear();
+                self.config.nav_order.clear();
+                self.config.hidden_pages.clear();
+                self.arrange_nav();
+                self.save_config();
+                return self.update_title();
+            }
+
+            Message::WindowResized(id, size) => {
+                let narrow = layout::is_narrow(size.width);
+                let crossed = if narrow {
+                    self.narrow_windows.insert(id)
+                } else {
+                    self.narrow_windows.remove(&id)
+                };
+
+                if self.core.main_window_id() == Some(id) {
+                    self.window_width = size.width;
+
+                    // Entering the narrow layout hides the nav bar, leaving the
+                    // page dropdown in the header to navigate with.
+                    if crossed && narrow {
+                        self.core.nav_bar_set_toggled_condensed(false);
+                    }
+
+                    if self.config.auto_condense_nav {
+                        self.refresh_badges();
+                    }
+
+                    self.config.window_size = Some((size.width.round() as u32, size.height.round() as u32));
+                    return self.save_window_geometry();
+                }
+            }
+
+            Message::WindowMoved(id, position) => {
+                if self.core.main_window_id() == Some(id) {
+                    self.config.window_position = Some((position.x.round() as i32, position.y.round() as i32));
+                    return self.save_window_geometry();
+                }
+            }
+
+            Message::SaveWindowGeometry(generation) => {
+                if generation == self.geometry_generation {
+                    self.save_config();
+                }
+            }
+            Message::WriteConfig => self.write_config(),
+            Message::ActivatePage(page) => {
+                let id = self
+                    .nav
+                    .iter()
+                    .find(|&id| self.nav.data::<Page>(id) == Some(&page));
+
+                if let Some(id) = id {
+                    return self.on_nav_select(id);
+                }
+            }
+
+            Message::CancelTask(page) => self.cancel_tasks(page),
+
+            Message::TaskFinished(page, id) => self.tasks.finish(&page, id),
+
+            Message::Transferred(transferred) => self.transfer = Some(transferred).filter(|t| !t.ended),
+
+            Message::CancelTransfer => {
+                if let Some(transfer) = self.transfer.take() {
+                    transfer.cancel.cancel();
+                }
+            }
+
+            Message::ToggleContextPage(context_page) => {
+                // A page in its own window is brought forward rather than shown twice.
+                if let Some(&(id, _)) = self.drawer_windows.iter().find(|(_, page)| *page == context_page) {
+                    return window::gain_focus(id);
+                }
+
+                if self.context_page == context_page {
+                    // Close the context drawer if the toggled context page is the same.
+                    self.core.window.show_context = !self.core.window.show_context;
+                } else {
+                    // Open the context drawer to display the requested context page.
+                    self.context_page = context_page;
+                    self.core.window.show_context = true;
+                }
+            }
+
+            Message::PopOutContextPage(context_page) => {
+                self.core.window.show_context = false;
+                return self.open_drawer_window(context_page);
+            }
+
+            Message::DockContextPage(id) => {
+                if let Some(idx) = self.drawer_windows.iter().position(|(window, _)| *window == id) {
+                    let (_, context_page) = self.drawer_windows.remove(idx);
+                    self.context_page = context_page;
+                    self.core.window.show_context = true;
+                    return window::close(id);
+                }
+            }
+
+            Message::ToggleShortcuts => {
+                self.show_shortcuts = !self.show_shortcuts;
+            }
+
+            Message::ToggleQuickSettings => {
+                self.show_quick_settings = !self.show_quick_settings;
+            }
+
+            Message::ShowAllSettings => {
+                self.show_quick_settings = false;
+                self.context_page = ContextPage::Settings;
+                self.core.window.show_context = true;
+            }
+
+            Message::MuteSounds(muted) => {
+                self.config.mute_sounds = muted;
+                self.save_config();
+            }
+
+            Message::Key(modifiers, key) => {
+                // While a menu is open from the keyboard, keys move through it.
+                if let Some(focus) = self.menu_focus {
+                    let menus = self.menu_labels();
+
+                    match &key {
+                        Key::Named(Named::Escape | Named::F10) => self.menu_focus = None,
+                        Key::Named(Named::ArrowLeft) => self.menu_focus = Some(focus.move_menu(-1, &menus)),
+                        Key::Named(Named::ArrowRight) => self.menu_focus = Some(focus.move_menu(1, &menus)),
+                        Key::Named(Named::ArrowUp) => self.menu_focus = Some(focus.move_entry(-1, &menus)),
+                        Key::Named(Named::ArrowDown) => self.menu_focus = Some(focus.move_entry(1, &menus)),
+                        Key::Named(Named::Enter | Named::Space) => {
+                            return self.update(Message::ActivateMenuEntry(focus.menu, focus.entry));
+                        }
+                        Key::Character(text) => {
+                            let letter = text.chars().next();
+
+                            // Alt with a letter switches menus, and the letter alone picks an entry.
+                            if modifiers.alt() {
+                                if let Some(menu) = letter.and_then(|letter| self.menu_with_mnemonic(letter)) {
+                                    return self.update(Message::OpenMenu(menu));
+                                }
+                            } else if let Some(focus) = letter.and_then(|letter| focus.jump(letter, &menus)) {
+                                self.menu_focus = Some(focus);
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    return Task::none();
+                }
+
+                if key == Key::Named(Named::F10) && !modifiers.control() && !modifiers.alt() {
+                    return self.update(Message::OpenMenu(0));
+                }
+
+                // Alt with a menu's first letter opens it, unless a key binding claims the key.
+                if let Key::Character(text) = &key {
+                    let bound = self.key_binds.keys().any(|bind| bind.matches(modifiers, &key));
+
+                    if modifiers.alt() && !modifiers.control() && !bound {
+                        if let Some(menu) = text.chars().next().and_then(|letter| self.menu_with_mnemonic(letter)) {
+                            return self.update(Message::OpenMenu(menu));
+                        }
+                    }
+                }
+
+                // The palette's input takes typed text, leaving keys for moving through the matches.
+                if self.palette.is_some() {
+                    let len = self.palette.as_ref().map_or(0, |palette| palette.filter(self.palette_commands()).len());
+
+                    match key {
+                        Key::Named(Named::Escape) => {
+                            self.palette = None;
+                            return Task::none();
+                        }
+                        Key::Named(Named::ArrowDown | Named::ArrowUp) => {
+                            let offset = if key == Key::Named(Named::ArrowDown) { 1 } else { -1 };
+
+                            if let Some(palette) = &mut self.palette {
+                                palette.move_selection(offset, len);
+                            }
+
+                            return Task::none();
+                        }
+                        _ => {}
+                    }
+                }
+
+                // `?` needs Shift on most layouts, so it is matched on the character alone.
+                if key == Key::Character("?".into())
+                    && !modifiers.control()
+                    && !modifiers.alt()
+                    && self.palette.is_none()
+                {
+                    self.show_shortcuts = !self.show_shortcuts;
+                    return Task::none();
+                }
+
+                if self.show_shortcuts && key == Key::Named(Named::Escape) {
+                    self.show_shortcuts = false;
+                    return Task::none();
+                }
+
+                let active = self.nav.active_data::<Page>().copied();
+
+                // A counter selected on its page takes the arrow keys and typed numbers.
+                if active == Some(Page::Page2) && !modifiers.control() && !modifiers.alt() {
+                    if let Some(message) = self.counters.key(&key) {
+                        return self.update(Message::Counters(message));
+                    }
+                }
+
+                for (key_bind, action) in &self.key_binds {
+                    if key_bind.matches(modifiers, &key)
+                        && action.page().is_none_or(|page| Some(page) == active)
+                    {
+                        return self.update(action.message());
+                    }
+                }
+            }
+
+            Message::OpenMenu(menu) => {
+                self.menu_focus = Some(MenuFocus::open(menu, &self.menu_labels()));
+                self.palette = None;
+                self.show_shortcuts = false;
+            }
+
+            Message::CloseMenu => self.menu_focus = None,
+
+            Message::ActivateMenuEntry(menu, entry) => {
+                self.menu_focus = None;
+
+                if let Some(action) = self.menu_action(MenuFocus { menu, entry }) {
+                    return self.update(action.message());
+                }
+            }
+
+            Message::TogglePalette => {
+                if self.palette.take().is_none() {
+                    self.palette = Some(Palette::default());
+                    self.show_shortcuts = false;
+                    return widget::text_input::focus(command_palette::PALETTE_INPUT.clone());
+                }
+            }
+
+            Message::PaletteInput(query) => {
+                if let Some(palette) = &mut self.palette {
+                    palette.set_query(query);
+                }
+            }
+
+            Message::PaletteRun(idx) => {
+                let Some(palette) = self.palette.take() else {
+                    return Task::none();
+                };
+
+                if let Some((_, command)) = palette.filter(self.palette_commands()).into_iter().nth(idx) {
+                    return self.update(command.message());
+                }
+            }
+
+            Message::UpdateConfig(config) => {
+                // Changes made here that are not yet on disk are kept over the new version.
+                let merge = self.config.merge(&self.config_base, &config);
+                let mut merged = merge.config;
+
+                for field in merge.conflicts {
+                    merged.copy_field(&self.config, field);
+
+                    if !self.config_conflicts.contains(&field) {
+                        self.config_conflicts.push(field);
+                    }
+                }
+
+                let unsaved = merged != config;
+                self.config_base = config;
+                let changes = self.config.diff(&merged);
+                self.config = merged;
+
+                if unsaved {
+                    self.save_config();
+                }
+
+                // Let each change be handled on its own rather than reapplying everything.
+                return Task::batch(
+                    changes
+                        .into_iter()
+                        .map(|field| cosmic::task::message(Message::ConfigChanged(field))),
+                );
+            }
+
+            Message::ResolveConfigConflicts(keep_mine) => {
+                let conflicts = std::mem::take(&mut self.config_conflicts);
+
+                if keep_mine {
+                    self.save_config();
+                } else {
+                    // The other instance's values are those last read or written.
+                    for &field in &conflicts {
+                        self.config.copy_field(&self.config_base, field);
+                    }
+
+                    return Task::batch(
+                        conflicts
+                            .into_iter()
+                            .map(|field| cosmic::task::message(Message::ConfigChanged(field))),
+                    );
+                }
+            }
+
+            Message::ConfigChanged(field) => match field {
+                ConfigField::HighContrast => contrast::set_forced(self.config.high_contrast),
+                ConfigField::ReduceMotion => animation::set_reduced_motion(self.config.reduce_motion),
+                ConfigField::CondensedNav | ConfigField::AutoCondenseNav | ConfigField::WatchFormat => {
+                    self.refresh_badges();
+                }
+                ConfigField::PinnedPages | ConfigField::NavOrder | ConfigField::HiddenPages => self.arrange_nav(),
+                ConfigField::Language => return self.relabel(),
+                ConfigField::KeyBinds => self.key_binds = menus::key_binds(&self.config.key_binds),
+                ConfigField::AppTheme => {
+                    if !self.custom_theme {
+                        return cosmic::command::set_theme(self.config.app_theme.theme());
+                    }
+                }
+                // The other fields are read from the configuration each time they are used.
+                _ => {}
+            },
+
+            Message::ExportReport => {
+                let report = self.report();
+
+                return Task::future(io::choose_save(fl!("export-report"), "report.md"))
+                    .then(move |chosen| match chosen {
+                        Ok(Some(path)) => {
+                            let contents = report.render(report::Format::from_path(&path));
+
+                            io::task(io::write(path.clone(), contents), Message::Transferred, move |result| {
+                                Message::ReportExported(result.transpose().map(|done| done.map(|()| path.clone())))
+                            })
+                        }
+                        Ok(None) => Task::none(),
+                        Err(why) => Task::done(Message::ReportExported(Err(why))),
+                    })
+                    .map(cosmic::Action::App);
+            }
+
+            Message::ReportExported(result) => match result {
+                Ok(None) => {}
+                Ok(Some(path)) => {
+                    return self.update(Message::ShowToast(fl!("saved-to", path = path.display().to_string())));
+                }
+                Err(why) => return self.fail(fl!("report-export-failed", reason = why.to_string()), why),
+            },
+
+            Message::PrintReport => return self.print(self.report().to_document()),
+
+            Message::PrintActivity => return self.print(self.activity_document()),
+
+            Message::Printed(result) => {
+                if let Err(why) = result {
+                    return self.fail(fl!("print-failed", reason = why.to_string()), why);
+                }
+            }
+
+            Message::ExportPageImage => {
+                if let Some(id) = self.core.main_window_id() {
+                    // The window is captured whole, then cut down to where the page is drawn.
+                    return cosmic::iced::widget::container::visible_bounds(PAGE_CONTENT.clone())
+                        .then(move |bounds| window::screenshot(id).map(move |shot| Message::PageCaptured(shot, bounds)))
+                        .map(cosmic::Action::App);
+                }
+            }
+
+            Message::PageCaptured(screenshot, bounds) => {
+                let file_name = self
+                    .nav
+                    .active_data::<Page>()
+                    .map_or_else(|| "page.png".to_owned(), |page| format!("page{}.png", page.index() + 1));
+
+                return Task::future(io::choose_save(fl!("export-page-image"), file_name))
+                    .then(move |chosen| {
+                        let path = match chosen {
+                            Ok(Some(path)) => path,
+                            Ok(None) => return Task::none(),
+                            Err(why) => return Task::done(Message::PageImageExported(Err(why))),
+                        };
+
+                        let page = snapshot::crop(&screenshot, bounds);
+
+                        let image = match snapshot::encode_png(&page) {
+                            Ok(image) => image,
+                            Err(why) => return Task::done(Message::PageImageExported(Err(why))),
+                        };
+
+                        io::task(io::write(path.clone(), image), Message::Transferred, move |result| {
+                            Message::PageImageExported(result.transpose().map(|done| done.map(|()| path.clone())))
+                        })
+                    })
+                    .map(cosmic::Action::App);
+            }
+
+            Message::PageImageExported(result) => match result {
+                Ok(None) => {}
+                Ok(Some(path)) => {
+                    return self.update(Message::ShowToast(fl!("saved-to", path = path.display().to_string())));
+                }
+                Err(why) => return self.fail(fl!("page-image-failed", reason = why.to_string()), why),
+            },
+
+            Message::BackUp => {
+                // The archive is made from what is on disk.
+                self.write_config();
+                let file_name = format!("counter-backup-{}.zip", chrono::Local::now().format("%Y-%m-%d"));
+
+                return Task::future(io::choose_save(fl!("back-up"), file_name))
+                    .then(move |chosen| match chosen {
+                        Ok(Some(path)) => {
+                            let target = path.clone();
+                            let create = io::blocking(move |tracker| backup::create(&target, tracker));
+
+                            io::task(create, Message::Transferred, move |result| {
+                                Message::BackedUp(result.transpose().map(|done| done.map(|()| path.clone())))
+                            })
+                        }
+                        Ok(None) => Task::none(),
+                        Err(why) => Task::done(Message::BackedUp(Err(why))),
+                    })
+                    .map(cosmic::Action::App);
+            }
+
+            Message::BackedUp(result) => match result {
+                Ok(None) => {}
+                Ok(Some(path)) => {
+                    return self.update(Message::ShowToast(fl!("saved-to", path = path.display().to_string())));
+                }
+                Err(why) => return self.fail(fl!("backup-failed", reason = why.to_string()), why),
+            },
+
+            Message::ChooseBackup => {
+                return cosmic::task::future(async move {
+                    let dialog = file_chooser::open::Dialog::new().title(fl!("restore-backup"));
+
+                    let path = match dialog.open_file().await {
+                        Ok(response) => response.url().to_file_path().ok(),
+                        Err(file_chooser::Error::Cancelled) => return Message::BackupChosen(None),
+                        Err(why) => return Message::BackupChosen(Some(Err(Error::dialog(why)))),
+                    };
+
+                    let Some(path) = path else {
+                        return Message::BackupChosen(None);
+                    };
+
+                    let check = move || backup::check(&path).map(|manifest| (path, manifest));
+                    let result = tokio::task::spawn_blocking(check)
+                        .await
+                        .unwrap_or_else(|why| Err(Error::new(Code::Io, why.to_string())));
+
+                    Message::BackupChosen(Some(result))
+                });
+            }
+
+            Message::BackupChosen(result) => match result {
+                None => {}
+                Some(Ok(backup)) => self.restoring = Some(backup),
+                Some(Err(why)) => return self.fail(fl!("restore-failed", reason = why.to_string()), why),
+            },
+
+            Message::CancelRestore => {
+                self.restoring = None;
+            }
+
+            Message::ConfirmRestore => {
+                let Some((path, _)) = self.restoring.take() else {
+                    return Task::none();
+                };
+
+                self.write_config();
+                let restore = io::blocking(move |tracker| backup::restore(&path, tracker));
+                return io::task(restore, Message::Transferred, Message::Restored).map(cosmic::Action::App);
+            }
+
+            Message::Restored(result) => match result {
+                None => {}
+                Some(Ok(())) => return self.reload(),
+                Some(Err(why)) => return self.fail(fl!("restore-failed", reason = why.to_string()), why),
+            },
+
+            Message::ToggleHeaderImages => {
+                self.config.hide_header_images = !self.config.hide_header_images;
+                self.save_config();
+            }
+
+            Message::ToggleHighContrast => {
+                self.config.high_contrast = !self.config.high_contrast;
+                contrast::set_forced(self.config.high_contrast);
+                self.save_config();
+            }
+
+            Message::ToggleReduceMotion => {
+                self.config.reduce_motion = !self.config.reduce_motion;
+                animation::set_reduced_motion(self.config.reduce_motion);
+                self.save_config();
+            }
+
+            Message::ToggleCondensedNav => {
+                self.config.condensed_nav = !self.config.condensed_nav;
+                self.refresh_badges();
+                self.save_config();
+            }
+
+            Message::AutoCondenseNav(enabled) => {
+                self.config.auto_condense_nav = enabled;
+                self.refresh_badges();
+                self.save_config();
+            }
+
+            Message::StartPage(page) => {
+                self.config.start_page = page;
+                self.save_config();
+            }
+
+            Message::StartMinimized(minimized) => {
+                self.config.start_minimized = minimized;
+                self.save_config();
+            }
+
+            Message::PagePerWorkspace(enabled) => {
+                self.config.page_per_workspace = enabled;
+
+                if !enabled {
+                    self.workspace = None;
+                    self.config.workspace_pages.clear();
+                }
+
+                self.save_config();
+            }
+
+            Message::WorkspaceChanged(name) => {
+                let page = self.config.workspace_pages.get(&name).and_then(|&idx| Page::ALL.get(idx)).copied();
+                self.workspace = Some(name);
+
+                // A workspace the window has not been on yet keeps the page shown.
+                match page {
+                    Some(page) => return self.update(Message::ActivatePage(page)),
+                    None => self.remember_workspace_page(),
+                }
+            }
+
+            Message::ResumeTimers(resume) => {
+                self.config.resume_timers = resume;
+                self.save_config();
+            }
+
+            Message::DesktopNotifications(enabled) => {
+                self.config.desktop_notifications = enabled;
+                self.save_config();
+            }
+
+            Message::SelectSound(event, idx) => match Sound::BUILT_IN.get(idx) {
+                Some(sound) => {
+                    self.config.sounds.insert(event, sound.clone());
+                    self.save_config();
+                }
+                // The entry after the built-in sounds picks a file.
+                None => {
+                    return cosmic::task::future(async move {
+                        let dialog = file_chooser::open::Dialog::new().title(fl!("choose-sound-file"));
+
+                        match dialog.open_file().await {
+                            Ok(response) => {
+                                Message::SoundFileChosen(event, response.url().to_file_path().ok().map(Ok))
+                            }
+                            Err(file_chooser::Error::Cancelled) => Message::SoundFileChosen(event, None),
+                            Err(why) => Message::SoundFileChosen(event, Some(Err(Error::dialog(why)))),
+                        }
+                    });
+                }
+            },
+
+            Message::SoundFileChosen(event, result) => match result {
+                None => {}
+                Some(Ok(path)) => {
+                    self.config.sounds.insert(event, Sound::File(path));
+                    self.save_config();
+                    return self.update(Message::PreviewSound(event));
+                }
+                Some(Err(why)) => return self.fail(fl!("sound-failed", reason = why.to_string()), why),
+            },
+
+            Message::PreviewSound(event) => {
+                let sound = self.config.sounds.get(&event).cloned().unwrap_or_default();
+                return cosmic::task::future(async move { Message::SoundPlayed(audio::play(sound).await) });
+            }
+
+            Message::SoundPlayed(result) => {
+                if let Err(why) = result {
+                    return self.fail(fl!("sound-failed", reason = why.to_string()), why);
+                }
+            }
+
+            Message::AppTheme(theme) => {
+                self.config.app_theme = theme;
+                self.save_config();
+
+                if !self.custom_theme {
+                    return cosmic::command::set_theme(theme.theme());
+                }
+            }
+
+            Message::WatchFormat(format) => {
+                self.config.watch_format = format;
+                self.refresh_badges();
+                self.save_config();
+            }
+
+            Message::ExplicitFeedback(enabled) => {
+                self.config.explicit_feedback = enabled;
+                self.save_config();
+            }
+
+            Message::Language(idx) => {
+                let language = idx.and_then(|idx| self.languages.get(idx));
+                self.config.language = language.map(ToString::to_string);
+                self.save_config();
+                return self.relabel();
+            }
+
+            Message::ContextDrawerWidth(width) => {
+                self.config.context_drawer_width = width;
+                self.save_config();
+            }
+
+            Message::ClearUsage => {
+                self.usage.clear();
+            }
+
+            Message::UndoRemoval(key) => {
+                if let Some(idx) = self.pending_removals.iter().position(|(k, _)| *k == key) {
+                    let (_, removed) = self.pending_removals.remove(idx);
+                    removed.restore(&mut self.config);
+                    self.save_config();
+                }
+            }
+
+            Message::ForgetRemoval(key) => {
+                // The grace period is over, so the removal is written to disk at last.
+                self.pending_removals.retain(|(k, _)| *k != key);
+                self.save_config();
+            }
+
+            Message::Undo => return self.step_history(true),
+
+            Message::Redo => return self.step_history(false),
+
+            Message::ChooseHeaderImage => {
+                let Some(page) = self.nav.active_data::<Page>().map(|page| page.index()) else {
+                    return Task::none();
+                };
+
+                return cosmic::task::future(async move {
+                    let dialog = file_chooser::open::Dialog::new().title(fl!("choose-header-image"));
+
+                    let path = match dialog.open_file().await {
+                        Ok(response) => response.url().to_file_path().ok(),
+                        Err(file_chooser::Error::Cancelled) => return Message::HeaderImageChosen(page, None),
+                        Err(why) => return Message::HeaderImageChosen(page, Some(Err(Error::dialog(why)))),
+                    };
+
+                    let Some(path) = path else {
+                        return Message::HeaderImageChosen(page, None);
+                    };
+
+                    Message::HeaderImageChosen(
+                        page,
+                        Some(headers::import(page, &path).await.map_err(Error::from)),
+                    )
+                });
+            }
+
+            Message::HeaderImageChosen(page, result) => match result {
+                None => {}
+                Some(Ok(path)) => {
+                    let old = self.config.header_images.insert(page, path.clone());
+
+                    // The image it replaces is removed, but never the one just copied.
+                    if let Some(old) = old.filter(|old| *old != path) {
+                        _ = std::fs::remove_file(old);
+                    }
+                    self.config.hide_header_images = false;
+                    self.save_config();
+                }
+                Some(Err(why)) => {
+                    return self.fail(fl!("header-image-failed", reason = why.to_string()), why);
+                }
+            },
+
+            Message::ResetHeaderImage => {
+                let page = self.nav.active_data::<Page>().map(|page| page.index());
+
+                if let Some(old) = page.and_then(|page| self.config.header_images.remove(&page)) {
+                    _ = std::fs::remove_file(old);
+                    self.save_config();
+                }
+            }
+
+            Message::ImportTheme => {
+                return Task::future(io::choose_open(fl!("import-theme")))
+                    .then(|chosen| {
+                        let path = match chosen {
+                            Ok(Some(path)) => path,
+                            Ok(None) => return Task::none(),
+                            Err(why) => return Task::done(Message::ThemeImported(Some(Err(why)))),
+                        };
+
+                        let name = path
+                            .file_stem()
+                            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+
+                        io::task(io::read_to_string(path, MAX_THEME_SIZE), Message::Transferred, move |result| {
+                            Message::ThemeImported(result.map(|result| {
+                                result
+                                    .and_then(|text| load_theme(&text))
+                                    .map(|theme| (name.clone(), theme))
+                            }))
+                        })
+                    })
+                    .map(cosmic::Action::App);
+            }
+
+            Message::ThemeImported(result) => match result {
+                None => {}
+                Some(Ok(theme)) => {
+                    self.theme_modes = Some(theme_modes(&theme.1));
+                    self.theme_preview = Some(theme);
+                    self.context_page = ContextPage::Theme;
+                    self.core.window.show_context = true;
+                }
+                Some(Err(why)) => {
+                    return self.fail(fl!("theme-import-failed", reason = why.to_string()), why);
+                }
+            },
+
+            Message::ApplyTheme => {
+                if let Some((_, theme)) = &self.theme_preview {
+                    self.custom_theme = true;
+                    return cosmic::command::set_theme(cosmic::Theme::custom(Arc::new(theme.clone())));
+                }
+            }
+
+            Message::ThemeSample(on) => self.theme_sample_on = on,
+
+            Message::UseSystemTheme => {
+                self.custom_theme = false;
+                return cosmic::command::set_theme(self.config.app_theme.theme());
+            }
+
+            Message::DbusReady(sender) => {
+                self.dbus = Some(sender);
+            }
+
+            Message::LaunchUrl(url) => match open::that_detached(&url) {
+                Ok(()) => {}
+                Err(err) => {
+                    eprintln!("failed to open {url:?}: {err}");
+                }
+            },
+        }
+        Task::none()
+    }
+
+    /// Called when a nav item is selected.
+    fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<cosmic::Action<Self::Message>> {
+        if self.nav.active() != id {
+            // Stop any work the page being left was doing.
+            if let Some(&page) = self.nav.active_data::<Page>() {
+                self.cancel_tasks(page);
+            }
+
+            if let Some(page) = self.nav.data::<Page>(id) {
+                self.usage.record(usage::Event::Visit(page.index()));
+            }
+        }
+
+        // Activate the page in the model.
+        self.nav.activate(id);
+        self.remember_workspace_page();
+
+        self.update_title()
+    }
+}
+
+impl AppModel {
+    /// Position in `languages` of the configured language, if one is chosen.
+    fn language_index(&self) -> Option<usize> {
+        let tag = self.config.language.as_deref()?;
+        self.languages.iter().position(|language| language.to_string() == tag)
+    }
+
+    /// Applies the configured language, building every translated label again.
+    fn relabel(&mut self) -> Task<cosmic::Action<Message>> {
+        let language = self.language_index().map(|idx| self.languages[idx].clone());
+        i18n::select(language.as_ref());
+
+        self.about = about();
+        self.page_names = page_names();
+        self.name_switcher_pages();
+        self.drawer_width_labels = settings::drawer_width_labels();
+        self.app_theme_labels = settings::app_theme_labels();
+        self.watch_format_labels = settings::watch_format_labels();
+        self.sound_labels = settings::sound_labels();
+        self.language_labels = settings::language_labels(&self.languages);
+
+        self.label_nav(true);
+
+        self.watch.relabel();
+        self.counters.relabel();
+        self.password.relabel();
+        self.game.relabel();
+        self.clipboard.relabel();
+        self.files.relabel();
+
+        self.update_title()
+    }
+
+    /// Opens the main window, with the app ID given on the command line if any.
+    fn open_window(&mut self) -> Task<cosmic::Action<Message>> {
+        let mut settings = window::Settings {
+            // The header bar draws the window's decorations, as in the runtime's own main window.
+            decorations: false,
+            transparent: true,
+            min_size: Some(cosmic::iced::Size::new(360.0, 180.0)),
+            platform_specific: window::settings::PlatformSpecific {
+                application_id: self.app_id.clone().unwrap_or_else(|| Self::APP_ID.to_owned()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Open the window as it was left.
+        if let Some((width, height)) = self.config.window_size {
+            settings.size = cosmic::iced::Size::new(width as f32, height as f32);
+        }
+
+        if let Some((x, y)) = self.config.window_position {
+            settings.position = window::Position::Specific(cosmic::iced::Point::new(x as f32, y as f32));
+        }
+
+        let (id, open) = window::open(settings);
+        self.core.set_main_window_id(Some(id));
+
+        Task::batch([open.discard(), self.update_title()])
+    }
+
+    /// Saves the page shown as the one to return to on the current workspace.
+    fn remember_workspace_page(&mut self) {
+        let (Some(workspace), Some(page)) = (&self.workspace, self.nav.active_data::<Page>()) else {
+            return;
+        };
+
+        if self.config.workspace_pages.get(workspace) != Some(&page.index()) {
+            self.config.workspace_pages.insert(workspace.clone(), page.index());
+            self.save_config();
+        }
+    }
+
+    /// Saves the window's size and position once resizing or moving it pauses,
+    /// rather than on every step.
+    fn save_window_geometry(&mut self) -> Task<cosmic::Action<Message>> {
+        self.geometry_generation += 1;
+        let generation = self.geometry_generation;
+
+        cosmic::task::future(async move {
+            tokio::time::sleep(filter::DEBOUNCE).await;
+            Message::SaveWindowGeometry(generation)
+        })
+    }
+
+    /// Opens another window showing only the stopwatch, which keeps running
+    /// with the main window.
+    fn open_stopwatch_window(&mut self) -> Task<cosmic::Action<Message>> {
+        let settings = window::Settings {
+            size: cosmic::iced::Size::new(320.0, 180.0),
+            min_size: Some(cosmic::iced::Size::new(240.0, 140.0)),
+            platform_specific: window::settings::PlatformSpecific {
+                application_id: self.app_id.clone().unwrap_or_else(|| Self::APP_ID.to_owned()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (id, open) = window::open(settings);
+        self.stopwatch_windows.push(id);
+
+        Task::batch([open.discard(), self.set_window_title(fl!("stopwatch"), id)])
+    }
+
+    /// Opens another window showing only `page`, which stays in step with the main window.
+    fn open_page_window(&mut self, page: Page) -> Task<cosmic::Action<Message>> {
+        let settings = window::Settings {
+            size: cosmic::iced::Size::new(480.0, 560.0),
+            min_size: Some(cosmic::iced::Size::new(360.0, 180.0)),
+            platform_specific: window::settings::PlatformSpecific {
+                application_id: self.app_id.clone().unwrap_or_else(|| Self::APP_ID.to_owned()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (id, open) = window::open(settings);
+        self.page_windows.push((id, page));
+
+        let title = format!("{} — {}", fl!("app-title"), self.page_names[page.index()]);
+        Task::batch([open.discard(), self.set_window_title(title, id)])
+    }
+
+    /// Opens a window showing `page` instead of the context drawer, or brings
+    /// forward the one already showing it.
+    fn open_drawer_window(&mut self, page: ContextPage) -> Task<cosmic::Action<Message>> {
+        if let Some(&(id, _)) = self.drawer_windows.iter().find(|(_, shown)| *shown == page) {
+            return window::gain_focus(id);
+        }
+
+        let settings = window::Settings {
+            size: cosmic::iced::Size::new(420.0, 640.0),
+            min_size: Some(cosmic::iced::Size::new(320.0, 240.0)),
+            platform_specific: window::settings::PlatformSpecific {
+                application_id: self.app_id.clone().unwrap_or_else(|| Self::APP_ID.to_owned()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (id, open) = window::open(settings);
+        self.drawer_windows.push((id, page));
+
+        let title = format!("{} — {}", fl!("app-title"), page.title());
+        Task::batch([open.discard(), self.set_window_title(title, id)])
+    }
+
+    /// Orders the nav bar with the pinned pages first, in the order they were
+    /// pinned, and a divider before the rest in the configured order. Hidden
+    /// pages are left out.
+    fn arrange_nav(&mut self) {
+        let hidden = |page: &Page| self.config.hidden_pages.contains(&page.index());
+        let mut order: Vec<Page> = Vec::with_capacity(Page::ALL.len());
+
+        for page in self.config.pinned_pages.iter().filter_map(|&idx| Page::ALL.get(idx)) {
+            if !order.contains(page) && !hidden(page) {
+                order.push(*page);
+            }
+        }
+
+        let pinned = order.len();
+        let rest: Vec<Page> = self
+            .page_order()
+            .into_iter()
+            .filter(|page| !order.contains(page) && !hidden(page))
+            .collect();
+        order.extend(rest);
+
+        // A configuration hiding every page, as written by hand, still shows them all.
+        if order.is_empty() {
+            order = self.page_order();
+        }
+
+        let ids: Vec<_> = self.nav.iter().collect();
+
+        for id in ids {
+            if self.nav.data::<Page>(id).is_none_or(|page| !order.contains(page)) {
+                self.nav.remove(id);
+            }
+        }
+
+        for (position, &page) in order.iter().enumerate() {
+            let id = match self.nav_id(page) {
+                Some(id) => id,
+                None => self
+                    .nav
+                    .insert()
+                    .text(self.page_names[page.index()].clone())
+                    .data::<Page>(page)
+                    .icon(icons::icon(page.icon()))
+                    .id(),
+            };
+
+            self.nav.position_set(id, position as u16);
+            self.nav.divider_above_set(id, pinned > 0 && position == pinned);
+        }
+
+        // Hiding the page being shown moves to the first page left.
+        if self.nav.active_data::<Page>().is_none() {
+            if let Some(id) = order.first().and_then(|&page| self.nav_id(page)) {
+                self.nav.activate(id);
+            }
+        }
+
+        self.switcher_pages = order;
+        self.name_switcher_pages();
+        self.refresh_badges();
+    }
+
+    /// Titles the header bar's page switcher lists, for the pages arranged in the nav bar.
+    fn name_switcher_pages(&mut self) {
+        self.switcher_names = self.switcher_pages.iter().map(|page| self.page_names[page.index()].clone()).collect();
+    }
+
+    /// Shows each page's live status after its name in the nav bar.
+    fn refresh_badges(&mut self) {
+        self.label_nav(false);
+    }
+
+    /// Labels the nav bar's items with their pages' names and live status, or
+    /// leaves only their icons while the nav bar is condensed.
+    ///
+    /// The status is kept on the item as a [`NavBadge`], next to its [`Page`],
+    /// so the item's text is only set again when the status changes, or for
+    /// every item with `all` set, as after switching languages.
+    fn label_nav(&mut self, all: bool) {
+        let ids: Vec<_> = self.nav.iter().collect();
+        let condensed = self.nav_condensed();
+
+        // Every item's text changes when the nav bar condenses or expands.
+        let relabel = all || condensed != self.condensed;
+        self.condensed = condensed;
+
+        for id in ids {
+            let Some(page) = self.nav.data::<Page>(id).copied() else {
+                continue;
+            };
+
+            let badge = NavBadge(self.badge(page));
+
+            if !relabel && self.nav.data::<NavBadge>(id) == Some(&badge) {
+                continue;
+            }
+
+            let name = self.page_names[page.index()].clone();
+
+            let text = match &badge.0 {
+                _ if condensed => String::new(),
+                Some(status) => fl!("nav-badge", name = name, status = status.as_str()),
+                None => name,
+            };
+
+            self.nav.text_set(id, text);
+            self.nav.data_set(id, badge);
+        }
+    }
+
+    /// Whether the nav bar shows only its items' icons, as chosen or because the window is narrow.
+    fn nav_condensed(&self) -> bool {
+        let narrow = self.window_width > 0.0 && self.window_width < layout::CONDENSED_NAV_WIDTH;
+        self.config.condensed_nav || (self.config.auto_condense_nav && narrow)
+    }
+
+    /// The live status of `page`: the stopwatch's time once started, and the
+    /// attempts made in the current game once guessing has begun.
+    fn badge(&self, page: Page) -> Option<String> {
+        match page {
+            Page::Page1 => self
+                .watch
+                .stopwatch()
+                .map(timer::Timer::elapsed)
+                .filter(|elapsed| !elapsed.is_zero())
+                .map(|elapsed| self.config.watch_format.format(elapsed)),
+            Page::Page4 => {
+                let attempts = self.game.attempts();
+                (attempts > 0).then(|| fl!("nav-badge-attempts", attempts = attempts))
+            }
+            Page::Page2 | Page::Page3 | Page::Page5 | Page::Page6 => None,
+        }
+    }
+
+    /// Every page in the configured order, before pinned pages are moved to the top.
+    fn page_order(&self) -> Vec<Page> {
+        let mut order: Vec<Page> = Vec::with_capacity(Page::ALL.len());
+
+        for page in self.config.nav_order.iter().filter_map(|&idx| Page::ALL.get(idx)) {
+            if !order.contains(page) {
+                order.push(*page);
+            }
+        }
+
+        let rest: Vec<Page> = Page::ALL.into_iter().filter(|page| !order.contains(page)).collect();
+        order.extend(rest);
+        order
+    }
+
+    /// Moves `page` to position `to` of the configured order, and rearranges the nav bar.
+    fn move_page(&mut self, page: Page, to: usize) {
+        let mut order = self.page_order();
+
+        if let Some(from) = order.iter().position(|&other| other == page) {
+            order.remove(from);
+            order.insert(to.min(order.len()), page);
+        }
+
+        self.config.nav_order = order.iter().map(|page| page.index()).collect();
+        self.arrange_nav();
+    }
+
+    /// The nav bar's item for `page`, unless the page is hidden.
+    fn nav_id(&self, page: Page) -> Option<nav_bar::Id> {
+        self.nav.iter().find(|&id| self.nav.data::<Page>(id) == Some(&page))
+    }
+
+    /// Whether the window `id` is narrower than the layout breakpoint.
+    fn is_narrow(&self, id: Option<window::Id>) -> bool {
+        id.is_some_and(|id| self.narrow_windows.contains(&id))
+    }
+
+    /// The content of `page`, as shown in the main window or a window of its own,
+    /// in the narrow layout if that window is `narrow`.
+    fn page_content(&self, page: Page, narrow: bool) -> Element<'_, Message> {
+        match page {
+            Page::Page1 => self.watch.view(&self.config, narrow).map(Message::Watch),
+            Page::Page2 => self.counters.view(&self.config, narrow).map(Message::Counters),
+            Page::Page3 => self.password.view(&self.config, narrow).map(Message::Password),
+            Page::Page4 => self.game.view(&self.config, narrow).map(Message::Game),
+            Page::Page5 => self.clipboard.view(&self.config, narrow).map(Message::Clipboard),
+            Page::Page6 => self.files.view(&self.config, narrow).map(Message::Files),
+        }
+    }
+
+    /// Tells the user about a failure in a toast, and broadcasts its code on the bus.
+    fn fail(&mut self, text: String, error: Error) -> Task<cosmic::Action<Message>> {
+        if let Some(sender) = &self.dbus {
+            _ = sender.send(dbus::Event::Failed(error));
+        }
+
+        self.show(text).map(cosmic::Action::App)
+    }
+
+    /// Shows `text` in a toast, or in a desktop notification while there is no window.
+    fn show(&mut self, text: String) -> Task<Message> {
+        if self.core.main_window_id().is_none() {
+            return Task::future(notify::send(text)).discard();
+        }
+
+        self.toasts.push(widget::toaster::Toast::new(text))
+    }
+
+    /// Carries out what a page asked for while handling a message, alongside its task.
+    /// Undoes the last change, or redoes the last one undone if `undo` is false,
+    /// by handing it to the page that made it.
+    fn step_history(&mut self, undo: bool) -> Task<cosmic::Action<Message>> {
+        let stack = if undo { &mut self.undo } else { &mut self.redo };
+
+        let Some(change) = stack.pop() else {
+            return Task::none();
+        };
+
+        let (reverse, effects) = match change {
+            pages::Change::Counters(_) => {
+                // Removal toasts would put counters back into groups they are no longer in.
+                self.pending_removals.retain(|(_, removed)| matches!(removed, pages::Removed::Alarm { .. }));
+                pages::revert(&mut self.counters, change, &mut self.config)
+            }
+            pages::Change::Round { .. } => pages::revert(&mut self.game, change, &mut self.config),
+        };
+
+        if let Some(reverse) = reverse {
+            if undo {
+                self.redo.push(reverse);
+            } else {
+                self.undo.push(reverse);
+            }
+        }
+
+        self.refresh_badges();
+        self.apply(None, effects, Task::none())
+    }
+
+    /// Asks before a backup replaces the current configuration and data.
+    fn restore_dialog(&self, manifest: &backup::Manifest) -> Element<'_, Message> {
+        let created = chrono::DateTime::from_timestamp(manifest.created, 0)
+            .map(|created| created.with_timezone(&chrono::Local).format("%c").to_string())
+            .unwrap_or_default();
+
+        widget::dialog()
+            .title(fl!("confirm-restore"))
+            .body(fl!(
+                "confirm-restore-body",
+                created = created,
+                version = manifest.app_version.as_str()
+            ))
+            .primary_action(widget::button::destructive(fl!("restore")).on_press(Message::ConfirmRestore))
+            .secondary_action(widget::button::standard(fl!("cancel")).on_press(Message::CancelRestore))
+            .into()
+    }
+
+    /// Reads everything again after a backup was restored: the configuration,
+    /// the pages with their saved state, and the usage counts.
+    fn reload(&mut self) -> Task<cosmic::Action<Message>> {
+        let config = self
+            .config_handler
+            .as_ref()
+            .map(|handler| Config::get_entry(handler).unwrap_or_else(|(_errors, config)| config))
+            .unwrap_or_default();
+
+        self.watch = pages::WatchPage::new(&config);
+        self.counters = pages::CountersPage::new();
+        self.password = pages::PasswordPage::new();
+        self.game = pages::GamePage::new(&config);
+
+        let state = State::load().unwrap_or_default();
+        self.watch.restore_state(&state, &config);
+        self.counters.restore_state(&state, &config);
+        self.password.restore_state(&state, &config);
+        self.game.restore_state(&state, &config);
+        self.refresh_badges();
+
+        self.usage = Usage::load();
+        self.undo.clear();
+        self.redo.clear();
+        self.pending_removals.clear();
+
+        Task::batch([
+            self.update(Message::UpdateConfig(config)),
+            self.update(Message::ShowToast(fl!("backup-restored"))),
+            pages::GamePage::load_leaderboard().map(|message| cosmic::Action::App(Message::Game(message))),
+        ])
+    }
+
+    /// Writes where every page is to the state file, to pick up from at the next launch.
+    fn save_state(&self) {
+        let mut state = State::default();
+        self.watch.save_state(&mut state);
+        self.counters.save_state(&mut state);
+        self.password.save_state(&mut state);
+        self.game.save_state(&mut state);
+        state.save();
+    }
+
+    /// Aborts the tasks and transfers `page` started, hiding the transfer bar if it showed one of them.
+    fn cancel_tasks(&mut self, page: Page) {
+        self.tasks.cancel(&page);
+
+        if self.transfer.as_ref().is_some_and(|transfer| transfer.cancel.is_cancelled()) {
+            self.transfer = None;
+        }
+    }
+
+    /// Carries out what a page asked for, tracking the task it returned under `page` so it can be
+    /// cancelled.
+    fn apply(
+        &mut self,
+        page: Option<Page>,
+        effects: pages::Effects,
+        task: Task<Message>,
+    ) -> Task<cosmic::Action<Message>> {
+        if let Some(sender) = &self.dbus {
+            for event in effects.events {
+                _ = sender.send(event);
+            }
+        }
+
+        for event in effects.usage {
+            self.usage.record(event);
+        }
+
+        if effects.save_config {
+            self.save_config();
+        }
+
+        if effects.save_state {
+            self.save_state();
+        }
+
+        if let Some(transferred) = effects.transfer {
+            if let Some(page) = page {
+                self.tasks.transfer(page, &transferred);
+            }
+
+            self.transfer = Some(transferred).filter(|t| !t.ended);
+        }
+
+        if !effects.changes.is_empty() {
+            self.undo.extend(effects.changes);
+            self.undo.drain(..self.undo.len().saturating_sub(UNDO_LIMIT));
+            self.redo.clear();
+        }
+
+        let mut tasks: Vec<_> = effects.toasts.into_iter().map(|text| self.show(text)).collect();
+
+        for text in effects.spoken {
+            speech::say(text, self.config.speech_voice, self.config.speech_rate);
+        }
+
+        for event in effects.sounds.into_iter().filter(|_| !self.config.mute_sounds) {
+            if let Some(sound) = self.config.sounds.get(&event).cloned() {
+                tasks.push(
+                    Task::future(async move {
+                        if let Err(why) = audio::play(sound).await {
+                            eprintln!("failed to play sound: {why}");
+                        }
+                    })
+                    .discard(),
+                );
+            }
+        }
+
+        // Without a window, `show` already turns the toast into a notification.
+        for text in effects.announcements {
+            if self.config.desktop_notifications && self.core.main_window_id().is_some() {
+                tasks.push(Task::future(notify::send(text.clone())).discard());
+            }
+
+            tasks.push(self.show(text));
+        }
+
+        // Removals stay undoable for a grace period, after which they are dropped for good.
+        for removed in effects.removed {
+            let key = self.next_removal;
+            self.next_removal += 1;
+
+            let toast = widget::toaster::Toast::new(removed.description())
+                .action(fl!("undo"), move |_| Message::UndoRemoval(key));
+
+            self.pending_removals.push((key, removed));
+            tasks.push(self.toasts.push(toast));
+            tasks.push(cosmic::task::future(async move {
+                tokio::time::sleep(UNDO_GRACE).await;
+                Message::ForgetRemoval(key)
+            }));
+        }
+
+        tasks.push(match page {
+            Some(page) => self.tasks.spawn(page, task, move |id| Message::TaskFinished(page, id)),
+            None => task,
+        });
+
+        Task::batch(tasks).map(cosmic::Action::App)
+    }
+
+    /// Swatches of the imported theme's colors, with buttons to apply it.
+    fn theme_view(&self, narrow: bool) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let Some((name, theme)) = &self.theme_preview else {
+            return widgets::empty_state(
+                icons::handle("preferences-desktop-theme-symbolic"),
+                fl!("no-theme"),
+            )
+            .action(fl!("import-theme"), Message::ImportTheme)
+            .into();
+        };
+
+        let colors = [
+            (fl!("theme-background"), theme.background.base),
+            (fl!("theme-primary"), theme.primary.base),
+            (fl!("theme-accent"), theme.accent.base),
+            (fl!("theme-success"), theme.success.base),
+            (fl!("theme-warning"), theme.warning.base),
+            (fl!("theme-destructive"), theme.destructive.base),
+        ];
+
+        let mut swatches = cosmic::widget::settings::section().title(name.as_str());
+
+        for (label, color) in colors {
+            let color = cosmic::iced::Color::from(color);
+
+            let swatch = widget::container(widget::row::<Message>::new())
+                .width(Length::Fixed(32.0))
+                .height(Length::Fixed(32.0))
+                .class(cosmic::theme::Container::custom(move |theme| {
+                    let cosmic = theme.cosmic();
+                    let (border, width) = if contrast::is_high_contrast(theme) {
+                        (cosmic.background.on, 2.0)
+                    } else {
+                        (cosmic.palette.neutral_5, 1.0)
+                    };
+
+                    widget::container::Style {
+                        background: Some(color.into()),
+                        border: cosmic::iced::Border {
+                            color: border.into(),
+                            width,
+                            radius: cosmic.corner_radii.radius_s.into(),
+                        },
+                        ..Default::default()
+                    }
+                }));
+
+            swatches = swatches.add(cosmic::widget::settings::item::builder(label).control(swatch));
+        }
+
+        let mode = if theme.is_dark { fl!("theme-dark") } else { fl!("theme-light") };
+
+        let buttons = widget::row::with_capacity(2)
+            .push(widget::button::suggested(fl!("apply-theme")).on_press(Message::ApplyTheme))
+            .push(
+                widget::button::standard(fl!("use-system-theme"))
+                    .on_press_maybe(self.custom_theme.then_some(Message::UseSystemTheme)),
+            )
+            .spacing(space_s);
+
+        let mut column = widget::column::with_capacity(4)
+            .push(widget::text::body(mode))
+            .push(swatches);
+
+        if let Some(modes) = &self.theme_modes {
+            column = column.push(self.theme_split_view(modes, narrow));
+        }
+
+        column.push(buttons).spacing(space_s).into()
+    }
+
+    /// The same sample controls drawn in the dark and the light theme side by side.
+    fn theme_split_view<'a>(&'a self, modes: &'a [cosmic::Theme; 2], narrow: bool) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let on = self.theme_sample_on;
+
+        let half = |theme: &cosmic::Theme, label: String| {
+            let sample = widget::column::with_capacity(6)
+                .push(widget::text::heading(label))
+                .push(widget::text::body(fl!("theme-sample-body")))
+                .push(widget::button::suggested(fl!("theme-sample-suggested")).on_press(Message::ThemeSample(!on)))
+                .push(widget::button::standard(fl!("theme-sample-standard")).on_press(Message::ThemeSample(!on)))
+                .push(widget::button::destructive(fl!("theme-sample-destructive")).on_press(Message::ThemeSample(!on)))
+                .push(widget::checkbox(fl!("theme-sample-option"), on).on_toggle(Message::ThemeSample))
+                .spacing(space_s)
+                .apply(widget::container)
+                .padding(space_s)
+                .width(Length::Fill)
+                .class(cosmic::theme::Container::Background);
+
+            cosmic::iced::widget::themer(theme.clone(), sample)
+        };
+
+        let [dark, light] = modes;
+
+        cosmic::widget::settings::section()
+            .title(fl!("theme-modes"))
+            .add(widgets::adaptive_row(narrow, vec![
+                half(dark, fl!("theme-dark")).into(),
+                half(light, fl!("theme-light")).into(),
+            ]))
+            .into()
+    }
+
+    /// Lists the key bindings, global ones first and then those of each page.
+    fn shortcuts_view(&self) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        // Bindings limited to `page`, or the global ones for `None`, sorted by label.
+        let binds = |page: Option<Page>| {
+            let mut binds: Vec<_> = self
+                .key_binds
+                .iter()
+                .filter(|(_, action)| action.page() == page)
+                .map(|(key_bind, action)| (action.label(), key_bind.to_string()))
+                .collect();
+            binds.sort();
+            binds
+        };
+
+        let item = |(label, keys): (String, String)| {
+            cosmic::widget::settings::item::builder(label).control(widget::text::body(keys))
+        };
+
+        // The cheatsheet's own key is not a key bind since it ignores Shift.
+        let global = binds(None).into_iter().map(item).fold(
+            cosmic::widget::settings::section()
+                .title(fl!("global-shortcuts"))
+                .add(item((fl!("keyboard-shortcuts"), "?".to_string()))),
+            |section, item| section.add(item),
+        );
+
+        let sections = Page::ALL
+            .into_iter()
+            .filter_map(|page| {
+                let binds = binds(Some(page));
+
+                (!binds.is_empty()).then(|| {
+                    binds.into_iter().map(item).fold(
+                        cosmic::widget::settings::section().title(self.page_names[page.index()].clone()),
+                        |section, item| section.add(item),
+                    )
+                })
+            })
+            .fold(widget::column::with_capacity(5).push(global), |column, section| {
+                column.push(section)
+            })
+            .spacing(space_s);
+
+        widget::column::with_capacity(3)
+            .push(widget::text::title3(fl!("keyboard-shortcuts")))
+            .push(sections)
+            .push(
+                widget::row::with_capacity(1)
+                    .push(widget::horizontal_space())
+                    .push(widget::button::standard(fl!("close")).on_press(Message::ToggleShortcuts)),
+            )
+            .spacing(space_s)
+            .width(Length::Fill)
+            .max_width(OVERLAY_WIDTH)
+            .apply(overlay)
+    }
+
+    /// Counts of how the application has been used, which never leave this device.
+    fn activity_view(&self) -> Element<'_, Message> {
+        let count = |label: String, count: u64| {
+            cosmic::widget::settings::item::builder(label).control(widget::text::body(count.to_string()))
+        };
+
+        let totals = cosmic::widget::settings::section()
+            .add(count(fl!("launches"), self.usage.launches))
+            .add(count(fl!("games-played"), self.usage.games_played));
+
+        let visits = Page::ALL.iter().fold(
+            cosmic::widget::settings::section().title(fl!("page-visits")),
+            |section, page| {
+                let idx = page.index();
+                section.add(count(self.page_names[idx].clone(), self.usage.visits(idx)))
+            },
+        );
+
+        widget::column::with_capacity(4)
+            .push(widget::text::body(fl!("activity-local-only")))
+            .push(totals)
+            .push(visits)
+            .push(
+                widget::row::with_capacity(2)
+                    .push(widget::button::standard(fl!("print")).on_press(Message::PrintActivity))
+                    .push(widget::button::destructive(fl!("clear-activity")).on_press(Message::ClearUsage))
+                    .spacing(cosmic::theme::spacing().space_s),
+            )
+            .spacing(cosmic::theme::spacing().space_m)
+            .into()
+    }
+
+    /// The activity counts, laid out for printing.
+    fn activity_document(&self) -> print::Document {
+        let document = print::Document::new(fl!("your-activity"))
+            .text(fl!("activity-local-only"))
+            .text(format!("{}: {}", fl!("launches"), self.usage.launches))
+            .text(format!("{}: {}", fl!("games-played"), self.usage.games_played))
+            .heading(fl!("page-visits"));
+
+        Page::ALL.iter().fold(document, |document, page| {
+            let idx = page.index();
+            document.text(format!("{}: {}", self.page_names[idx], self.usage.visits(idx)))
+        })
+    }
+
+    /// Hands a document to the print portal, which asks where and how to print it.
+    fn print(&self, document: print::Document) -> Task<cosmic::Action<Message>> {
+        cosmic::task::future(async move { Message::Printed(print::print(document).await) })
+    }
+
+    /// The content of a context page other than the about page, shown in the
+    /// drawer or in a window of its own, in the narrow layout if that window is `narrow`.
+    fn context_page_view(&self, page: ContextPage, narrow: bool) -> Element<'_, Message> {
+        match page {
+            ContextPage::Theme => self.theme_view(narrow),
+            ContextPage::Settings => self.settings_view(),
+            ContextPage::Activity => self.activity_view(),
+            // The drawer lays the about page out itself, and it is never popped out.
+            ContextPage::About => widget::text::body("").into(),
+        }
+    }
+
+    /// Attaches the context drawer to `content` at the width the user chose.
+    ///
+    /// The application's own drawer is as wide as libcosmic makes it, so a
+    /// chosen width is given to the context drawer widget here instead, while
+    /// [`Application::context_drawer`] shows none. The about page keeps the
+    /// application's drawer, which lays it out.
+    fn sized_drawer<'a>(&'a self, content: Element<'a, Message>) -> Element<'a, Message> {
+        let width = self.config.context_drawer_width;
+
+        if width == 0 || !self.core.window.show_context || self.context_page == ContextPage::About {
+            return content;
+        }
+
+        let page = self.context_page;
+
+        widget::context_drawer(
+            Some(page.title().into()),
+            vec![pop_out_button(page)],
+            None,
+            None,
+            Message::ToggleContextPage(page),
+            content,
+            self.context_page_view(page, self.is_narrow(self.core.main_window_id())),
+            width as f32,
+        )
+        .into()
+    }
+
+    /// Captures the values summarized by an exported report.
+    fn report(&self) -> Report {
+        let stopwatch = self.watch.stopwatch();
+
+        Report {
+            stopwatch: stopwatch.map(timer::Timer::elapsed).unwrap_or_default(),
+            laps: stopwatch.map(|timer| timer.laps().to_vec()).unwrap_or_default(),
+            counters: self
+                .config
+                .counter_groups
+                .iter()
+                .map(|group| (group.name.clone(), group.subtotal()))
+                .collect(),
+            game_attempts: self.game.attempts(),
+        }
+    }
+
+    /// Marks the configuration to be written to disk shortly, rather than on
+    /// every change, since each write reads what is on disk to merge with.
+    fn save_config(&mut self) {
+        self.config_unsaved = true;
+    }
+
+    /// Writes the configuration to disk if it changed since it was last
+    /// written, merged with what another instance wrote since it was last
+    /// read, so neither loses its changes.
+    ///
+    /// Fields both changed are left as the other instance wrote them, and the
+    /// user is asked which to keep.
+    fn write_config(&mut self) {
+        if !std::mem::take(&mut self.config_unsaved) {
+            return;
+        }
+
+        let Some(handler) = &self.config_handler else {
+            return;
+        };
+
+        let disk = Config::get_entry(handler).unwrap_or_else(|(_errors, config)| config);
+        let (merged, conflicts) = self.merged_config(&disk);
+
+        match merged.write_entry(handler) {
+            Ok(()) => self.config_base = merged,
+            Err(why) => eprintln!("failed to save config: {why}"),
+        }
+
+        for field in conflicts {
+            if !self.config_conflicts.contains(&field) {
+                self.config_conflicts.push(field);
+            }
+        }
+    }
+
+    /// Writes a configuration change still waiting to be written, as the app quits.
+    fn flush_config(&self) {
+        let Some(handler) = self.config_handler.as_ref().filter(|_| self.config_unsaved) else {
+            return;
+        };
+
+        let disk = Config::get_entry(handler).unwrap_or_else(|(_errors, config)| config);
+
+        if let Err(why) = self.merged_config(&disk).0.write_entry(handler) {
+            eprintln!("failed to save config: {why}");
+        }
+    }
+
+    /// The configuration to write over `disk`, with the fields both this and
+    /// another instance changed since it was last read.
+    fn merged_config(&self, disk: &Config) -> (Config, Vec<ConfigField>) {
+        // Items whose removal may still be undone are kept on disk until it no longer can.
+        let mut config = self.config.clone();
+
+        for (_, removed) in self.pending_removals.iter().rev() {
+            removed.clone().restore(&mut config);
+        }
+
+        let merge = config.merge(&self.config_base, disk);
+        let mut merged = merge.config;
+
+        for &field in self.config_conflicts.iter().chain(&merge.conflicts) {
+            merged.copy_field(disk, field);
+        }
+
+        (merged, merge.conflicts)
+    }
+
+    /// Asks whether to keep this instance's settings or another's, where both changed.
+    fn config_conflict_dialog(&self) -> Element<'_, Message> {
+        widget::dialog()
+            .title(fl!("config-conflict"))
+            .body(fl!("config-conflict-body"))
+            .primary_action(
+                widget::button::suggested(fl!("keep-mine")).on_press(Message::ResolveConfigConflicts(true)),
+            )
+            .secondary_action(
+                widget::button::standard(fl!("use-theirs")).on_press(Message::ResolveConfigConflicts(false)),
+            )
+            .into()
+    }
+
+    /// Updates the header and window titles.
+    pub fn update_title(&mut self) -> Task<cosmic::Action<Message>> {
+        let mut window_title = fl!("app-title");
+
+        // The page's name without its badge, which would change the title every second.
+        if let Some(page) = self.nav.active_data::<Page>() {
+            window_title.push_str(" — ");
+            window_title.push_str(&self.page_names[page.index()]);
+        }
+
+        if let Some(id) = self.core.main_window_id() {
+            self.set_window_title(window_title, id)
+        } else {
+            Task::none()
+        }
+    }
+}
+
+/// The button in a context drawer's header moving `page` into a window of its own.
+fn pop_out_button<'a>(page: ContextPage) -> Element<'a, Message> {
+    widget::tooltip(
+        widget::button::icon(icons::handle("window-new-symbolic")).on_press(Message::PopOutContextPage(page)),
+        widget::text::body(fl!("pop-out")),
+        widget::tooltip::Position::Bottom,
+    )
+    .into()
+}
+
+/// How far the current file transfer has got, with a button to stop it.
+/// The transfer bar; a transfer started by `page` is cancelled with the rest of that page's work.
+fn transfer_view<'a>(transfer: &io::Transferred, page: Option<Page>) -> Element<'a, Message> {
+    let cancel = page.map_or(Message::CancelTransfer, Message::CancelTask);
+    let label = match transfer.fraction() {
+        Some(fraction) => fl!("transferring-percent", percent = (fraction * 100.0).round() as u32),
+        None => fl!("transferring"),
+    };
+
+    widget::row::with_capacity(3)
+        .push(widget::text::caption(label))
+        .push(widget::progress_bar(0.0..=1.0, transfer.fraction().unwrap_or(0.0)))
+        .push(widget::button::standard(fl!("cancel")).on_press(cancel))
+        .align_y(Vertical::Center)
+        .spacing(cosmic::theme::spacing().space_s)
+        .into()
+}
+
+/// Parses a COSMIC theme file, as exported by COSMIC Settings, or a built theme.
+fn load_theme(text: &str) -> Result<cosmic_theme::Theme, Error> {
+    match ron::from_str::<ThemeBuilder>(text) {
+        Ok(builder) => Ok(builder.build()),
+        Err(builder_error) => ron::from_str::<cosmic_theme::Theme>(text)
+            .map_err(|_| Error::new(Code::InvalidData, builder_error.to_string())),
+    }
+}
+
+/// The theme in both modes, dark first: as imported, and rebuilt in the other
+/// mode with the same accent and status colors.
+fn theme_modes(theme: &cosmic_theme::Theme) -> [cosmic::Theme; 2] {
+    let builder = if theme.is_dark { ThemeBuilder::light() } else { ThemeBuilder::dark() };
+
+    let other = builder
+        .accent(theme.accent.base.color)
+        .success(theme.success.base.color)
+        .warning(theme.warning.base.color)
+        .destructive(theme.destructive.base.color)
+        .build();
+
+    let (dark, light) = if theme.is_dark { (theme.clone(), other) } else { (other, theme.clone()) };
+    [cosmic::Theme::custom(Arc::new(dark)), cosmic::Theme::custom(Arc::new(light))]
+}
+
+/// A card floating over the page, as for the cheatsheet and the command palette.
+fn overlay<'a>(content: impl Into<Element<'a, Message>>) -> Element<'a, Message> {
+    widget::container(content)
+        .padding(cosmic::theme::spacing().space_m)
+        .class(cosmic::theme::Container::custom(|theme| {
+            let cosmic = theme.cosmic();
+            let high_contrast = contrast::is_high_contrast(theme);
+            let mut background: cosmic::iced::Color = cosmic.background.base.into();
+
+            // The page showing through would make the labels harder to read in high contrast.
+            if !high_contrast {
+                background.a = 0.92;
+            }
+
+            widget::container::Style {
+                background: Some(background.into()),
+                border: cosmic::iced::Border {
+                    color: cosmic.on_bg_color().into(),
+                    width: if high_contrast { 2.0 } else { 0.0 },
+                    radius: cosmic.corner_radii.radius_m.into(),
+                },
+                ..Default::default()
+            }
+        }))
+        .into()
+}
+
+/// The about page's contents.
+fn about() -> About {
+    About::default()
+        .name(fl!("app-title"))
+        .icon(widget::icon::from_svg_bytes(APP_ICON))
+        .version(env!("CARGO_PKG_VERSION"))
+        .links([(fl!("repository"), REPOSITORY)])
+        .license(env!("CARGO_PKG_LICENSE"))
+}
+
+/// Page titles, in the order of `Page::ALL`.
+fn page_names() -> Vec<String> {
+    (1..=Page::ALL.len()).map(|num| fl!("page-id", num = num)).collect()
+}
+
+/// Options given on the command line.
+#[derive(Clone, Debug, Default)]
+pub struct Flags {
+    /// Position in the nav bar of the page to open, overriding the configured one.
+    pub page: Option<usize>,
+    /// Run the timers, D-Bus signals, and notifications without opening a window.
+    pub daemon: bool,
+    /// App ID, and so window class, to run a separate instance under.
+    pub app_id: Option<String>,
+    /// Name of the action to run in an instance already running, when the
+    /// launch is handed to it, as `page-2` for `--page 2`.
+    pub action: Option<String>,
+}
+
+impl cosmic::app::CosmicFlags for Flags {
+    type SubCommand = String;
+    type Args = Vec<String>;
+
+    fn action(&self) -> Option<&String> {
+        self.action.as_ref()
+    }
+}
+
+/// The page to display in the application.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Page {
+    Page1,
+    Page2,
+    Page3,
+    Page4,
+    Page5,
+    Page6,
+}
+
+impl Page {
+    /// Every page, in the order they appear in the nav bar.
+    pub const ALL: [Page; 6] = [Page::Page1, Page::Page2, Page::Page3, Page::Page4, Page::Page5, Page::Page6];
+
+    /// The page's place among every page, which is its position in the nav
+    /// bar unless pages are reordered, pinned, or hidden.
+    pub fn index(self) -> usize {
+        Page::ALL.iter().position(|&page| page == self).unwrap_or_default()
+    }
+
+    /// Name of the page's icon in the nav bar.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Page::Page1 => "applications-science-symbolic",
+            Page::Page2 => "applications-system-symbolic",
+            Page::Page3 => "applications-utilities-symbolic",
+            Page::Page4 => "applications-games-symbolic",
+            Page::Page5 => "edit-paste-symbolic",
+            Page::Page6 => "document-open-symbolic",
+        }
+    }
+}
+
+
+/// The context page to display in the context drawer.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ContextPage {
+    #[default]
+    About,
+    Theme,
+    Settings,
+    Activity,
+}
+
+impl ContextPage {
+    /// The page's title, over the drawer or its window.
+    fn title(self) -> String {
+        match self {
+            ContextPage::About => fl!("about"),
+            ContextPage::Theme => fl!("theme"),
+            ContextPage::Settings => fl!("settings"),
+            ContextPage::Activity => fl!("your-activity"),
+        }
+    }
+}
+
+/// Actions in the menu of a right-clicked nav item, for that item's page.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NavMenuAction {
+    /// Returns the page to how it started: clears the timers, zeroes the
+    /// counters, generates a new password, or starts a new game.
+    Reset(Page),
+    OpenWindow(Page),
+    /// Pins the page to the top of the nav bar, or unpins it.
+    Pin(Page),
+    /// Leaves the page out of the nav bar, until shown again in the settings.
+    Hide(Page),
+}
+
+impl menu::action::MenuAction for NavMenuAction {
+    type Message = cosmic::Action<Message>;
+
+    fn message(&self) -> Self::Message {
+        cosmic::Action::App(Message::NavMenu(*self))
+    }
+}
+
+/// Live status of a page, shown after its name in the nav bar.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct NavBadge(Option<String>);
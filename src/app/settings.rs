@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The settings drawer, and the quick settings popover from the header.
+
+use super::{AppModel, Message, Page};
+use crate::audio::{Sound, SoundEvent};
+use crate::config::AppTheme;
+use crate::fl;
+use crate::i18n;
+use crate::icons;
+use crate::pages;
+use crate::timer;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::{Length, mouse};
+use cosmic::prelude::*;
+use cosmic::widget;
+use i18n_embed::unic_langid::LanguageIdentifier;
+
+/// Context drawer widths the user may choose from, in logical pixels.
+const DRAWER_WIDTHS: [u32; 3] = [320, 400, 480];
+/// Width of the quick settings popover.
+const QUICK_SETTINGS_WIDTH: f32 = 320.0;
+
+impl AppModel {
+    /// Preferences, shown in the context drawer.
+    pub(super) fn settings_view(&self) -> Element<'_, Message> {
+        let startup = cosmic::widget::settings::section()
+            .title(fl!("startup"))
+            .add(
+                cosmic::widget::settings::item::builder(fl!("start-page")).control(widget::dropdown(
+                    &self.page_names,
+                    Some(self.config.start_page.min(Page::ALL.len() - 1)),
+                    Message::StartPage,
+                )),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("start-minimized"))
+                    .toggler(self.config.start_minimized, Message::StartMinimized),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("resume-timers"))
+                    .description(fl!("resume-timers-description"))
+                    .toggler(self.config.resume_timers, Message::ResumeTimers),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("page-per-workspace"))
+                    .description(fl!("page-per-workspace-description"))
+                    .toggler(self.config.page_per_workspace, Message::PagePerWorkspace),
+            );
+
+        let width = DRAWER_WIDTHS
+            .iter()
+            .position(|&width| width == self.config.context_drawer_width)
+            .map_or(0, |idx| idx + 1);
+
+        let drawer = cosmic::widget::settings::section().title(fl!("context-drawer")).add(
+            cosmic::widget::settings::item::builder(fl!("drawer-width")).control(widget::dropdown(
+                &self.drawer_width_labels,
+                Some(width),
+                |idx| Message::ContextDrawerWidth(idx.checked_sub(1).map_or(0, |idx| DRAWER_WIDTHS[idx])),
+            )),
+        );
+
+        let selected = |choices: &[_], current| choices.iter().position(|&choice| choice == current);
+
+        let appearance = cosmic::widget::settings::section()
+            .title(fl!("appearance"))
+            .add(
+                cosmic::widget::settings::item::builder(fl!("language")).control(widget::dropdown(
+                    &self.language_labels,
+                    Some(self.language_index().map_or(0, |idx| idx + 1)),
+                    |idx| Message::Language(idx.checked_sub(1)),
+                )),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("theme-mode")).control(widget::dropdown(
+                    &self.app_theme_labels,
+                    selected(&AppTheme::ALL, self.config.app_theme),
+                    |idx| Message::AppTheme(AppTheme::ALL[idx]),
+                )),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("high-contrast"))
+                    .toggler(self.config.high_contrast, |_| Message::ToggleHighContrast),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("reduce-motion"))
+                    .toggler(self.config.reduce_motion, |_| Message::ToggleReduceMotion),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("condensed-nav"))
+                    .description(fl!("condensed-nav-description"))
+                    .toggler(self.config.condensed_nav, |_| Message::ToggleCondensedNav),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("auto-condense-nav"))
+                    .description(fl!("auto-condense-nav-description"))
+                    .toggler(self.config.auto_condense_nav, Message::AutoCondenseNav),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("explicit-feedback"))
+                    .description(fl!("explicit-feedback-description"))
+                    .toggler(self.config.explicit_feedback, Message::ExplicitFeedback),
+            );
+
+        let watch = cosmic::widget::settings::section().title(fl!("watch")).add(
+            cosmic::widget::settings::item::builder(fl!("watch-format")).control(widget::dropdown(
+                &self.watch_format_labels,
+                selected(&timer::WatchFormat::ALL, self.config.watch_format),
+                |idx| Message::WatchFormat(timer::WatchFormat::ALL[idx]),
+            )),
+        );
+
+        let notifications = cosmic::widget::settings::section()
+            .title(fl!("notifications"))
+            .add(
+                cosmic::widget::settings::item::builder(fl!("desktop-notifications"))
+                    .description(fl!("desktop-notifications-description"))
+                    .toggler(self.config.desktop_notifications, Message::DesktopNotifications),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("sounds"))
+                    .toggler(!self.config.mute_sounds, |on| Message::MuteSounds(!on)),
+            );
+
+        // Each event's sound, with a button to hear it. A chosen file is named below the event.
+        let notifications = SoundEvent::ALL.into_iter().fold(notifications, |section, event| {
+            let sound = self.config.sounds.get(&event);
+
+            let selected = match sound {
+                Some(Sound::File(_)) => Sound::BUILT_IN.len(),
+                Some(sound) => Sound::BUILT_IN.iter().position(|built_in| built_in == sound).unwrap_or(0),
+                None => 0,
+            };
+
+            let mut item = cosmic::widget::settings::item::builder(sound_event_label(event));
+
+            if let Some(Sound::File(path)) = sound {
+                let name = path.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+                item = item.description(name);
+            }
+
+            section.add(
+                item.control(
+                    widget::row::with_capacity(2)
+                        .push(widget::dropdown(&self.sound_labels, Some(selected), move |idx| {
+                            Message::SelectSound(event, idx)
+                        }))
+                        .push(
+                            widget::button::icon(icons::handle("media-playback-start-symbolic"))
+                                .on_press_maybe((selected != 0).then_some(Message::PreviewSound(event))),
+                        )
+                        .align_y(Vertical::Center)
+                        .spacing(cosmic::theme::spacing().space_xs),
+                ),
+            )
+        });
+
+        // The password page's own options, so changes show up there too.
+        let passwords = pages::password::password_options(
+            cosmic::widget::settings::section().title(fl!("password-defaults")),
+            &self.config,
+        );
+
+        let backup = cosmic::widget::settings::section().title(fl!("backup")).add(
+            cosmic::widget::settings::item::builder(fl!("backup-description")).control(
+                widget::row::with_capacity(2)
+                    .push(widget::button::standard(fl!("back-up")).on_press(Message::BackUp))
+                    .push(widget::button::standard(fl!("restore-backup")).on_press(Message::ChooseBackup))
+                    .spacing(cosmic::theme::spacing().space_xs),
+            ),
+        );
+
+        widget::column::with_capacity(8)
+            .push(startup)
+            .push(self.navigation_settings())
+            .push(appearance)
+            .push(notifications)
+            .push(watch)
+            .push(Element::from(passwords).map(Message::Password))
+            .push(drawer)
+            .push(backup)
+            .spacing(cosmic::theme::spacing().space_m)
+            .into()
+    }
+
+    /// The pages of the nav bar, which can be dragged by their handles or moved with
+    /// the arrow buttons into a new order, and hidden.
+    fn navigation_settings(&self) -> Element<'_, Message> {
+        let order = self.page_order();
+        let shown = order.len() - self.config.hidden_pages.len().min(order.len());
+        let space_xs = cosmic::theme::spacing().space_xs;
+
+        let section = order.iter().enumerate().fold(
+            cosmic::widget::settings::section().title(fl!("navigation")),
+            |section, (position, &page)| {
+                let visible = !self.config.hidden_pages.contains(&page.index());
+
+                let handle = widget::mouse_area(widget::icon(icons::handle("list-drag-handle-symbolic")).size(16))
+                    .on_press(Message::DragPage(page))
+                    .interaction(mouse::Interaction::Grab);
+
+                let controls = widget::row::with_capacity(3)
+                    .push(
+                        widget::button::icon(icons::handle("pan-up-symbolic"))
+                            .on_press_maybe((position > 0).then_some(Message::MovePage(page, -1))),
+                    )
+                    .push(
+                        widget::button::icon(icons::handle("pan-down-symbolic"))
+                            .on_press_maybe((position + 1 < order.len()).then_some(Message::MovePage(page, 1))),
+                    )
+                    .push(
+                        widget::toggler(visible).on_toggle_maybe(
+                            (!visible || shown > 1).then_some(move |show| Message::ShowInNav(page, show)),
+                        ),
+                    )
+                    .align_y(Vertical::Center)
+                    .spacing(space_xs);
+
+                let name = self.page_names[page.index()].clone();
+
+                let item = widget::row::with_capacity(3)
+                    .push(handle)
+                    .push(widget::icon(icons::handle(page.icon())).size(16))
+                    .push(cosmic::widget::settings::item::builder(name).control(controls))
+                    .align_y(Vertical::Center)
+                    .spacing(space_xs);
+
+                section.add(widget::mouse_area(item).on_enter(Message::DragPageOver(page)))
+            },
+        );
+
+        let customized = !self.config.pinned_pages.is_empty()
+            || !self.config.nav_order.is_empty()
+            || !self.config.hidden_pages.is_empty();
+
+        let restore = widget::button::standard(fl!("restore-defaults"))
+            .on_press_maybe(customized.then_some(Message::RestoreNav));
+
+        let section =
+            section.add(cosmic::widget::settings::item::builder(fl!("restore-nav-description")).control(restore));
+
+        // Letting go anywhere in the list, or leaving it, ends a drag.
+        widget::mouse_area(section)
+            .on_release(Message::DropPage)
+            .on_exit(Message::DropPage)
+            .into()
+    }
+
+    /// The switches changed most often, to reach without opening the settings.
+    pub(super) fn quick_settings_view(&self) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let selected = AppTheme::ALL.iter().position(|&theme| theme == self.config.app_theme);
+        let running = self.watch.stopwatch().is_some_and(timer::Timer::is_running);
+
+        let stopwatch = if running {
+            widget::button::standard(fl!("stop-stopwatch"))
+        } else {
+            widget::button::suggested(fl!("start-stopwatch"))
+        }
+        .on_press(Message::Watch(pages::watch::Message::ToggleWatch));
+
+        let section = cosmic::widget::settings::section()
+            .add(
+                cosmic::widget::settings::item::builder(fl!("theme-mode")).control(widget::dropdown(
+                    &self.app_theme_labels,
+                    selected,
+                    |idx| Message::AppTheme(AppTheme::ALL[idx]),
+                )),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("sounds"))
+                    .toggler(!self.config.mute_sounds, |on| Message::MuteSounds(!on)),
+            )
+            .add(cosmic::widget::settings::item::builder(fl!("stopwatch")).control(stopwatch));
+
+        let all_settings = widget::button::text(fl!("all-settings")).on_press(Message::ShowAllSettings);
+
+        widget::column::with_capacity(2)
+            .push(section)
+            .push(all_settings)
+            .spacing(space_s)
+            .apply(widget::container)
+            .padding(space_s)
+            .width(Length::Fixed(QUICK_SETTINGS_WIDTH))
+            .class(cosmic::theme::Container::Dialog)
+            .into()
+    }
+}
+
+/// Labels for the context drawer width choices: the default, then each of `DRAWER_WIDTHS`.
+pub(super) fn drawer_width_labels() -> Vec<String> {
+    std::iter::once(fl!("drawer-width-default"))
+        .chain(DRAWER_WIDTHS.iter().map(|&width| fl!("drawer-width-px", width = width)))
+        .collect()
+}
+
+/// Labels for the app theme choices, in the order of `AppTheme::ALL`.
+pub(super) fn app_theme_labels() -> Vec<String> {
+    vec![fl!("theme-system"), fl!("theme-light"), fl!("theme-dark")]
+}
+
+/// Labels for the sound choices, in the order of `Sound::BUILT_IN`, then a file.
+pub(super) fn sound_labels() -> Vec<String> {
+    vec![
+        fl!("sound-silent"),
+        fl!("sound-chime"),
+        fl!("sound-bell"),
+        fl!("sound-beeps"),
+        fl!("sound-file"),
+    ]
+}
+
+/// Names the event a sound plays for, as in the settings.
+fn sound_event_label(event: SoundEvent) -> String {
+    match event {
+        SoundEvent::TimerFinished => fl!("sound-timer-finished"),
+        SoundEvent::Alarm => fl!("sound-alarm"),
+        SoundEvent::GoalReached => fl!("sound-goal-reached"),
+        SoundEvent::GameWon => fl!("sound-game-won"),
+    }
+}
+
+/// Labels for the stopwatch format choices, in the order of `WatchFormat::ALL`.
+pub(super) fn watch_format_labels() -> Vec<String> {
+    vec![fl!("watch-format-seconds"), fl!("watch-format-clock")]
+}
+
+/// Labels for the language choices: the system's, then each of `languages` by its own name.
+pub(super) fn language_labels(languages: &[LanguageIdentifier]) -> Vec<String> {
+    std::iter::once(fl!("language-system"))
+        .chain(languages.iter().map(i18n::native_name))
+        .collect()
+}
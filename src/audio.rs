@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Short sounds played for events, from built-in tones or the user's own files.
+
+use crate::error::{Code, Error};
+use rodio::Source;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Loudness of the built-in tones, as a share of full scale.
+const TONE_VOLUME: f32 = 0.2;
+/// Longest a chosen sound file plays for, so that picking a whole song does
+/// not keep playing over the sounds after it.
+const MAX_FILE_DURATION: Duration = Duration::from_secs(10);
+
+/// What plays when an event happens.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Sound {
+    #[default]
+    Silent,
+    /// Two rising notes.
+    Chime,
+    /// One long, high note.
+    Bell,
+    /// Three short beeps.
+    Beeps,
+    /// An audio file chosen by the user, in any format rodio decodes.
+    File(PathBuf),
+}
+
+impl Sound {
+    /// The sounds that need no file, in the order they are offered.
+    pub const BUILT_IN: [Sound; 4] = [Sound::Silent, Sound::Chime, Sound::Bell, Sound::Beeps];
+}
+
+/// An event that may play a sound.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum SoundEvent {
+    /// A countdown ran out.
+    TimerFinished,
+    /// An alarm went off.
+    Alarm,
+    /// A counter reached its goal.
+    GoalReached,
+    /// A round of the guessing game was won.
+    GameWon,
+}
+
+impl SoundEvent {
+    /// Every event, in the order they are listed in the settings.
+    pub const ALL: [SoundEvent; 4] = [
+        SoundEvent::TimerFinished,
+        SoundEvent::Alarm,
+        SoundEvent::GoalReached,
+        SoundEvent::GameWon,
+    ];
+}
+
+/// Plays `sound` to the end on the default audio output, or a sound file
+/// for its first [`MAX_FILE_DURATION`].
+pub async fn play(sound: Sound) -> Result<(), Error> {
+    if sound == Sound::Silent {
+        return Ok(());
+    }
+
+    // The output stream is not `Send`, so it lives and dies on a blocking thread.
+    tokio::task::spawn_blocking(move || play_blocking(&sound))
+        .await
+        .map_err(|why| Error::new(Code::Unavailable, why.to_string()))?
+}
+
+fn play_blocking(sound: &Sound) -> Result<(), Error> {
+    let unavailable = |why: &dyn fmt::Display| Error::new(Code::Unavailable, why.to_string());
+
+    let (_stream, handle) = rodio::OutputStream::try_default().map_err(|why| unavailable(&why))?;
+    let sink = rodio::Sink::try_new(&handle).map_err(|why| unavailable(&why))?;
+
+    let tone = |frequency: f32, millis: u64| {
+        sink.append(
+            rodio::source::SineWave::new(frequency)
+                .take_duration(Duration::from_millis(millis))
+                .amplify(TONE_VOLUME),
+        );
+    };
+
+    let pause = |millis: u64| {
+        sink.append(rodio::source::Zero::<f32>::new(1, 48_000).take_duration(Duration::from_millis(millis)));
+    };
+
+    match sound {
+        Sound::Silent => return Ok(()),
+        Sound::Chime => {
+            tone(660.0, 150);
+            tone(880.0, 250);
+        }
+        Sound::Bell => tone(1320.0, 600),
+        Sound::Beeps => {
+            for _ in 0..3 {
+                tone(990.0, 120);
+                pause(80);
+            }
+        }
+        Sound::File(path) => {
+            let file = std::fs::File::open(path)?;
+            let source = rodio::Decoder::new(BufReader::new(file))
+                .map_err(|why| Error::new(Code::InvalidData, why.to_string()))?;
+            sink.append(source.take_duration(MAX_FILE_DURATION));
+        }
+    }
+
+    sink.sleep_until_end();
+    Ok(())
+}
@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Backing up the configuration and data directories into one zip archive,
+//! and restoring them from it.
+//!
+//! An archive holds a manifest naming the app and the configuration version
+//! it was made with, then the configuration's files under `config/` and the
+//! data files under `data/`. Archives made for another app, or for another
+//! configuration version, are refused rather than restored.
+//!
+//! The zip archive is read and written with blocking I/O, so backing up and
+//! restoring run as [`io::blocking`] transfers, reporting how many bytes of
+//! the files they have handled.
+//!
+//! No file in a backup may exceed [`MAX_FILE_SIZE`], nor all of them together
+//! [`MAX_BACKUP_SIZE`], so a crafted archive cannot fill the disk when it is
+//! unpacked. Before a restore replaces anything, the current files are backed
+//! up to [`safety_copy`], and put back from there if the restore fails.
+
+use crate::app::AppModel;
+use crate::config::Config;
+use crate::error::{Code, Error};
+use crate::io;
+use crate::storage;
+use cosmic::Application;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+const MANIFEST: &str = "manifest.json";
+const CONFIG_PREFIX: &str = "config/";
+const DATA_PREFIX: &str = "data/";
+/// Largest file a backup may hold, unpacked.
+pub const MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+/// Largest all of a backup's files may be together, unpacked.
+pub const MAX_BACKUP_SIZE: u64 = 512 * 1024 * 1024;
+
+/// What a backup was made from, checked before it is restored.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Manifest {
+    pub app_id: String,
+    pub config_version: u64,
+    /// Version of the app that made the backup, shown when restoring.
+    pub app_version: String,
+    /// Unix time the backup was made.
+    pub created: i64,
+}
+
+/// Writes every configuration and data file to a zip archive at `path`,
+/// giving `None` if it was cancelled.
+///
+/// The archive is written next to `path` first, so a backup that does not
+/// finish leaves any file already there as it was.
+pub fn create(path: &Path, tracker: &io::Tracker) -> Result<Option<()>, Error> {
+    write(path, Some(tracker))
+}
+
+/// Writes the backup [`create`] does, which cannot be cancelled without a `tracker`.
+fn write(path: &Path, tracker: Option<&io::Tracker>) -> Result<Option<()>, Error> {
+    let manifest = Manifest {
+        app_id: AppModel::APP_ID.to_owned(),
+        config_version: Config::VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_owned(),
+        created: chrono::Local::now().timestamp(),
+    };
+
+    let partial = io::Partial::next_to(path);
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(partial.path())?);
+    let options = SimpleFileOptions::default();
+
+    zip.start_file(MANIFEST, options).map_err(zip_error)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(std::io::Error::from)?)?;
+
+    let mut entries = Vec::new();
+
+    for (prefix, dir) in [(CONFIG_PREFIX, storage::config_dir()), (DATA_PREFIX, storage::data_dir())] {
+        let Some(dir) = dir else {
+            continue;
+        };
+
+        for file in files(&dir) {
+            let Ok(relative) = file.strip_prefix(&dir) else {
+                continue;
+            };
+
+            let name = format!("{prefix}{}", relative.display());
+            entries.push((name, file));
+        }
+    }
+
+    let mut total = 0;
+
+    // A backup which could not be restored is refused before it is written.
+    for (name, file) in &entries {
+        let size = std::fs::metadata(file).map_or(0, |metadata| metadata.len());
+        check_size(name, size, total + size)?;
+        total += size;
+    }
+
+    let mut done = 0;
+
+    for (name, file) in entries {
+        if tracker.is_some_and(io::Tracker::is_cancelled) {
+            return Ok(None);
+        }
+
+        zip.start_file(name, options).map_err(zip_error)?;
+        done += std::io::copy(&mut std::fs::File::open(&file)?, &mut zip)?;
+
+        if let Some(tracker) = tracker {
+            tracker.advance(done, Some(total));
+        }
+    }
+
+    zip.finish().map_err(zip_error)?;
+    std::fs::rename(partial.path(), path)?;
+    partial.keep();
+
+    Ok(Some(()))
+}
+
+/// Reads the manifest of the backup at `path`, failing unless it can be
+/// restored into this version of the app.
+pub fn check(path: &Path) -> Result<Manifest, Error> {
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?).map_err(zip_error)?;
+
+    let mut text = String::new();
+    archive
+        .by_name(MANIFEST)
+        .map_err(|_| Error::new(Code::InvalidData, "not a backup of this app"))?
+        .read_to_string(&mut text)?;
+
+    let manifest: Manifest =
+        serde_json::from_str(&text).map_err(|why| Error::new(Code::InvalidData, why.to_string()))?;
+
+    if manifest.app_id != AppModel::APP_ID {
+        return Err(Error::new(
+            Code::InvalidData,
+            format!("backup of another app, {}", manifest.app_id),
+        ));
+    }
+
+    if manifest.config_version != Config::VERSION {
+        return Err(Error::new(
+            Code::InvalidData,
+            format!(
+                "backup of configuration version {}, while this app uses version {}",
+                manifest.config_version,
+                Config::VERSION
+            ),
+        ));
+    }
+
+    Ok(manifest)
+}
+
+/// Replaces the configuration and data files with those in the backup at `path`.
+///
+/// Every file of the backup is checked before anything is touched. The current
+/// files are then backed up to [`safety_copy`] and removed, so none the backup
+/// lacks are left mixed in with it. Once files are being replaced, the restore
+/// carries on even if cancelled, and if it fails, the files from before are
+/// put back.
+pub fn restore(path: &Path, tracker: &io::Tracker) -> Result<Option<()>, Error> {
+    check(path)?;
+
+    let mut archive = zip::ZipArchive::new(std::fs::File::open(path)?).map_err(zip_error)?;
+    let (files, total) = contents(&mut archive)?;
+
+    if tracker.is_cancelled() {
+        return Ok(None);
+    }
+
+    let safety = safety_copy().ok_or_else(|| Error::new(Code::NotFound, "no cache directory for a safety copy"))?;
+
+    if let Some(parent) = safety.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    write(&safety, None)?;
+
+    if let Err(why) = unpack(&mut archive, &files, Some((tracker, total))) {
+        let put_back = std::fs::File::open(&safety)
+            .map_err(Error::from)
+            .and_then(|file| zip::ZipArchive::new(file).map_err(zip_error))
+            .and_then(|mut previous| {
+                let (files, _) = contents(&mut previous)?;
+                unpack(&mut previous, &files, None)
+            });
+
+        return Err(match put_back {
+            Ok(()) => why,
+            Err(undo) => Error::new(
+                why.code,
+                format!(
+                    "{}, and the files from before could not be put back from {}: {}",
+                    why.message,
+                    safety.display(),
+                    undo.message
+                ),
+            ),
+        });
+    }
+
+    Ok(Some(()))
+}
+
+/// Where the files from before a restore are kept, under `$XDG_CACHE_HOME`.
+pub fn safety_copy() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join(AppModel::APP_ID).join("before-restore.zip"))
+}
+
+/// The files of `archive` a restore unpacks, as their index in it and where
+/// they go, with their size together.
+fn contents(archive: &mut zip::ZipArchive<std::fs::File>) -> Result<(Vec<(usize, PathBuf)>, u64), Error> {
+    let mut files = Vec::new();
+    let mut total = 0;
+
+    for idx in 0..archive.len() {
+        let entry = archive.by_index(idx).map_err(zip_error)?;
+
+        // Names leaving the archive's own tree, as with `..`, are skipped.
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+
+        let target = if let Ok(relative) = name.strip_prefix(CONFIG_PREFIX) {
+            storage::config_dir().map(|dir| dir.join(relative))
+        } else if let Ok(relative) = name.strip_prefix(DATA_PREFIX) {
+            storage::data_dir().map(|dir| dir.join(relative))
+        } else {
+            None
+        };
+
+        let Some(target) = target.filter(|_| entry.is_file()) else {
+            continue;
+        };
+
+        check_size(entry.name(), entry.size(), total + entry.size())?;
+        total += entry.size();
+        files.push((idx, target));
+    }
+
+    Ok((files, total))
+}
+
+/// Removes the configuration and data directories, then unpacks `files` of
+/// `archive` in their place, reporting to the tracker of `progress` if given.
+fn unpack(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    files: &[(usize, PathBuf)],
+    progress: Option<(&io::Tracker, u64)>,
+) -> Result<(), Error> {
+    for dir in [storage::config_dir(), storage::data_dir()].into_iter().flatten() {
+        match std::fs::remove_dir_all(&dir) {
+            Err(why) if why.kind() != std::io::ErrorKind::NotFound => return Err(why.into()),
+            _ => {}
+        }
+    }
+
+    let mut done = 0;
+
+    for (idx, target) in files {
+        let mut entry = archive.by_index(*idx).map_err(zip_error)?;
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // Sizes were checked as the archive states them, which it may understate.
+        let written = std::io::copy(&mut (&mut entry).take(MAX_FILE_SIZE + 1), &mut std::fs::File::create(target)?)?;
+        done += written;
+        check_size(entry.name(), written, done)?;
+
+        if let Some((tracker, total)) = progress {
+            tracker.advance(done, Some(total));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fails if the file `name` of `size` bytes, bringing the backup to `total`
+/// bytes, is larger than a backup may hold.
+fn check_size(name: &str, size: u64, total: u64) -> Result<(), Error> {
+    const MIB: u64 = 1024 * 1024;
+
+    if size > MAX_FILE_SIZE {
+        Err(Error::new(
+            Code::InvalidData,
+            format!("{name} is larger than {} MiB", MAX_FILE_SIZE / MIB),
+        ))
+    } else if total > MAX_BACKUP_SIZE {
+        Err(Error::new(
+            Code::InvalidData,
+            format!("the files are larger than {} MiB together", MAX_BACKUP_SIZE / MIB),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Every file below `dir`, which is empty if it does not exist.
+fn files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            found.extend(files(&path));
+        } else if path.is_file() {
+            found.push(path);
+        }
+    }
+
+    found
+}
+
+fn zip_error(why: zip::result::ZipError) -> Error {
+    match why {
+        zip::result::ZipError::Io(why) => Error::from(why),
+        why => Error::new(Code::InvalidData, why.to_string()),
+    }
+}
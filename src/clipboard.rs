@@ -0,0 +1,347 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Following the system clipboard through the Wayland data control protocol.
+//!
+//! Reading the clipboard through a window only works while it has focus, so
+//! copies made in other apps would be missed. Data control lets a client read
+//! the clipboard at any time instead, and tells it each time the clipboard
+//! changes hands. Not every compositor offers it, and some only once it has
+//! been enabled; without it, [`watch`] reports the clipboard as unavailable
+//! and stops.
+//!
+//! Password managers mark what they copy with [`SECRET_HINT`], as the app
+//! does with [`write_secret`] for its own passwords. Such copies are left out.
+//! Each read waits at most [`READ_TIMEOUT`] for the copying app to hand the
+//! text over, so an app that never answers cannot hold the watch up.
+
+use cosmic::Task;
+use cosmic::cctk::wayland_client::globals::{GlobalListContents, registry_queue_init};
+use cosmic::cctk::wayland_client::protocol::{wl_registry, wl_seat};
+use cosmic::cctk::wayland_client::{self, Connection, Dispatch, Proxy, QueueHandle, backend::ObjectId};
+use cosmic::iced::Subscription;
+use cosmic::iced::clipboard::{self, mime::AsMimeTypes};
+use cosmic::iced_futures;
+use futures_util::SinkExt;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{ErrorKind, Read};
+use std::os::fd::{AsFd, AsRawFd, FromRawFd, OwnedFd};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_device_v1::{self, ZwlrDataControlDeviceV1};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_manager_v1::{self, ZwlrDataControlManagerV1};
+use wayland_protocols_wlr::data_control::v1::client::zwlr_data_control_offer_v1::{self, ZwlrDataControlOfferV1};
+
+/// Longest the app that copied is waited on for the text, after which the
+/// copy is skipped.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+/// Most of a copied text read, so that a huge copy cannot exhaust memory.
+const MAX_TEXT_SIZE: usize = 64 * 1024;
+/// Type offered alongside a copy to say whether it holds a secret, which it
+/// does if the type's content is `secret`.
+const SECRET_HINT: &str = "x-kde-passwordManagerHint";
+/// Types text may be offered as, most preferred first.
+const TEXT_TYPES: [&str; 5] = ["text/plain;charset=utf-8", "UTF8_STRING", "text/plain", "TEXT", "STRING"];
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// Text was copied, possibly cut to [`MAX_TEXT_SIZE`].
+    Copied(String),
+    /// The clipboard cannot be followed, for the given reason.
+    Unavailable(String),
+}
+
+/// Emits the text on the clipboard each time it changes, starting with what
+/// is on it already.
+pub fn watch() -> Subscription<Event> {
+    Subscription::run_with_id(
+        "clipboard",
+        iced_futures::stream::channel(1, |mut emitter| async move {
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+
+            // The protocol is read on a thread of its own, which blocks
+            // waiting for the compositor's events.
+            std::thread::spawn(move || {
+                let unavailable = sender.clone();
+
+                if let Err(why) = run(sender) {
+                    _ = unavailable.send(Event::Unavailable(why.to_string()));
+                }
+            });
+
+            while let Some(event) = receiver.recv().await {
+                _ = emitter.send(event).await;
+            }
+        }),
+    )
+}
+
+/// Puts `text` on the clipboard marked as a secret, so clipboard histories,
+/// this app's included, leave it out.
+pub fn write_secret<T>(text: String) -> Task<T> {
+    clipboard::write_data(Secret(text))
+}
+
+/// Copied text, offered as plain text and with [`SECRET_HINT`].
+struct Secret(String);
+
+impl AsMimeTypes for Secret {
+    fn available(&self) -> Cow<'static, [String]> {
+        TEXT_TYPES
+            .iter()
+            .chain([&SECRET_HINT])
+            .map(|mime_type| (*mime_type).to_owned())
+            .collect()
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<Cow<'static, [u8]>> {
+        if mime_type == SECRET_HINT {
+            Some(Cow::Borrowed(b"secret"))
+        } else if TEXT_TYPES.contains(&mime_type) {
+            Some(Cow::Owned(self.0.clone().into_bytes()))
+        } else {
+            None
+        }
+    }
+}
+
+fn run(sender: mpsc::UnboundedSender<Event>) -> Result<(), Box<dyn std::error::Error>> {
+    let connection = Connection::connect_to_env()?;
+    let (globals, mut queue) = registry_queue_init(&connection)?;
+    let qh = queue.handle();
+
+    let manager: ZwlrDataControlManagerV1 = globals
+        .bind(&qh, 1..=2, ())
+        .map_err(|_| "the compositor does not offer data control")?;
+    let seat: wl_seat::WlSeat = globals.bind(&qh, 1..=1, ()).map_err(|_| "there is no seat")?;
+    let _device = manager.get_data_device(&seat, &qh, ());
+
+    let mut state = State {
+        connection: connection.clone(),
+        offers: HashMap::new(),
+        finished: false,
+        sender,
+    };
+
+    loop {
+        queue.blocking_dispatch(&mut state)?;
+
+        if state.finished {
+            return Err("the compositor stopped sharing the clipboard".into());
+        }
+
+        // The app stopped listening, as when the page stopped following.
+        if state.sender.is_closed() {
+            return Ok(());
+        }
+    }
+}
+
+struct State {
+    connection: Connection,
+    /// Types each offer not yet taken or dropped can be read as.
+    offers: HashMap<ObjectId, Vec<String>>,
+    /// Whether the clipboard can no longer be followed.
+    finished: bool,
+    sender: mpsc::UnboundedSender<Event>,
+}
+
+impl State {
+    /// Sends the text `offer` holds, unless it is a secret or not text at all.
+    fn take(&mut self, offer: &ZwlrDataControlOfferV1) {
+        let mime_types = self.offers.remove(&offer.id()).unwrap_or_default();
+
+        if mime_types.iter().any(|mime_type| mime_type == SECRET_HINT)
+            && self.receive(offer, SECRET_HINT).is_none_or(|hint| hint.trim_ascii() == b"secret")
+        {
+            return;
+        }
+
+        let offered = |mime_type: &&str| mime_types.iter().any(|offered| offered == mime_type);
+
+        let Some(mime_type) = TEXT_TYPES.into_iter().find(offered) else {
+            return;
+        };
+
+        let Some(bytes) = self.receive(offer, mime_type) else {
+            return;
+        };
+
+        let text = String::from_utf8_lossy(&bytes).into_owned();
+
+        if !text.is_empty() {
+            _ = self.sender.send(Event::Copied(text));
+        }
+    }
+
+    /// Reads what `offer` holds as `mime_type`, or `None` if the copying app
+    /// does not hand it over in time.
+    fn receive(&self, offer: &ZwlrDataControlOfferV1, mime_type: &str) -> Option<Vec<u8>> {
+        let (reader, writer) = pipe().ok()?;
+        offer.receive(mime_type.to_owned(), writer.as_fd());
+
+        // The copying app writes once the request reaches it, and the pipe
+        // ends when it closes its end, so ours is closed right away.
+        self.connection.flush().ok()?;
+        drop(writer);
+
+        match read_until(std::fs::File::from(reader), Instant::now() + READ_TIMEOUT) {
+            Ok(bytes) => Some(bytes),
+            Err(why) => {
+                if why.kind() != ErrorKind::TimedOut {
+                    eprintln!("failed to read the clipboard: {why}");
+                }
+
+                None
+            }
+        }
+    }
+}
+
+impl Dispatch<wl_registry::WlRegistry, GlobalListContents> for State {
+    fn event(
+        _state: &mut Self,
+        _registry: &wl_registry::WlRegistry,
+        _event: wl_registry::Event,
+        _data: &GlobalListContents,
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<wl_seat::WlSeat, ()> for State {
+    fn event(
+        _state: &mut Self,
+        _seat: &wl_seat::WlSeat,
+        _event: wl_seat::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlManagerV1, ()> for State {
+    fn event(
+        _state: &mut Self,
+        _manager: &ZwlrDataControlManagerV1,
+        _event: zwlr_data_control_manager_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrDataControlDeviceV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        _device: &ZwlrDataControlDeviceV1,
+        event: zwlr_data_control_device_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_data_control_device_v1::Event::DataOffer { id } => {
+                state.offers.insert(id.id(), Vec::new());
+            }
+            zwlr_data_control_device_v1::Event::Selection { id: Some(offer) } => {
+                state.take(&offer);
+                offer.destroy();
+            }
+            // The selection made by highlighting text is not followed.
+            zwlr_data_control_device_v1::Event::PrimarySelection { id: Some(offer) } => {
+                state.offers.remove(&offer.id());
+                offer.destroy();
+            }
+            zwlr_data_control_device_v1::Event::Finished => {
+                state.finished = true;
+            }
+            _ => {}
+        }
+    }
+
+    wayland_client::event_created_child!(State, ZwlrDataControlDeviceV1, [
+        zwlr_data_control_device_v1::EVT_DATA_OFFER_OPCODE => (ZwlrDataControlOfferV1, ()),
+    ]);
+}
+
+impl Dispatch<ZwlrDataControlOfferV1, ()> for State {
+    fn event(
+        state: &mut Self,
+        offer: &ZwlrDataControlOfferV1,
+        event: zwlr_data_control_offer_v1::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qh: &QueueHandle<Self>,
+    ) {
+        if let zwlr_data_control_offer_v1::Event::Offer { mime_type } = event {
+            state.offers.entry(offer.id()).or_default().push(mime_type);
+        }
+    }
+}
+
+/// A pipe's read and write ends.
+fn pipe() -> std::io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds = [0; 2];
+
+    // SAFETY: `fds` has room for the two descriptors written.
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // SAFETY: both descriptors were just opened and are owned by nothing else.
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+/// Reads `pipe` to its end or to [`MAX_TEXT_SIZE`], failing with
+/// [`ErrorKind::TimedOut`] if it is still open at `deadline`.
+fn read_until(mut pipe: impl Read + AsRawFd, deadline: Instant) -> std::io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut chunk = [0; 4096];
+
+    while bytes.len() < MAX_TEXT_SIZE {
+        let left = deadline.saturating_duration_since(Instant::now());
+
+        if left.is_zero() {
+            return Err(ErrorKind::TimedOut.into());
+        }
+
+        let mut poll = libc::pollfd {
+            fd: pipe.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        let timeout = i32::try_from(left.as_millis()).unwrap_or(i32::MAX).max(1);
+
+        // SAFETY: `poll` is one valid entry, borrowed only for the call.
+        match unsafe { libc::poll(&mut poll, 1, timeout) } {
+            0 => return Err(ErrorKind::TimedOut.into()),
+            ready if ready < 0 => {
+                let why = std::io::Error::last_os_error();
+
+                if why.kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+
+                return Err(why);
+            }
+            // Readable or closed, so the read returns without waiting.
+            _ => {}
+        }
+
+        let read = pipe.read(&mut chunk)?;
+
+        if read == 0 {
+            break;
+        }
+
+        bytes.extend_from_slice(&chunk[..read]);
+    }
+
+    bytes.truncate(MAX_TEXT_SIZE);
+    Ok(bytes)
+}
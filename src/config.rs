@@ -1,9 +1,296 @@
 // SPDX-License-Identifier: GPL-3
 
+use crate::counters;
+use crate::audio::{Sound, SoundEvent};
+use crate::game::Difficulty;
+use crate::generators::password::CharClasses;
+use crate::speech;
+use crate::timer::{self, Alarm};
 use cosmic::cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 
-#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
-#[version = 1]
-pub struct Config {
-    demo: String,
+/// Declares [`Config`] along with a [`ConfigField`] for each of its fields, and
+/// the methods comparing and copying them, so that no field can be left out
+/// of one of them.
+macro_rules! config {
+    (
+        $(#[$attr:meta])*
+        pub struct Config {
+            $(
+                $(#[$field_attr:meta])*
+                $vis:vis $field:ident: $ty:ty => $variant:ident,
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        pub struct Config {
+            $(
+                $(#[$field_attr])*
+                $vis $field: $ty,
+            )*
+        }
+
+        /// A field of [`Config`] whose value has changed.
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub enum ConfigField {
+            $($variant,)*
+        }
+
+        impl Config {
+            /// Lists the fields whose values differ between `self` and `other`.
+            pub fn diff(&self, other: &Self) -> Vec<ConfigField> {
+                let mut changes = Vec::new();
+
+                $(
+                    if self.$field != other.$field {
+                        changes.push(ConfigField::$variant);
+                    }
+                )*
+
+                changes
+            }
+
+            /// Copies the value of `field` from `other`.
+            pub fn copy_field(&mut self, other: &Self, field: ConfigField) {
+                match field {
+                    $(ConfigField::$variant => self.$field.clone_from(&other.$field),)*
+                }
+            }
+        }
+    };
+}
+
+config! {
+    #[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq, Serialize)]
+    #[version = 1]
+    pub struct Config {
+        demo: String => Demo,
+        /// Pause the stopwatch after this many minutes of inactivity.
+        pub idle_pause_minutes: Option<u32> => IdlePauseMinutes,
+        /// Regenerate passwords until their estimated entropy reaches this many bits.
+        pub min_entropy_bits: Option<u32> => MinEntropyBits,
+        /// Highest guessing game campaign level completed.
+        pub campaign_completed: u32 => CampaignCompleted,
+        /// Show an on-screen number pad beneath the guess field and counter value fields.
+        pub show_numpad: bool => ShowNumpad,
+        /// Counters shown on the counter page, by group.
+        pub counter_groups: Vec<counters::Group> => CounterGroups,
+        /// Length of the countdown in seconds, or zero for the default.
+        pub countdown_secs: u64 => CountdownSecs,
+        /// Shell command run each time a countdown finishes, once the user confirmed it.
+        pub countdown_command: Option<String> => CountdownCommand,
+        /// Speak the last seconds of a countdown, and its end, aloud.
+        pub spoken_countdown: bool => SpokenCountdown,
+        /// The voice countdowns are spoken in.
+        pub speech_voice: speech::Voice => SpeechVoice,
+        /// How fast countdowns are spoken, from -100 for slowest to 100 for fastest.
+        pub speech_rate: i32 => SpeechRate,
+        /// Alarms on the watch page.
+        pub alarms: Vec<Alarm> => Alarms,
+        /// Announce every this many minutes of stopwatch time, if set.
+        pub milestone_minutes: Option<u32> => MilestoneMinutes,
+        /// Let the desktop's Play/Pause media keys control the watch page's timers.
+        pub media_keys: bool => MediaKeys,
+        /// Do not show decorative images at the top of each page.
+        pub hide_header_images: bool => HideHeaderImages,
+        /// User-chosen header images, by the page's position in the nav bar.
+        pub header_images: BTreeMap<usize, PathBuf> => HeaderImages,
+        /// Use high-contrast styles even when the system theme does not ask for them.
+        pub high_contrast: bool => HighContrast,
+        /// Keep animations still, showing only their fading highlights.
+        pub reduce_motion: bool => ReduceMotion,
+        /// Show only the icons of the nav bar's items.
+        pub condensed_nav: bool => CondensedNav,
+        /// Condense the nav bar while the window is too narrow to fit page names beside the content.
+        pub auto_condense_nav: bool => AutoCondenseNav,
+        /// Mark color-coded feedback with symbols as well, so it reads without telling colors apart.
+        pub explicit_feedback: bool => ExplicitFeedback,
+        /// Shortcuts replacing the defaults, by action name, such as `quit` to
+        /// `Ctrl+W`. An empty shortcut leaves the action unbound.
+        pub key_binds: BTreeMap<String, String> => KeyBinds,
+        /// Position in the nav bar of the page shown at startup.
+        pub start_page: usize => StartPage,
+        /// Minimize the window as soon as the application starts.
+        pub start_minimized: bool => StartMinimized,
+        /// Keep the stopwatch running at launch if it was running when the app quit.
+        pub resume_timers: bool => ResumeTimers,
+        /// Switch to the page last shown on a workspace when the window moves to it.
+        pub page_per_workspace: bool => PagePerWorkspace,
+        /// The page last shown on each workspace, by the workspace's name.
+        pub workspace_pages: BTreeMap<String, usize> => WorkspacePages,
+        /// Pages pinned to the top of the nav bar, by position, in the order they were pinned.
+        pub pinned_pages: Vec<usize> => PinnedPages,
+        /// Order of the pages below those pinned, by position. Pages left out
+        /// follow the rest in their usual order.
+        pub nav_order: Vec<usize> => NavOrder,
+        /// Pages left out of the nav bar, by position.
+        pub hidden_pages: Vec<usize> => HiddenPages,
+        /// Also announce won games and finished countdowns in desktop notifications.
+        pub desktop_notifications: bool => DesktopNotifications,
+        /// The sound played for each event. Events without one stay silent.
+        pub sounds: BTreeMap<SoundEvent, Sound> => Sounds,
+        /// Keep every event silent, without forgetting the sound each one plays.
+        pub mute_sounds: bool => MuteSounds,
+        /// Number of characters in generated passwords, or zero for the default.
+        pub password_length: u32 => PasswordLength,
+        /// Character classes generated passwords draw from.
+        pub password_classes: CharClasses => PasswordClasses,
+        /// Size of the main window in logical pixels, restored at launch.
+        pub window_size: Option<(u32, u32)> => WindowSize,
+        /// Position of the main window's top left corner, restored at launch where the
+        /// windowing system lets apps place their windows.
+        pub window_position: Option<(i32, i32)> => WindowPosition,
+        /// Width of the context drawer's content in logical pixels, or zero for the default.
+        pub context_drawer_width: u32 => ContextDrawerWidth,
+        /// Range of free play rounds in the guessing game.
+        pub game_difficulty: Difficulty => GameDifficulty,
+        /// Fewest attempts taken to win a free play round, by difficulty.
+        #[serde(serialize_with = "serialize_pairs")]
+        pub game_best: BTreeMap<Difficulty, u32> => GameBest,
+        /// Name last entered for the leaderboard, offered again on the next record.
+        pub player_name: String => PlayerName,
+        /// Whether the app follows the system's light or dark theme, or overrides it.
+        pub app_theme: AppTheme => AppTheme,
+        /// How the stopwatch's elapsed time is written.
+        pub watch_format: timer::WatchFormat => WatchFormat,
+        /// Language tag of the translations to show, or `None` to follow the system.
+        pub language: Option<String> => Language,
+    }
+}
+
+/// The theme the app is drawn with, unless an imported theme is applied.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AppTheme {
+    /// Follow the system's theme.
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl AppTheme {
+    /// Every choice, in the order offered in the settings.
+    pub const ALL: [AppTheme; 3] = [AppTheme::System, AppTheme::Light, AppTheme::Dark];
+
+    /// The theme to draw the app with.
+    ///
+    /// Overrides use the system's own light or dark theme, so its accent color and
+    /// other customizations are kept.
+    pub fn theme(self) -> cosmic::Theme {
+        match self {
+            AppTheme::System => cosmic::theme::system_preference(),
+            AppTheme::Light => {
+                let mut theme = cosmic::theme::system_light();
+                theme.theme_type.prefer_dark(Some(false));
+                theme
+            }
+            AppTheme::Dark => {
+                let mut theme = cosmic::theme::system_dark();
+                theme.theme_type.prefer_dark(Some(true));
+                theme
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Applies the changes made to `self` since `base` onto `theirs`, which was
+    /// changed elsewhere since `base`.
+    ///
+    /// Fields both changed to different values keep the value in `theirs`,
+    /// and are listed as conflicts for the user to settle.
+    pub fn merge(&self, base: &Self, theirs: &Self) -> Merge {
+        let their_changes = base.diff(theirs);
+        let differences = self.diff(theirs);
+        let mut merge = Merge {
+            config: theirs.clone(),
+            conflicts: Vec::new(),
+        };
+
+        for field in base.diff(self) {
+            if their_changes.contains(&field) && differences.contains(&field) {
+                merge.conflicts.push(field);
+            } else {
+                merge.config.copy_field(self, field);
+            }
+        }
+
+        merge
+    }
+}
+
+/// Two versions of the configuration brought together by [`Config::merge`].
+pub struct Merge {
+    pub config: Config,
+    /// Fields changed in both versions to different values.
+    pub conflicts: Vec<ConfigField>,
+}
+
+/// Writes a map as a list of key and value pairs, for formats such as JSON
+/// whose map keys must be strings.
+fn serialize_pairs<S, K, V>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    K: Serialize,
+    V: Serialize,
+{
+    serializer.collect_seq(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_changes_to_different_fields() {
+        let base = Config::default();
+        let ours = Config {
+            show_numpad: true,
+            ..base.clone()
+        };
+        let theirs = Config {
+            speech_rate: 20,
+            ..base.clone()
+        };
+
+        let merge = ours.merge(&base, &theirs);
+
+        assert!(merge.config.show_numpad);
+        assert_eq!(merge.config.speech_rate, 20);
+        assert!(merge.conflicts.is_empty());
+    }
+
+    #[test]
+    fn merge_accepts_the_same_change_made_twice() {
+        let base = Config::default();
+        let ours = Config {
+            start_page: 2,
+            ..base.clone()
+        };
+
+        let merge = ours.merge(&base, &ours);
+
+        assert_eq!(merge.config.start_page, 2);
+        assert!(merge.conflicts.is_empty());
+    }
+
+    #[test]
+    fn merge_keeps_theirs_when_both_change_a_field() {
+        let base = Config::default();
+        let ours = Config {
+            start_page: 2,
+            ..base.clone()
+        };
+        let theirs = Config {
+            start_page: 3,
+            ..base.clone()
+        };
+
+        let merge = ours.merge(&base, &theirs);
+
+        assert_eq!(merge.config.start_page, 3);
+        assert_eq!(merge.conflicts, [ConfigField::StartPage]);
+    }
 }
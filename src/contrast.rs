@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Whether custom widget styles should use their high-contrast variants.
+//!
+//! Style closures only receive the theme, so the user's manual preference is
+//! kept here for them to read alongside the system's high-contrast setting.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The user asked for high contrast regardless of the system setting.
+static FORCED: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether high contrast is used even when the system theme does not ask for it.
+pub fn set_forced(forced: bool) {
+    FORCED.store(forced, Ordering::Relaxed);
+}
+
+/// Whether styles drawn with `theme` should use their high-contrast variants.
+pub fn is_high_contrast(theme: &cosmic::Theme) -> bool {
+    FORCED.load(Ordering::Relaxed) || theme.cosmic().is_high_contrast
+}
@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Named counters organized into groups, and the history of their values.
+//!
+//! Counters live in the configuration, while their history is kept on its own
+//! in the application's data directory, since it grows with every change and
+//! the configuration is written far more often than it needs to be read.
+
+use crate::format::NumberFormat;
+use crate::storage;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDateTime, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Most entries kept in a counter's history, after which the oldest are dropped.
+const HISTORY_LIMIT: usize = 1000;
+
+/// A named running count.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Counter {
+    /// Identifies the counter's history while counters are added, removed, and
+    /// renamed, or zero until [`assign_ids`] gives it one.
+    #[serde(default)]
+    pub id: u64,
+    pub name: String,
+    pub value: i64,
+    /// The value the user is counting towards, if any.
+    #[serde(default)]
+    pub goal: Option<i64>,
+    /// Amount each press of + or - changes the value by, or zero for one.
+    #[serde(default)]
+    pub step: u32,
+    /// Lowest value the counter goes down to, if limited.
+    #[serde(default)]
+    pub min: Option<i64>,
+    /// Highest value the counter goes up to, if limited.
+    #[serde(default)]
+    pub max: Option<i64>,
+    /// How the value is displayed.
+    #[serde(default)]
+    pub format: NumberFormat,
+    /// When the value automatically returns to zero, if ever.
+    #[serde(default)]
+    pub reset: Option<ResetSchedule>,
+    /// Unix time of the last automatic reset, or of when the schedule was set.
+    #[serde(default)]
+    pub last_reset: Option<i64>,
+}
+
+impl Counter {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: new_id(),
+            name,
+            ..Self::default()
+        }
+    }
+
+    /// Amount each press of + or - changes the value by.
+    pub fn step(&self) -> i64 {
+        i64::from(self.step.max(1))
+    }
+
+    /// Keeps `value` within the counter's bounds.
+    pub fn clamp(&self, value: i64) -> i64 {
+        let value = self.min.map_or(value, |min| value.max(min));
+        self.max.map_or(value, |max| value.min(max))
+    }
+
+    /// Adds `delta` to the value, stopping at its bounds, and returns whether
+    /// this reached the goal.
+    pub fn add(&mut self, delta: i64) -> bool {
+        let before = self.value;
+        self.value = self.clamp(self.value.saturating_add(delta));
+
+        self.goal.is_some_and(|goal| {
+            before != goal && before.min(self.value) <= goal && goal <= before.max(self.value)
+        })
+    }
+
+    /// Sets the reset schedule, counting from now.
+    pub fn set_reset(&mut self, reset: Option<ResetSchedule>) {
+        self.reset = reset;
+        self.last_reset = reset.map(|_| Local::now().timestamp());
+    }
+
+    /// Resets the value if a scheduled reset has come due since the last one,
+    /// returning whether it was reset.
+    pub fn reset_if_due(&mut self, now: DateTime<Local>) -> bool {
+        let (Some(reset), Some(last_reset)) = (self.reset, self.last_reset) else {
+            return false;
+        };
+
+        let Some(due) = reset.previous(now) else {
+            return false;
+        };
+
+        if due.timestamp() <= last_reset {
+            return false;
+        }
+
+        self.reset();
+        self.last_reset = Some(now.timestamp());
+
+        true
+    }
+
+    /// Returns the value to zero, or to the bound nearest it if zero is out of bounds.
+    pub fn reset(&mut self) {
+        self.value = self.clamp(0);
+    }
+
+    /// Fraction of the way from zero to the goal, clamped to `0.0..=1.0`.
+    pub fn progress(&self) -> Option<f32> {
+        let goal = self.goal.filter(|&goal| goal != 0)?;
+        Some((self.value as f32 / goal as f32).clamp(0.0, 1.0))
+    }
+}
+
+/// When a counter automatically returns to zero.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ResetSchedule {
+    /// Every day at the given local time.
+    Daily { hour: u32, minute: u32 },
+    /// Every week on the given day, counted from Monday as 0, at the given local time.
+    Weekly { weekday: u32, hour: u32, minute: u32 },
+}
+
+impl ResetSchedule {
+    pub fn hour(self) -> u32 {
+        match self {
+            ResetSchedule::Daily { hour, .. } | ResetSchedule::Weekly { hour, .. } => hour,
+        }
+    }
+
+    pub fn minute(self) -> u32 {
+        match self {
+            ResetSchedule::Daily { minute, .. } | ResetSchedule::Weekly { minute, .. } => minute,
+        }
+    }
+
+    /// The most recent time at or before `now` the reset was scheduled for.
+    ///
+    /// On a day the clocks skip over the scheduled time, the reset is due at
+    /// the first time after it that the day does have.
+    pub fn previous(self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let time = NaiveTime::from_hms_opt(self.hour(), self.minute(), 0)?;
+        let today = now.date_naive();
+
+        let days_back = match self {
+            ResetSchedule::Daily { .. } => 0,
+            ResetSchedule::Weekly { weekday, .. } => {
+                (today.weekday().num_days_from_monday() + 7 - weekday % 7) % 7
+            }
+        };
+
+        let mut date = today - Duration::days(i64::from(days_back));
+
+        loop {
+            let due = local_time(date.and_time(time))?;
+
+            if due <= now {
+                return Some(due);
+            }
+
+            date -= Duration::days(match self {
+                ResetSchedule::Daily { .. } => 1,
+                ResetSchedule::Weekly { .. } => 7,
+            });
+        }
+    }
+}
+
+/// The moment `time` happens locally, or the first minute after it that does
+/// when a clock change skips over it.
+fn local_time(time: NaiveDateTime) -> Option<DateTime<Local>> {
+    // Clock changes skip an hour or so, never a whole day.
+    (0..=24 * 60).find_map(|minutes| {
+        Local
+            .from_local_datetime(&(time + Duration::minutes(minutes)))
+            .earliest()
+    })
+}
+
+/// A past value of a counter.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HistoryEntry {
+    /// Unix time the value was recorded.
+    pub timestamp: i64,
+    pub value: i64,
+    pub kind: HistoryKind,
+}
+
+/// Why a value was recorded in a counter's history.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HistoryKind {
+    /// The value just before a reset, which is followed by a change to the value after it.
+    Reset,
+    /// The value after any change, whether stepped, typed, kept within new bounds, or undone.
+    Change,
+}
+
+/// The values every counter has had, by counter ID, each oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    counters: HashMap<u64, Vec<HistoryEntry>>,
+}
+
+impl History {
+    /// Loads the history from disk, starting empty if it is missing or unreadable.
+    ///
+    /// A history that cannot be understood is moved aside to a `.bad` file
+    /// next to it, so that saving the new history does not destroy it.
+    pub fn load() -> Self {
+        let Some(path) = history_path() else {
+            return Self::default();
+        };
+
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str(&text) {
+            Ok(counters) => Self { counters },
+            Err(why) => {
+                let bad = path.with_extension("json.bad");
+                eprintln!("failed to read counter history, moving it to {}: {why}", bad.display());
+
+                if let Err(why) = std::fs::rename(&path, &bad) {
+                    eprintln!("failed to move the counter history aside: {why}");
+                }
+
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = history_path() else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string(&self.counters)?;
+                std::fs::write(&path, json)
+            });
+
+        if let Err(why) = result {
+            eprintln!("failed to save counter history: {why}");
+        }
+    }
+
+    /// The entries recorded for the counter with the ID `id`, oldest first.
+    pub fn entries(&self, id: u64) -> &[HistoryEntry] {
+        self.counters.get(&id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Records that a counter went from `before` to `after` at `timestamp`,
+    /// noting the value it had first if this was a reset. The oldest entries
+    /// past the limit are dropped.
+    pub fn record(&mut self, id: u64, timestamp: i64, before: i64, after: i64, reset: bool) {
+        let entries = self.counters.entry(id).or_default();
+
+        if reset {
+            entries.push(HistoryEntry {
+                timestamp,
+                value: before,
+                kind: HistoryKind::Reset,
+            });
+        }
+
+        entries.push(HistoryEntry {
+            timestamp,
+            value: after,
+            kind: HistoryKind::Change,
+        });
+
+        let excess = entries.len().saturating_sub(HISTORY_LIMIT);
+        entries.drain(..excess);
+    }
+
+    /// Forgets the history of every counter `keep` returns false for.
+    pub fn retain(&mut self, mut keep: impl FnMut(u64) -> bool) {
+        self.counters.retain(|&id, _| keep(id));
+    }
+
+    /// The value of the counter with the ID `id` at each of `points` equal
+    /// steps of time from its first entry to `now`, oldest first, or nothing
+    /// if it has no entries.
+    pub fn timeline(&self, id: u64, now: i64, points: usize) -> Vec<i64> {
+        let entries = self.entries(id);
+
+        let Some(first) = entries.first() else {
+            return Vec::new();
+        };
+
+        let span = now.saturating_sub(first.timestamp).max(0);
+        let mut value = first.value;
+        let mut next = 0;
+
+        (1..=points)
+            .map(|point| {
+                let time = first.timestamp.saturating_add(span.saturating_mul(point as i64) / points as i64);
+
+                while let Some(entry) = entries.get(next).filter(|entry| entry.timestamp <= time) {
+                    if entry.kind == HistoryKind::Change {
+                        value = entry.value;
+                    }
+
+                    next += 1;
+                }
+
+                value
+            })
+            .collect()
+    }
+
+    /// The history of the counter with the ID `id` as CSV, with a header line
+    /// and one entry per line.
+    pub fn csv(&self, id: u64) -> String {
+        std::iter::once("timestamp,value,kind\n".to_owned())
+            .chain(self.entries(id).iter().map(|entry| {
+                let kind = match entry.kind {
+                    HistoryKind::Change => "change",
+                    HistoryKind::Reset => "reset",
+                };
+
+                format!("{},{},{kind}\n", entry.timestamp, entry.value)
+            }))
+            .collect()
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join("counter-history.json"))
+}
+
+/// Counters which are totalled together, such as the species in a bird survey.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Group {
+    /// Identifies the group while others are added and removed around it, or
+    /// zero until [`assign_ids`] gives it one.
+    #[serde(default)]
+    pub id: u64,
+    pub name: String,
+    pub counters: Vec<Counter>,
+}
+
+impl Group {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: new_id(),
+            name,
+            counters: Vec::new(),
+        }
+    }
+
+    /// Sum of every counter in the group, held at the bounds of `i64` rather than overflowing.
+    pub fn subtotal(&self) -> i64 {
+        self.counters.iter().map(|counter| counter.value).fold(0, i64::saturating_add)
+    }
+}
+
+/// A random ID for a group or counter, never zero.
+fn new_id() -> u64 {
+    rand::random::<u64>().max(1)
+}
+
+/// Gives an ID to every group and counter saved before they had one,
+/// returning whether any was given one.
+///
+/// Missing IDs are read as zero rather than made up, so that reading the same
+/// configuration twice gives the same groups.
+pub fn assign_ids(groups: &mut [Group]) -> bool {
+    let mut assigned = false;
+
+    for group in groups {
+        let ids = std::iter::once(&mut group.id).chain(group.counters.iter_mut().map(|counter| &mut counter.id));
+
+        for id in ids.filter(|id| **id == 0) {
+            *id = new_id();
+            assigned = true;
+        }
+    }
+
+    assigned
+}
+
+/// Sum of every counter in every group, held at the bounds of `i64` rather than overflowing.
+pub fn grand_total(groups: &[Group]) -> i64 {
+    groups.iter().map(Group::subtotal).fold(0, i64::saturating_add)
+}
+
+/// Number of counters across every group.
+pub fn count(groups: &[Group]) -> usize {
+    groups.iter().map(|group| group.counters.len()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(values: &[i64]) -> Group {
+        let mut group = Group::new(String::from("Group"));
+
+        for &value in values {
+            let mut counter = Counter::new(String::from("Counter"));
+            counter.value = value;
+            group.counters.push(counter);
+        }
+
+        group
+    }
+
+    #[test]
+    fn totals_saturate_instead_of_overflowing() {
+        let high = group(&[i64::MAX - 1, i64::MAX - 1]);
+        assert_eq!(high.subtotal(), i64::MAX);
+
+        let low = group(&[i64::MIN + 1, i64::MIN + 1]);
+        assert_eq!(low.subtotal(), i64::MIN);
+
+        assert_eq!(grand_total(&[high, group(&[i64::MAX])]), i64::MAX);
+    }
+
+    #[test]
+    fn totals_add_up_within_range() {
+        let groups = [group(&[1, 2, 3]), group(&[-4])];
+
+        assert_eq!(groups[0].subtotal(), 6);
+        assert_eq!(grand_total(&groups), 2);
+    }
+}
@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Record of completed daily challenges, kept in the application's data directory.
+
+use crate::storage;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Days on which the daily challenge was solved, with the attempts taken.
+#[derive(Debug, Default)]
+pub struct Completions {
+    days: BTreeMap<NaiveDate, u32>,
+}
+
+impl Completions {
+    /// Loads completions from disk, ignoring malformed lines.
+    pub fn load() -> Self {
+        let Some(text) = path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return Self::default();
+        };
+
+        let days = text
+            .lines()
+            .filter_map(|line| {
+                let (date, attempts) = line.split_once(' ')?;
+                Some((date.parse().ok()?, attempts.parse().ok()?))
+            })
+            .collect();
+
+        Self { days }
+    }
+
+    /// Every solved day, with the attempts taken.
+    pub fn days(&self) -> &BTreeMap<NaiveDate, u32> {
+        &self.days
+    }
+
+    /// Attempts taken to solve the challenge on `date`, if it was solved.
+    pub fn get(&self, date: NaiveDate) -> Option<u32> {
+        self.days.get(&date).copied()
+    }
+
+    /// Records a solved challenge and saves it. Only the first solve of a day
+    /// counts, since a replay already knows the number.
+    pub fn record(&mut self, date: NaiveDate, attempts: u32) {
+        if self.days.contains_key(&date) {
+            return;
+        }
+
+        self.days.insert(date, attempts);
+
+        if let Err(why) = self.save() {
+            eprintln!("failed to save daily challenge completions: {why}");
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = path() else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let text: String = self
+            .days
+            .iter()
+            .map(|(date, attempts)| format!("{date} {attempts}\n"))
+            .collect();
+
+        std::fs::write(path, text)
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join("daily.txt"))
+}
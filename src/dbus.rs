@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Signals on the session bus which let external tools react to app events.
+//!
+//! Listeners can match on the `dev.mmurphy.Test.Events` interface, for example
+//! with `dbus-monitor "interface='dev.mmurphy.Test.Events'"`.
+
+use crate::error::Error;
+use cosmic::iced::Subscription;
+use cosmic::iced_futures;
+use futures_util::SinkExt;
+use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
+
+/// Object path the signals are emitted from.
+const PATH: &str = "/dev/mmurphy/Test";
+
+/// An app event to broadcast on the bus.
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// A timer, named `stopwatch` or `countdown`, started running.
+    TimerStarted(&'static str),
+    /// A countdown ran out.
+    TimerFinished(&'static str),
+    /// A counter took a new value.
+    CounterChanged { group: String, counter: String, value: i64 },
+    /// An operation failed, as also told to the user in a toast.
+    Failed(Error),
+}
+
+/// Sends events to the bus connection owned by [`serve`].
+pub type Sender = mpsc::UnboundedSender<Event>;
+
+struct Events;
+
+#[zbus::interface(name = "dev.mmurphy.Test.Events")]
+impl Events {
+    #[zbus(signal)]
+    async fn timer_started(emitter: &SignalEmitter<'_>, timer: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn timer_finished(emitter: &SignalEmitter<'_>, timer: &str) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn counter_changed(
+        emitter: &SignalEmitter<'_>,
+        group: &str,
+        counter: &str,
+        value: i64,
+    ) -> zbus::Result<()>;
+
+    /// Carries the failure's stable code name, such as `not-found`, and its description.
+    #[zbus(signal)]
+    async fn failed(emitter: &SignalEmitter<'_>, code: &str, message: &str) -> zbus::Result<()>;
+}
+
+/// Connects to the session bus, emits a [`Sender`] once ready, and then
+/// broadcasts every event sent through it.
+pub fn serve() -> Subscription<Sender> {
+    Subscription::run(|| {
+        iced_futures::stream::channel(1, |mut output| async move {
+            let connection = zbus::connection::Builder::session()
+                .and_then(|builder| builder.serve_at(PATH, Events));
+
+            let connection = match connection {
+                Ok(builder) => builder.build().await,
+                Err(why) => Err(why),
+            };
+
+            let emitter = connection.and_then(|connection| {
+                SignalEmitter::new(&connection, PATH).map(|emitter| (connection, emitter.into_owned()))
+            });
+
+            // The connection must stay alive for as long as signals are sent.
+            let (_connection, emitter) = match emitter {
+                Ok(emitter) => emitter,
+                Err(why) => {
+                    eprintln!("D-Bus signals unavailable: {why}");
+                    return;
+                }
+            };
+
+            let (sender, mut events) = mpsc::unbounded_channel();
+            _ = output.send(sender).await;
+
+            while let Some(event) = events.recv().await {
+                let result = match &event {
+                    Event::TimerStarted(timer) => Events::timer_started(&emitter, timer).await,
+                    Event::TimerFinished(timer) => Events::timer_finished(&emitter, timer).await,
+                    Event::CounterChanged { group, counter, value } => {
+                        Events::counter_changed(&emitter, group, counter, *value).await
+                    }
+                    Event::Failed(error) => {
+                        Events::failed(&emitter, error.code.name(), &error.message).await
+                    }
+                };
+
+                if let Err(why) = result {
+                    eprintln!("failed to emit {event:?}: {why}");
+                }
+            }
+        })
+    })
+}
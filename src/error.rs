@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Failures shown to the user, each with a stable code for automation.
+//!
+//! The same [`Code`] is shown in toasts, broadcast in the `Failed` D-Bus
+//! signal, and used as the exit status of the command line. Codes keep their
+//! names and numbers once released; new failure causes get new codes.
+
+use crate::generators::passphrase::WordlistError;
+use crate::sessions::ImportError;
+use std::fmt;
+
+/// Why an operation failed, independent of how the failure is reported.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Code {
+    /// The command line or a request was malformed.
+    Usage,
+    /// A file was read but its contents were not understood.
+    InvalidData,
+    /// A file or directory does not exist.
+    NotFound,
+    /// A file could not be read or written.
+    Io,
+    /// A desktop service, such as the file chooser portal, is not available.
+    Unavailable,
+    /// A command the user chose to run exited unsuccessfully.
+    CommandFailed,
+}
+
+impl Code {
+    /// The code's name, as shown to the user and sent on the bus.
+    pub fn name(self) -> &'static str {
+        match self {
+            Code::Usage => "usage",
+            Code::InvalidData => "invalid-data",
+            Code::NotFound => "not-found",
+            Code::Io => "io",
+            Code::Unavailable => "unavailable",
+            Code::CommandFailed => "command-failed",
+        }
+    }
+
+    /// The command line's exit status, following the BSD `sysexits.h` values.
+    pub fn exit_status(self) -> i32 {
+        match self {
+            Code::Usage => 64,
+            Code::InvalidData => 65,
+            Code::NotFound => 66,
+            Code::Unavailable => 69,
+            Code::CommandFailed => 70,
+            Code::Io => 74,
+        }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A failure with its code and a description for people.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    pub code: Code,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(code: Code, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    /// A failure of the file chooser portal.
+    pub fn dialog(why: impl fmt::Display) -> Self {
+        Self::new(Code::Unavailable, why.to_string())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]", self.message, self.code)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(why: std::io::Error) -> Self {
+        let code = match why.kind() {
+            std::io::ErrorKind::NotFound => Code::NotFound,
+            std::io::ErrorKind::InvalidData => Code::InvalidData,
+            _ => Code::Io,
+        };
+
+        Self::new(code, why.to_string())
+    }
+}
+
+impl From<ImportError> for Error {
+    fn from(why: ImportError) -> Self {
+        match why {
+            ImportError::Io(why) => why.into(),
+            why => Self::new(Code::InvalidData, why.to_string()),
+        }
+    }
+}
+
+impl From<WordlistError> for Error {
+    fn from(why: WordlistError) -> Self {
+        match why {
+            WordlistError::Io(why) => why.into(),
+            WordlistError::NoDataDir => Self::new(Code::Unavailable, why.to_string()),
+            why => Self::new(Code::InvalidData, why.to_string()),
+        }
+    }
+}
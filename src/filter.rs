@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Filtering lists by a typed query, applied once typing pauses.
+//!
+//! Each keystroke updates the text shown in the input straight away, but the
+//! list is only filtered again after [`DEBOUNCE`] without further typing, so
+//! long lists are not searched on every keystroke.
+
+use cosmic::Task;
+use std::time::Duration;
+
+/// How long typing must pause before the query is applied.
+pub const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// The text typed into a filter input, and the query last applied from it.
+#[derive(Debug, Default)]
+pub struct Filter {
+    input: String,
+    query: String,
+    /// Counts edits, so that only the latest edit's delayed apply takes effect.
+    generation: u64,
+}
+
+impl Filter {
+    /// The text shown in the filter input.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Whether a query is being applied.
+    pub fn is_active(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    /// Updates the typed text, returning a task which emits `on_apply` once typing pauses.
+    pub fn edit<Message: Send + 'static>(
+        &mut self,
+        input: String,
+        on_apply: impl FnOnce(u64) -> Message + Send + 'static,
+    ) -> Task<Message> {
+        self.input = input;
+        self.generation += 1;
+        let generation = self.generation;
+
+        cosmic::task::future(async move {
+            tokio::time::sleep(DEBOUNCE).await;
+            on_apply(generation)
+        })
+    }
+
+    /// Applies the typed text as the query, unless it was edited again since `generation`.
+    pub fn apply(&mut self, generation: u64) {
+        if generation == self.generation {
+            self.query = self.input.trim().to_lowercase();
+        }
+    }
+
+    /// Clears the input and the query at once.
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.query.clear();
+        self.generation += 1;
+    }
+
+    /// Whether any of `texts` contains the query, ignoring case.
+    pub fn matches<'a>(&self, texts: impl IntoIterator<Item = &'a str>) -> bool {
+        self.query.is_empty()
+            || texts
+                .into_iter()
+                .any(|text| text.to_lowercase().contains(&self.query))
+    }
+}
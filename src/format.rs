@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Locale-aware formatting of values for display.
+
+use i18n_embed::DesktopLanguageRequester;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Language of the system's numeric locale. Numbers are grouped by it
+/// whatever language the app is shown in.
+static NUMERIC_LANGUAGE: LazyLock<String> = LazyLock::new(numeric_language);
+
+/// How a number is presented.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NumberFormat {
+    /// Prefix positive numbers with `+`.
+    pub always_sign: bool,
+    /// Draw negative numbers in the theme's destructive color.
+    pub red_negative: bool,
+    /// Group digits in thousands with the locale's separator.
+    pub thousands_separators: bool,
+}
+
+/// Formats an integer according to `format` and the system's numeric locale.
+pub fn number(value: i64, format: NumberFormat) -> String {
+    let digits = value.unsigned_abs().to_string();
+
+    let grouped = if format.thousands_separators && digits.len() > 3 {
+        let separator = thousands_separator();
+        let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+
+        for (idx, digit) in digits.chars().enumerate() {
+            if idx > 0 && (digits.len() - idx) % 3 == 0 {
+                grouped.push_str(separator);
+            }
+            grouped.push(digit);
+        }
+
+        grouped
+    } else {
+        digits
+    };
+
+    let sign = match value {
+        v if v < 0 => "−",
+        v if v > 0 && format.always_sign => "+",
+        _ => "",
+    };
+
+    [sign, &grouped].concat()
+}
+
+/// The digit group separator of the system's numeric locale.
+fn thousands_separator() -> &'static str {
+    match NUMERIC_LANGUAGE.as_str() {
+        "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" => ".",
+        "fr" | "ru" | "uk" | "pl" | "cs" | "sv" | "fi" | "nb" => "\u{202F}",
+        _ => ",",
+    }
+}
+
+/// The language of the numeric locale, from the variables setting it in the
+/// order they take effect, or else the first language the desktop asks for.
+fn numeric_language() -> String {
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|locale| !locale.is_empty());
+
+    match locale {
+        // A locale such as `de_DE.UTF-8@euro` names its language first.
+        Some(locale) => locale
+            .split(['_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase(),
+        None => DesktopLanguageRequester::requested_languages()
+            .first()
+            .map(|language| language.language.to_string())
+            .unwrap_or_default(),
+    }
+}
@@ -0,0 +1,360 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Rules of the number guessing game, independent of any user interface.
+
+use chrono::{Datelike, NaiveDate};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// How long the player has to find the number in a timed challenge.
+pub const CHALLENGE_TIME: Duration = Duration::from_secs(60);
+
+/// Which kind of round is being played.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Mode {
+    /// Unlimited guesses for a number in the range of the chosen [`Difficulty`].
+    Free,
+    /// The numbered campaign level.
+    Campaign(u32),
+    /// The puzzle shared by every player on the given day.
+    Daily(NaiveDate),
+    /// A number from 1 to 100 to be found within [`CHALLENGE_TIME`].
+    Timed,
+}
+
+/// How wide the range of a free play round is.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Difficulty {
+    /// A number from 1 to 50.
+    Easy,
+    /// A number from 1 to 100.
+    #[default]
+    Normal,
+    /// A number from 1 to 1000.
+    Hard,
+    /// A number between bounds the player chose.
+    Custom { low: i64, high: i64 },
+}
+
+impl Difficulty {
+    /// The difficulties offered before a custom range is chosen.
+    pub const PRESETS: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// The range secret numbers are drawn from.
+    ///
+    /// A custom range whose bounds are out of order falls back to 1 to 100.
+    pub fn range(self) -> RangeInclusive<i64> {
+        match self {
+            Difficulty::Easy => 1..=50,
+            Difficulty::Normal => 1..=100,
+            Difficulty::Hard => 1..=1000,
+            Difficulty::Custom { low, high } if low < high => low..=high,
+            Difficulty::Custom { .. } => 1..=100,
+        }
+    }
+}
+
+/// The result of a single guess.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// The secret number is higher than the guess.
+    TooLow,
+    /// The secret number is lower than the guess.
+    TooHigh,
+    /// The guess is the secret number.
+    Correct,
+}
+
+/// How close a guess came to the secret number, relative to the size of the range.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Proximity {
+    Correct,
+    /// Within 5% of the range.
+    Hot,
+    /// Within 15% of the range.
+    Warm,
+    Cold,
+}
+
+/// A round of the guessing game.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Engine {
+    range: RangeInclusive<i64>,
+    secret: i64,
+    attempts: u32,
+    /// Every guess made this round, oldest first.
+    guesses: Vec<i64>,
+    /// Most guesses allowed this round, if limited.
+    max_attempts: Option<u32>,
+    won: bool,
+    /// Whether time ran out before the number was found.
+    expired: bool,
+}
+
+impl Engine {
+    /// Starts a round with a secret number chosen at random from `range`.
+    pub fn new(range: RangeInclusive<i64>) -> Self {
+        let secret = rand::thread_rng().gen_range(range.clone());
+        Self::with_secret(range, secret)
+    }
+
+    /// Starts a round with a known secret number.
+    pub fn with_secret(range: RangeInclusive<i64>, secret: i64) -> Self {
+        Self {
+            range,
+            secret,
+            attempts: 0,
+            guesses: Vec::new(),
+            max_attempts: None,
+            won: false,
+            expired: false,
+        }
+    }
+
+    /// Starts a round at the given campaign level.
+    pub fn for_level(level: Level) -> Self {
+        Self::new(level.range).with_max_attempts(level.max_attempts)
+    }
+
+    /// Starts the daily challenge for `date`, whose secret number is the same
+    /// for every player.
+    pub fn daily(date: NaiveDate) -> Self {
+        let range = 1..=100;
+        let secret = daily_secret(date, &range);
+        Self::with_secret(range, secret)
+    }
+
+    /// Limits the number of guesses allowed this round.
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Checks a guess against the secret number, counting it as an attempt.
+    pub fn guess(&mut self, n: i64) -> Outcome {
+        self.attempts += 1;
+        self.guesses.push(n);
+
+        match n.cmp(&self.secret) {
+            std::cmp::Ordering::Less => Outcome::TooLow,
+            std::cmp::Ordering::Greater => Outcome::TooHigh,
+            std::cmp::Ordering::Equal => {
+                self.won = true;
+                Outcome::Correct
+            }
+        }
+    }
+
+    /// Whether the secret number has been guessed.
+    pub fn is_won(&self) -> bool {
+        self.won
+    }
+
+    /// Ends the round unwon, as when a timed challenge runs out of time.
+    pub fn expire(&mut self) {
+        self.expired = !self.won;
+    }
+
+    /// Whether every allowed guess was used, or time ran out, without finding the number.
+    pub fn is_lost(&self) -> bool {
+        !self.won && (self.expired || self.attempts_left() == Some(0))
+    }
+
+    /// Guesses remaining this round, if limited.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// Number of guesses made this round.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Every guess made this round, oldest first.
+    pub fn guesses(&self) -> &[i64] {
+        &self.guesses
+    }
+
+    /// Which side of the secret number `guess` is on, without counting it as an attempt.
+    pub fn outcome(&self, guess: i64) -> Outcome {
+        match guess.cmp(&self.secret) {
+            std::cmp::Ordering::Less => Outcome::TooLow,
+            std::cmp::Ordering::Greater => Outcome::TooHigh,
+            std::cmp::Ordering::Equal => Outcome::Correct,
+        }
+    }
+
+    /// How close `guess` is to the secret number.
+    ///
+    /// Worked out in `u128`, which holds the span of any `i64` range and
+    /// twenty times any distance between two `i64`s.
+    pub fn proximity(&self, guess: i64) -> Proximity {
+        let span = u128::from(self.range.end().abs_diff(*self.range.start())) + 1;
+        let distance = u128::from(guess.abs_diff(self.secret));
+
+        match distance {
+            0 => Proximity::Correct,
+            d if d * 20 <= span => Proximity::Hot,
+            d if d * 20 <= span * 3 => Proximity::Warm,
+            _ => Proximity::Cold,
+        }
+    }
+
+    /// The range the secret number was drawn from.
+    pub fn range(&self) -> &RangeInclusive<i64> {
+        &self.range
+    }
+
+    /// The number to be guessed.
+    pub fn secret(&self) -> i64 {
+        self.secret
+    }
+}
+
+/// The range and guess budget of a campaign level.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Level {
+    pub range: RangeInclusive<i64>,
+    pub max_attempts: u32,
+}
+
+/// Upper bound and guess budget of each campaign level. Ranges widen and
+/// budgets shrink until only a perfect binary search wins.
+const CAMPAIGN: [(i64, u32); 10] = [
+    (50, 10),
+    (100, 10),
+    (100, 8),
+    (250, 10),
+    (250, 9),
+    (500, 11),
+    (500, 9),
+    (1000, 12),
+    (1000, 11),
+    (1000, 10),
+];
+
+/// Number of levels in the campaign.
+pub const CAMPAIGN_LEVELS: u32 = CAMPAIGN.len() as u32;
+
+/// The campaign level numbered `level`, starting from 1.
+pub fn campaign_level(level: u32) -> Option<Level> {
+    let (upper, max_attempts) = *CAMPAIGN.get(level.checked_sub(1)? as usize)?;
+
+    Some(Level {
+        range: 1..=upper,
+        max_attempts,
+    })
+}
+
+/// Derives the daily challenge's secret number from the date.
+///
+/// This uses SplitMix64 rather than a `rand` generator, whose output is not
+/// guaranteed to stay the same across versions.
+fn daily_secret(date: NaiveDate, range: &RangeInclusive<i64>) -> i64 {
+    let mut z = (date.num_days_from_ce() as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+
+    let span = u128::from(range.end().abs_diff(*range.start())) + 1;
+    range.start().wrapping_add((u128::from(z) % span) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_point_toward_the_secret() {
+        let mut engine = Engine::with_secret(1..=100, 42);
+
+        assert_eq!(engine.guess(10), Outcome::TooLow);
+        assert_eq!(engine.guess(90), Outcome::TooHigh);
+        assert!(!engine.is_won());
+        assert_eq!(engine.guess(42), Outcome::Correct);
+        assert!(engine.is_won());
+        assert_eq!(engine.attempts(), 3);
+        assert_eq!(engine.guesses(), [10, 90, 42]);
+    }
+
+    #[test]
+    fn new_rounds_draw_from_the_range() {
+        for _ in 0..100 {
+            let engine = Engine::new(5..=9);
+            assert!(engine.range().contains(&engine.secret()));
+        }
+    }
+
+    #[test]
+    fn a_round_is_lost_once_its_attempts_run_out() {
+        let mut engine = Engine::with_secret(1..=10, 7).with_max_attempts(2);
+
+        engine.guess(1);
+        assert_eq!(engine.attempts_left(), Some(1));
+        assert!(!engine.is_lost());
+        engine.guess(2);
+        assert_eq!(engine.attempts_left(), Some(0));
+        assert!(engine.is_lost());
+    }
+
+    #[test]
+    fn expiring_a_won_round_does_not_lose_it() {
+        let mut engine = Engine::with_secret(1..=10, 7);
+        engine.guess(7);
+        engine.expire();
+        assert!(engine.is_won());
+        assert!(!engine.is_lost());
+
+        let mut engine = Engine::with_secret(1..=10, 7);
+        engine.expire();
+        assert!(engine.is_lost());
+    }
+
+    #[test]
+    fn proximity_follows_the_share_of_the_range() {
+        let engine = Engine::with_secret(1..=100, 50);
+
+        assert_eq!(engine.proximity(50), Proximity::Correct);
+        assert_eq!(engine.proximity(55), Proximity::Hot);
+        assert_eq!(engine.proximity(65), Proximity::Warm);
+        assert_eq!(engine.proximity(90), Proximity::Cold);
+    }
+
+    #[test]
+    fn proximity_handles_extreme_numbers() {
+        let engine = Engine::with_secret(i64::MIN..=i64::MAX, 0);
+        assert_eq!(engine.proximity(i64::MAX), Proximity::Cold);
+        assert_eq!(engine.proximity(1), Proximity::Hot);
+
+        let engine = Engine::with_secret(1..=100, 1);
+        assert_eq!(engine.proximity(i64::MAX), Proximity::Cold);
+        assert_eq!(engine.proximity(i64::MIN), Proximity::Cold);
+    }
+
+    #[test]
+    fn custom_ranges_out_of_order_fall_back() {
+        assert_eq!(Difficulty::Custom { low: 1, high: 500 }.range(), 1..=500);
+        assert_eq!(Difficulty::Custom { low: 9, high: 3 }.range(), 1..=100);
+    }
+
+    #[test]
+    fn the_daily_secret_is_stable_and_in_range() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+
+        assert_eq!(Engine::daily(date).secret(), Engine::daily(date).secret());
+        assert!((1..=100).contains(&Engine::daily(date).secret()));
+        assert!((i64::MIN..=i64::MAX).contains(&daily_secret(date, &(i64::MIN..=i64::MAX))));
+    }
+
+    #[test]
+    fn campaign_levels_are_numbered_from_one() {
+        assert_eq!(campaign_level(0), None);
+        assert_eq!(campaign_level(1).map(|level| level.range), Some(1..=50));
+        assert!(campaign_level(CAMPAIGN_LEVELS).is_some());
+        assert_eq!(campaign_level(CAMPAIGN_LEVELS + 1), None);
+    }
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Generators for the values produced by the application's pages.
+
+pub mod passphrase;
+pub mod password;
+pub mod phonetic;
+pub mod strength;
@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Random passphrase generation from word lists.
+
+use crate::storage;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Words shipped with the application.
+const BUNDLED: &str = include_str!("../../resources/wordlist.txt");
+
+/// Fewest distinct words a list may hold, keeping each word worth at least
+/// 7 bits of entropy.
+pub const MIN_WORDS: usize = 128;
+
+/// A list of distinct words to draw passphrases from.
+#[derive(Clone, Debug)]
+pub struct Wordlist {
+    name: String,
+    words: Vec<String>,
+}
+
+impl Wordlist {
+    /// The word list shipped with the application.
+    pub fn bundled() -> Self {
+        Self::parse(String::from("bundled"), BUNDLED).expect("bundled word list is valid")
+    }
+
+    /// Parses one word per line, ignoring blank lines.
+    ///
+    /// Diceware-style lines such as `11111 abacus` are accepted by taking the
+    /// last whitespace-separated field of each line.
+    pub fn parse(name: String, text: &str) -> Result<Self, WordlistError> {
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+
+        for word in text.lines().filter_map(|line| line.split_whitespace().last()) {
+            if !seen.insert(word) {
+                return Err(WordlistError::Duplicate(word.to_owned()));
+            }
+
+            words.push(word.to_owned());
+        }
+
+        if words.len() < MIN_WORDS {
+            return Err(WordlistError::TooFew(words.len()));
+        }
+
+        Ok(Self { name, words })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Validates the word list at `path` and copies it into the cache.
+    pub async fn import(path: &Path) -> Result<Self, WordlistError> {
+        let text = tokio::fs::read_to_string(path).await?;
+        let name = path
+            .file_stem()
+            .map_or_else(|| String::from("imported"), |stem| stem.to_string_lossy().into_owned());
+        let wordlist = Self::parse(name, &text)?;
+
+        let dir = cache_dir().ok_or(WordlistError::NoDataDir)?;
+        tokio::fs::create_dir_all(&dir).await?;
+        // Appended rather than set as the extension, which would replace
+        // whatever follows a dot in the name, as in `en.short`.
+        tokio::fs::write(dir.join(format!("{}.txt", wordlist.name)), wordlist.words.join("\n")).await?;
+
+        Ok(wordlist)
+    }
+
+    /// Loads previously imported word lists from the cache.
+    pub fn load_cached() -> Vec<Self> {
+        let Some(entries) = cache_dir().and_then(|dir| std::fs::read_dir(dir).ok()) else {
+            return Vec::new();
+        };
+
+        let mut wordlists: Vec<Self> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                let text = std::fs::read_to_string(&path).ok()?;
+                Self::parse(name, &text).ok()
+            })
+            .collect();
+
+        wordlists.sort_by(|a, b| a.name.cmp(&b.name));
+        wordlists
+    }
+}
+
+/// Where imported word lists are kept.
+fn cache_dir() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join("wordlists"))
+}
+
+/// Describes the passphrase to generate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PassphraseSpec {
+    /// Number of words in the passphrase.
+    pub words: usize,
+    /// Text placed between words.
+    pub separator: String,
+}
+
+impl Default for PassphraseSpec {
+    fn default() -> Self {
+        Self {
+            words: 6,
+            separator: String::from("-"),
+        }
+    }
+}
+
+impl PassphraseSpec {
+    /// Generates a passphrase from `wordlist` using the thread-local random number generator.
+    pub fn generate(&self, wordlist: &Wordlist) -> String {
+        self.generate_with(wordlist, &mut rand::thread_rng())
+    }
+
+    /// Generates a passphrase from `wordlist` using the given random number generator.
+    pub fn generate_with<R: Rng + ?Sized>(&self, wordlist: &Wordlist, rng: &mut R) -> String {
+        (0..self.words)
+            .map(|_| wordlist.words[rng.gen_range(0..wordlist.words.len())].as_str())
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+/// Reasons a word list cannot be used.
+#[derive(Debug)]
+pub enum WordlistError {
+    /// The list holds fewer than [`MIN_WORDS`] words.
+    TooFew(usize),
+    /// A word appears more than once.
+    Duplicate(String),
+    /// There is no XDG data directory to cache the list in.
+    NoDataDir,
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for WordlistError {
+    fn from(why: std::io::Error) -> Self {
+        WordlistError::Io(why)
+    }
+}
+
+impl fmt::Display for WordlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordlistError::TooFew(found) => {
+                write!(f, "found {found} words, but at least {MIN_WORDS} are required")
+            }
+            WordlistError::Duplicate(word) => write!(f, "the word {word:?} appears more than once"),
+            WordlistError::NoDataDir => f.write_str("no data directory is available"),
+            WordlistError::Io(why) => write!(f, "{why}"),
+        }
+    }
+}
+
+impl std::error::Error for WordlistError {}
@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Random password generation.
+//!
+//! A [`PasswordSpec`] describes the password to produce. Generated passwords
+//! are guaranteed to:
+//!
+//! - contain exactly [`PasswordSpec::length`] characters,
+//! - draw only from the enabled [`CharClasses`], minus any excluded characters,
+//! - contain at least one character from every enabled class that still has
+//!   characters left after exclusions.
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!#$%&()*+,-./:;<=>?@[]^_{|}~";
+
+/// The character classes a password may draw from.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CharClasses {
+    pub lowercase: bool,
+    pub uppercase: bool,
+    pub digits: bool,
+    pub symbols: bool,
+}
+
+impl Default for CharClasses {
+    fn default() -> Self {
+        Self {
+            lowercase: true,
+            uppercase: false,
+            digits: true,
+            symbols: false,
+        }
+    }
+}
+
+impl CharClasses {
+    /// The characters of each enabled class.
+    fn sets(self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.lowercase, LOWERCASE),
+            (self.uppercase, UPPERCASE),
+            (self.digits, DIGITS),
+            (self.symbols, SYMBOLS),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, set)| enabled.then_some(set))
+    }
+}
+
+/// Describes the password to generate.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PasswordSpec {
+    /// Number of characters in the password.
+    pub length: usize,
+    /// Character classes to draw from.
+    pub classes: CharClasses,
+    /// Characters which must never appear, such as look-alikes like `0O1l`.
+    pub exclusions: String,
+}
+
+impl Default for PasswordSpec {
+    fn default() -> Self {
+        Self {
+            length: 16,
+            classes: CharClasses::default(),
+            exclusions: String::new(),
+        }
+    }
+}
+
+impl PasswordSpec {
+    /// Sets the number of characters in the password.
+    #[must_use]
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Sets the character classes to draw from.
+    #[must_use]
+    pub fn classes(mut self, classes: CharClasses) -> Self {
+        self.classes = classes;
+        self
+    }
+
+    /// Sets the characters which must never appear in the password.
+    #[must_use]
+    pub fn exclusions(mut self, exclusions: impl Into<String>) -> Self {
+        self.exclusions = exclusions.into();
+        self
+    }
+
+    /// The characters passwords are drawn from: those of the enabled classes,
+    /// minus any excluded characters.
+    pub fn alphabet(&self) -> String {
+        self.classes
+            .sets()
+            .flat_map(str::chars)
+            .filter(|c| !self.exclusions.contains(*c))
+            .collect()
+    }
+
+    /// Generates a password using the thread-local random number generator.
+    pub fn generate(&self) -> Result<String, Error> {
+        self.generate_with(&mut rand::thread_rng())
+    }
+
+    /// Generates a password using the given random number generator.
+    pub fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<String, Error> {
+        let pools: Vec<Vec<char>> = self
+            .classes
+            .sets()
+            .map(|set| {
+                set.chars()
+                    .filter(|c| !self.exclusions.contains(*c))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|pool| !pool.is_empty())
+            .collect();
+
+        if pools.is_empty() {
+            return Err(Error::NoCharacters);
+        }
+
+        if self.length < pools.len() {
+            return Err(Error::TooShort {
+                length: self.length,
+                required: pools.len(),
+            });
+        }
+
+        let all: Vec<char> = pools.iter().flatten().copied().collect();
+
+        // One character from every class, then fill the rest from any class.
+        let mut password: Vec<char> = pools
+            .iter()
+            .map(|pool| pool[rng.gen_range(0..pool.len())])
+            .chain((pools.len()..self.length).map(|_| all[rng.gen_range(0..all.len())]))
+            .collect();
+
+        password.shuffle(rng);
+
+        Ok(password.into_iter().collect())
+    }
+}
+
+/// Reasons a [`PasswordSpec`] cannot produce a password.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// No character classes are enabled, or exclusions removed every character.
+    NoCharacters,
+    /// The length is too short to include every enabled character class.
+    TooShort { length: usize, required: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoCharacters => f.write_str("no characters are available to generate from"),
+            Error::TooShort { length, required } => write!(
+                f,
+                "a length of {length} cannot include all {required} character classes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    fn all_classes() -> CharClasses {
+        CharClasses {
+            lowercase: true,
+            uppercase: true,
+            digits: true,
+            symbols: true,
+        }
+    }
+
+    #[test]
+    fn generates_the_requested_length() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for length in [4, 16, 128] {
+            let password = PasswordSpec::default()
+                .length(length)
+                .classes(all_classes())
+                .generate_with(&mut rng)
+                .unwrap();
+            assert_eq!(password.chars().count(), length);
+        }
+    }
+
+    #[test]
+    fn covers_every_enabled_class() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let spec = PasswordSpec::default().length(4).classes(all_classes());
+
+        for _ in 0..200 {
+            let password = spec.generate_with(&mut rng).unwrap();
+
+            for set in [LOWERCASE, UPPERCASE, DIGITS, SYMBOLS] {
+                assert!(password.chars().any(|c| set.contains(c)), "{password} lacks one of {set}");
+            }
+        }
+    }
+
+    #[test]
+    fn draws_only_from_enabled_classes_without_exclusions() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let spec = PasswordSpec::default()
+            .length(64)
+            .classes(CharClasses {
+                lowercase: true,
+                uppercase: false,
+                digits: true,
+                symbols: false,
+            })
+            .exclusions("0o1l");
+
+        for _ in 0..50 {
+            let password = spec.generate_with(&mut rng).unwrap();
+            assert!(password.chars().all(|c| LOWERCASE.contains(c) || DIGITS.contains(c)));
+            assert!(!password.chars().any(|c| "0o1l".contains(c)));
+        }
+    }
+
+    #[test]
+    fn skips_classes_emptied_by_exclusions() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let spec = PasswordSpec::default()
+            .length(8)
+            .classes(CharClasses {
+                lowercase: true,
+                uppercase: false,
+                digits: true,
+                symbols: false,
+            })
+            .exclusions(DIGITS);
+
+        let password = spec.generate_with(&mut rng).unwrap();
+        assert!(password.chars().all(|c| LOWERCASE.contains(c)));
+    }
+
+    #[test]
+    fn rejects_a_spec_without_characters() {
+        let no_classes = CharClasses {
+            lowercase: false,
+            uppercase: false,
+            digits: false,
+            symbols: false,
+        };
+        assert_eq!(PasswordSpec::default().classes(no_classes).generate(), Err(Error::NoCharacters));
+
+        let excluded = PasswordSpec::default()
+            .classes(CharClasses {
+                lowercase: false,
+                uppercase: false,
+                digits: true,
+                symbols: false,
+            })
+            .exclusions(DIGITS);
+        assert_eq!(excluded.generate(), Err(Error::NoCharacters));
+    }
+
+    #[test]
+    fn rejects_a_length_too_short_for_every_class() {
+        let spec = PasswordSpec::default().length(3).classes(all_classes());
+        assert_eq!(spec.generate(), Err(Error::TooShort { length: 3, required: 4 }));
+    }
+}
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Spelling passwords out in the NATO phonetic alphabet, for reading them aloud.
+
+/// Code words for the letters A to Z.
+const WORDS: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett", "Kilo", "Lima",
+    "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango", "Uniform", "Victor", "Whiskey",
+    "X-ray", "Yankee", "Zulu",
+];
+
+/// How one character of a password is read out.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Spelled {
+    /// A letter from A to Z by its code word, which must be said to be a
+    /// capital when `uppercase` is set.
+    Letter { word: &'static str, uppercase: bool },
+    Digit(char),
+    Space,
+    /// Anything else, such as punctuation or a letter outside A to Z, read as itself.
+    Symbol(char),
+}
+
+/// Spells out `text` one character at a time.
+pub fn spell(text: &str) -> Vec<Spelled> {
+    text.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' => Spelled::Letter {
+                word: WORDS[usize::from(c.to_ascii_lowercase() as u8 - b'a')],
+                uppercase: c.is_ascii_uppercase(),
+            },
+            '0'..='9' => Spelled::Digit(c),
+            ' ' => Spelled::Space,
+            c => Spelled::Symbol(c),
+        })
+        .collect()
+}
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Rough password strength estimation.
+
+/// Estimates the entropy of `password` in bits, from the characters it
+/// actually uses and how they repeat.
+///
+/// A character new to the password contributes `log2` of the size of the
+/// character classes present in it. A character used earlier only
+/// contributes the choice among the characters used so far, so `aaaa` or
+/// `abab` gain almost nothing from their length, and a step of one from the
+/// previous character, as in `123` or `cba`, contributes a single bit. This
+/// does not detect dictionary words and should be read as an upper bound.
+pub fn entropy_bits(password: &str) -> f64 {
+    let pool = pool_size(password);
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    let per_char = f64::from(pool).log2();
+    let mut bits = 0.0;
+    let mut previous: Option<char> = None;
+    let mut used: Vec<char> = Vec::new();
+
+    for c in password.chars() {
+        bits += if previous.is_some_and(|p| (c as i64 - p as i64).abs() == 1) {
+            1.0
+        } else if used.contains(&c) {
+            (used.len() as f64).log2()
+        } else {
+            per_char
+        };
+
+        if !used.contains(&c) {
+            used.push(c);
+        }
+
+        previous = Some(c);
+    }
+
+    bits
+}
+
+/// The most entropy [`entropy_bits`] can estimate for a password of `length`
+/// characters drawn from `alphabet`, which is reached when the password uses
+/// as many different characters as it can and contains no steps of one.
+pub fn max_entropy_bits(alphabet: &str, length: usize) -> f64 {
+    let pool = pool_size(alphabet);
+    let distinct = alphabet.chars().count();
+
+    if pool == 0 {
+        return 0.0;
+    }
+
+    let fresh = length.min(distinct);
+    fresh as f64 * f64::from(pool).log2() + (length - fresh) as f64 * (distinct as f64).log2()
+}
+
+/// Number of distinct characters an attacker must consider per position.
+fn pool_size(password: &str) -> u32 {
+    let mut lowercase = false;
+    let mut uppercase = false;
+    let mut digits = false;
+    let mut other = false;
+
+    for c in password.chars() {
+        match c {
+            'a'..='z' => lowercase = true,
+            'A'..='Z' => uppercase = true,
+            '0'..='9' => digits = true,
+            _ => other = true,
+        }
+    }
+
+    u32::from(lowercase) * 26
+        + u32::from(uppercase) * 26
+        + u32::from(digits) * 10
+        + u32::from(other) * 33
+}
+
+/// A qualitative rating of a password's estimated entropy.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Strength {
+    VeryWeak,
+    Weak,
+    Fair,
+    Strong,
+    VeryStrong,
+}
+
+impl Strength {
+    /// Entropy at which the meter is full, in bits.
+    pub const FULL_BITS: f64 = 128.0;
+
+    /// Rates a password with `bits` of estimated entropy.
+    pub fn from_bits(bits: f64) -> Self {
+        match bits {
+            b if b < 28.0 => Strength::VeryWeak,
+            b if b < 36.0 => Strength::Weak,
+            b if b < 60.0 => Strength::Fair,
+            b if b < 100.0 => Strength::Strong,
+            _ => Strength::VeryStrong,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeating_characters_adds_little() {
+        assert!(entropy_bits("aaaaaaaaaaaaaaaa") < 5.0);
+        assert!(entropy_bits("abababababababab") < 28.0);
+        assert_eq!(Strength::from_bits(entropy_bits("aaaaaaaaaaaaaaaa")), Strength::VeryWeak);
+    }
+
+    #[test]
+    fn sequences_add_a_bit_each() {
+        let bits = entropy_bits("123456789");
+        assert!((bits - (10f64.log2() + 8.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn distinct_characters_count_in_full() {
+        let password = "q7#Lx2!mZ9@v";
+        let bits = entropy_bits(password);
+        assert!((bits - 12.0 * 95f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn maximum_bounds_the_estimate() {
+        let alphabet = "abcdefghijklmnopqrstuvwxyz0123456789";
+        let max = max_entropy_bits(alphabet, 16);
+
+        assert!((max - 16.0 * 36f64.log2()).abs() < 1e-9);
+        assert!(entropy_bits("q7xl2mz9vk4jw8rb") <= max + 1e-9);
+        assert!((max_entropy_bits("ab", 4) - (2.0 * 26f64.log2() + 2.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_has_no_entropy() {
+        assert_eq!(entropy_bits(""), 0.0);
+    }
+}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Decorative header images shown at the top of each page.
+//!
+//! Every page has an embedded default. A user-chosen image is copied into the
+//! application's data directory, so it outlives the file it was picked from.
+
+use crate::storage;
+use cosmic::iced::{ContentFit, Length};
+use cosmic::widget;
+use cosmic::Element;
+use std::path::{Path, PathBuf};
+
+/// Height of the header image, in logical pixels.
+const HEIGHT: f32 = 120.0;
+
+/// Embedded default images, one per page in nav bar order.
+const DEFAULTS: [&[u8]; 4] = [
+    include_bytes!("../resources/headers/page1.svg"),
+    include_bytes!("../resources/headers/page2.svg"),
+    include_bytes!("../resources/headers/page3.svg"),
+    include_bytes!("../resources/headers/page4.svg"),
+];
+
+/// Shows the header for the page at `page`, preferring the user's image if one is set.
+///
+/// The image fills the available width and is cropped to a fixed height.
+pub fn header<'a, Message: 'static>(page: usize, custom: Option<&Path>) -> Element<'a, Message> {
+    match custom {
+        Some(path) if is_svg(path) => widget::svg(widget::svg::Handle::from_path(path))
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fill)
+            .height(Length::Fixed(HEIGHT))
+            .into(),
+        Some(path) => widget::image(widget::image::Handle::from_path(path))
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fill)
+            .height(Length::Fixed(HEIGHT))
+            .into(),
+        None => widget::svg(widget::svg::Handle::from_memory(DEFAULTS[page % DEFAULTS.len()]))
+            .content_fit(ContentFit::Cover)
+            .width(Length::Fill)
+            .height(Length::Fixed(HEIGHT))
+            .into(),
+    }
+}
+
+/// Copies the image at `path` into the data directory as the header for `page`,
+/// returning where it was copied to.
+pub async fn import(page: usize, path: &Path) -> std::io::Result<PathBuf> {
+    let dir = storage::data_dir()
+        .map(|dir| dir.join("headers"))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no data directory"))?;
+
+    tokio::fs::create_dir_all(&dir).await?;
+
+    // A new name each time keeps a previously shown image from being reused from
+    // the cache. It is random rather than the time, which two picks can share.
+    let mut target = dir.join(format!("page{}-{:016x}", page + 1, rand::random::<u64>()));
+    if let Some(extension) = path.extension() {
+        target.set_extension(extension);
+    }
+
+    tokio::fs::copy(path, &target).await?;
+    Ok(target)
+}
+
+fn is_svg(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg"))
+}
@@ -3,7 +3,7 @@
 //! Provides localization support for this crate.
 
 use i18n_embed::{
-    DefaultLocalizer, LanguageLoader, Localizer,
+    DefaultLocalizer, DesktopLanguageRequester, LanguageLoader, Localizer,
     fluent::{FluentLanguageLoader, fluent_language_loader},
     unic_langid::LanguageIdentifier,
 };
@@ -17,6 +17,54 @@ pub fn init(requested_languages: &[LanguageIdentifier]) {
     }
 }
 
+/// Switches `fl!()` strings to `language`, or to the system's preferred languages if `None`.
+///
+/// Text already built from translations keeps its language until it is built again.
+pub fn select(language: Option<&LanguageIdentifier>) {
+    match language {
+        Some(language) => init(std::slice::from_ref(language)),
+        None => init(&DesktopLanguageRequester::requested_languages()),
+    }
+}
+
+/// Languages the app has translations for.
+pub fn available() -> Vec<LanguageIdentifier> {
+    let mut languages = LANGUAGE_LOADER
+        .available_languages(&Localizations)
+        .unwrap_or_default();
+    languages.sort_by_key(ToString::to_string);
+    languages
+}
+
+/// The name of `language` in that language, as listed in a language picker.
+pub fn native_name(language: &LanguageIdentifier) -> String {
+    let name = match language.language.as_str() {
+        "cs" => "Čeština",
+        "da" => "Dansk",
+        "de" => "Deutsch",
+        "en" => "English",
+        "es" => "Español",
+        "fi" => "Suomi",
+        "fr" => "Français",
+        "it" => "Italiano",
+        "nb" => "Norsk bokmål",
+        "nl" => "Nederlands",
+        "pl" => "Polski",
+        "pt" => "Português",
+        "ru" => "Русский",
+        "sv" => "Svenska",
+        "tr" => "Türkçe",
+        "uk" => "Українська",
+        _ => return language.to_string(),
+    };
+
+    // Regional variants keep their region, as in "Português (BR)".
+    match language.region {
+        Some(region) => format!("{name} ({region})"),
+        None => name.to_owned(),
+    }
+}
+
 // Get the `Localizer` to be used for localizing this library.
 #[must_use]
 pub fn localizer() -> Box<dyn Localizer> {
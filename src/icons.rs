@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Symbolic icons used by the nav bar and buttons.
+//!
+//! Icons come from the system icon theme when it has them. Minimal installs may
+//! ship without a full symbolic theme, so every icon the interface uses is also
+//! embedded and drawn from memory when the theme lookup fails.
+
+use cosmic::widget::icon;
+use rust_embed::RustEmbed;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+#[derive(RustEmbed)]
+#[folder = "resources/icons/symbolic/"]
+struct Bundled;
+
+/// Resolved icons by name, so the theme is only searched once per icon.
+static HANDLES: LazyLock<Mutex<HashMap<&'static str, icon::Handle>>> = LazyLock::new(Mutex::default);
+
+/// The icon named `name`, from the system theme or else the embedded copy.
+pub fn handle(name: &'static str) -> icon::Handle {
+    HANDLES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(name)
+        .or_insert_with(|| resolve(name))
+        .clone()
+}
+
+/// An icon widget showing the icon named `name`.
+pub fn icon(name: &'static str) -> icon::Icon {
+    icon::icon(handle(name))
+}
+
+fn resolve(name: &'static str) -> icon::Handle {
+    let named = icon::from_name(name);
+
+    if named.clone().path().is_some() {
+        return named.handle();
+    }
+
+    match Bundled::get(&format!("{name}.svg")) {
+        Some(file) => icon::from_svg_bytes(file.data).symbolic(true),
+        // Still let the toolkit try its own fallbacks for icons we do not bundle.
+        None => named.handle(),
+    }
+}
@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: GPL-3
+
+//! User inactivity detection through systemd-logind's session idle hint.
+
+use cosmic::iced::Subscription;
+use cosmic::iced_futures;
+use futures_util::SinkExt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often logind is asked for the session's idle state.
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+#[zbus::proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait Session {
+    #[zbus(property)]
+    fn idle_hint(&self) -> zbus::Result<bool>;
+
+    /// Wall-clock time the session became idle, in microseconds since the epoch.
+    #[zbus(property)]
+    fn idle_since_hint(&self) -> zbus::Result<u64>;
+}
+
+/// Emits `true` once the user has been inactive for `threshold`, and `false`
+/// when activity resumes.
+pub fn watch(threshold: Duration) -> Subscription<bool> {
+    Subscription::run_with_id(
+        ("idle", threshold),
+        iced_futures::stream::channel(1, move |mut emitter| async move {
+            let proxy = match zbus::Connection::system().await {
+                Ok(connection) => SessionProxy::new(&connection).await,
+                Err(why) => Err(why),
+            };
+
+            let proxy = match proxy {
+                Ok(proxy) => proxy,
+                Err(why) => {
+                    eprintln!("idle detection unavailable: {why}");
+                    return;
+                }
+            };
+
+            let mut idle = false;
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                let now_idle = idle_for(&proxy).await.is_some_and(|idle| idle >= threshold);
+
+                if now_idle != idle {
+                    idle = now_idle;
+                    _ = emitter.send(idle).await;
+                }
+            }
+        }),
+    )
+}
+
+/// How long the session has been idle, if it is idle.
+async fn idle_for(proxy: &SessionProxy<'_>) -> Option<Duration> {
+    if !proxy.idle_hint().await.ok()? {
+        return None;
+    }
+
+    let since = UNIX_EPOCH + Duration::from_micros(proxy.idle_since_hint().await.ok()?);
+
+    SystemTime::now().duration_since(since).ok()
+}
@@ -0,0 +1,342 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Reading and writing files in chunks, reporting progress along the way.
+//!
+//! A transfer is a stream of [`Progress`] updates ending with its result,
+//! which [`task`] turns into messages. It stops between chunks once its
+//! [`Cancel`] is triggered or the task running it is dropped. Writes go to a
+//! hidden file next to the target first, so a write that does not finish
+//! leaves the target as it was.
+//!
+//! [`read`] hands each chunk to its caller as it arrives, so only what the
+//! caller keeps of it stays in memory; [`read_to_string`] keeps the text, up
+//! to a limit. [`write`] is given the whole file up front, which suits the
+//! reports, images, and exports the app writes.
+//! Work needing another kind of I/O, such as zipping a backup, runs on a
+//! blocking thread with [`blocking`], reporting through a [`Tracker`].
+
+use crate::error::{Code, Error};
+use cosmic::Task;
+use cosmic::dialog::file_chooser;
+use cosmic::iced_futures;
+use cosmic::iced_futures::futures::channel::mpsc;
+use futures_util::{SinkExt, Stream, StreamExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Bytes read or written between progress updates.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Stops the transfer it was handed out by, from any of its copies.
+#[derive(Clone, Debug, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Copies of the same cancel are equal.
+impl PartialEq for Cancel {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// How far a transfer has got.
+#[derive(Clone, Debug)]
+pub struct Transferred {
+    pub done: u64,
+    /// Size of the whole file, if known.
+    pub total: Option<u64>,
+    /// Whether the transfer has ended, however it did.
+    pub ended: bool,
+    pub cancel: Cancel,
+}
+
+impl Transferred {
+    /// Share of the file transferred, from 0 to 1, if its size is known.
+    pub fn fraction(&self) -> Option<f32> {
+        self.total
+            .filter(|&total| total > 0)
+            .map(|total| (self.done as f64 / total as f64).min(1.0) as f32)
+    }
+}
+
+#[derive(Debug)]
+pub enum Progress<T> {
+    Transferred(Transferred),
+    /// The transfer ended, with `None` if it was cancelled.
+    Finished(Option<Result<T, Error>>),
+}
+
+/// Reads the file at `path` chunk by chunk, handing each to `consume` with
+/// `state`, which the transfer ends with. The read stops at the first error
+/// `consume` gives.
+pub fn read<S: Send + 'static>(
+    path: PathBuf,
+    state: S,
+    consume: impl FnMut(&mut S, &[u8]) -> Result<(), Error> + Send + 'static,
+) -> impl Stream<Item = Progress<S>> {
+    iced_futures::stream::channel(1, move |emitter| async move {
+        let mut reporter = Reporter::new(emitter);
+        let result = read_chunks(&path, state, consume, &mut reporter).await;
+        reporter.finish(result).await;
+    })
+}
+
+/// Reads the file at `path`, which must be UTF-8 text of at most `limit` bytes.
+pub fn read_to_string(path: PathBuf, limit: u64) -> impl Stream<Item = Progress<String>> {
+    const MIB: u64 = 1024 * 1024;
+
+    let contents = read(path, Vec::new(), move |contents: &mut Vec<u8>, chunk| {
+        if (contents.len() + chunk.len()) as u64 > limit {
+            return Err(Error::new(
+                Code::InvalidData,
+                format!("the file is larger than {} MiB", limit.div_ceil(MIB)),
+            ));
+        }
+
+        contents.extend_from_slice(chunk);
+        Ok(())
+    });
+
+    contents.map(|progress| match progress {
+        Progress::Transferred(transferred) => Progress::Transferred(transferred),
+        Progress::Finished(result) => Progress::Finished(result.map(|result| {
+            result.and_then(|bytes| {
+                String::from_utf8(bytes).map_err(|why| Error::new(Code::InvalidData, why.to_string()))
+            })
+        })),
+    })
+}
+
+/// Writes `contents` to the file at `path`, replacing it once every byte is written.
+pub fn write(path: PathBuf, contents: impl Into<Vec<u8>>) -> impl Stream<Item = Progress<()>> {
+    let contents = contents.into();
+
+    iced_futures::stream::channel(1, move |emitter| async move {
+        let mut reporter = Reporter::new(emitter);
+        let result = write_chunks(&path, &contents, &mut reporter).await;
+        reporter.finish(result).await;
+    })
+}
+
+/// Runs `work` on a blocking thread as a transfer, which reports its progress
+/// and checks whether it should stop through the [`Tracker`] it is given.
+///
+/// The work gives `None` if it stopped because it was cancelled.
+pub fn blocking<T: Send + 'static>(
+    work: impl FnOnce(&Tracker) -> Result<Option<T>, Error> + Send + 'static,
+) -> impl Stream<Item = Progress<T>> {
+    iced_futures::stream::channel(1, move |emitter| async move {
+        let mut reporter = Reporter::new(emitter);
+        let (updates, mut received) = tokio::sync::mpsc::unbounded_channel();
+
+        let tracker = Tracker {
+            updates,
+            cancel: reporter.progress.cancel.clone(),
+        };
+
+        let handle = tokio::task::spawn_blocking(move || work(&tracker));
+
+        // Ends once the work is done with the tracker.
+        while let Some((done, total)) = received.recv().await {
+            reporter.advance(done, total).await;
+        }
+
+        let result = handle
+            .await
+            .unwrap_or_else(|why| Err(Error::new(Code::Io, why.to_string())));
+        reporter.finish(result).await;
+    })
+}
+
+/// Reports the progress of work run with [`blocking`], from the thread doing it.
+pub struct Tracker {
+    updates: tokio::sync::mpsc::UnboundedSender<(u64, Option<u64>)>,
+    cancel: Cancel,
+}
+
+impl Tracker {
+    /// Whether the work should stop, because it was cancelled or nothing is
+    /// waiting for its result any more.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled() || self.updates.is_closed()
+    }
+
+    /// Reports that `done` bytes of `total` have been handled.
+    pub fn advance(&self, done: u64, total: Option<u64>) {
+        _ = self.updates.send((done, total));
+    }
+}
+
+/// Runs `transfer`, turning its progress updates and its result into messages.
+pub fn task<T: Send + 'static, Message: Send + 'static>(
+    transfer: impl Stream<Item = Progress<T>> + Send + 'static,
+    progress: impl Fn(Transferred) -> Message + Send + 'static,
+    finished: impl Fn(Option<Result<T, Error>>) -> Message + Send + 'static,
+) -> Task<Message> {
+    Task::run(transfer, move |update| match update {
+        Progress::Transferred(transferred) => progress(transferred),
+        Progress::Finished(result) => finished(result),
+    })
+}
+
+/// Asks where to save a file, suggesting `file_name`. Gives `None` if the dialog was cancelled.
+pub async fn choose_save(title: String, file_name: impl Into<String>) -> Result<Option<PathBuf>, Error> {
+    let dialog = file_chooser::save::Dialog::new().title(title).file_name(file_name);
+
+    match dialog.save_file().await {
+        Ok(response) => Ok(response.url().and_then(|url| url.to_file_path().ok())),
+        Err(file_chooser::Error::Cancelled) => Ok(None),
+        Err(why) => Err(Error::dialog(why)),
+    }
+}
+
+/// Asks for a file to open. Gives `None` if the dialog was cancelled.
+pub async fn choose_open(title: String) -> Result<Option<PathBuf>, Error> {
+    let dialog = file_chooser::open::Dialog::new().title(title);
+
+    match dialog.open_file().await {
+        Ok(response) => Ok(response.url().to_file_path().ok()),
+        Err(file_chooser::Error::Cancelled) => Ok(None),
+        Err(why) => Err(Error::dialog(why)),
+    }
+}
+
+async fn read_chunks<S>(
+    path: &Path,
+    mut state: S,
+    mut consume: impl FnMut(&mut S, &[u8]) -> Result<(), Error>,
+    reporter: &mut Reporter<S>,
+) -> Result<Option<S>, Error> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let total = file.metadata().await.ok().map(|metadata| metadata.len());
+
+    let mut chunk = vec![0; CHUNK_SIZE];
+    let mut done = 0;
+
+    loop {
+        if reporter.is_cancelled() {
+            return Ok(None);
+        }
+
+        let read = file.read(&mut chunk).await?;
+
+        if read == 0 {
+            return Ok(Some(state));
+        }
+
+        consume(&mut state, &chunk[..read])?;
+
+        // Files can grow while they are read, so the total is never less than what was read.
+        done += read as u64;
+        reporter.advance(done, total.map(|total| total.max(done))).await;
+    }
+}
+
+async fn write_chunks(path: &Path, contents: &[u8], reporter: &mut Reporter<()>) -> Result<Option<()>, Error> {
+    let partial = Partial::next_to(path);
+    let mut file = tokio::fs::File::create(&partial.path).await?;
+    let total = contents.len() as u64;
+    let mut done = 0;
+
+    for chunk in contents.chunks(CHUNK_SIZE) {
+        if reporter.is_cancelled() {
+            return Ok(None);
+        }
+
+        file.write_all(chunk).await?;
+        done += chunk.len() as u64;
+        reporter.advance(done, Some(total)).await;
+    }
+
+    file.flush().await?;
+    drop(file);
+
+    tokio::fs::rename(&partial.path, path).await?;
+    partial.keep();
+
+    Ok(Some(()))
+}
+
+/// Sends a transfer's progress, keeping track of how far it has got.
+struct Reporter<T> {
+    emitter: mpsc::Sender<Progress<T>>,
+    progress: Transferred,
+}
+
+impl<T> Reporter<T> {
+    fn new(emitter: mpsc::Sender<Progress<T>>) -> Self {
+        Self {
+            emitter,
+            progress: Transferred {
+                done: 0,
+                total: None,
+                ended: false,
+                cancel: Cancel::default(),
+            },
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.progress.cancel.is_cancelled()
+    }
+
+    async fn advance(&mut self, done: u64, total: Option<u64>) {
+        self.progress.done = done;
+        self.progress.total = total;
+        _ = self.emitter.send(Progress::Transferred(self.progress.clone())).await;
+    }
+
+    /// Reports that the transfer ended, then its result.
+    async fn finish(mut self, result: Result<Option<T>, Error>) {
+        self.progress.ended = true;
+        _ = self.emitter.send(Progress::Transferred(self.progress.clone())).await;
+        _ = self.emitter.send(Progress::Finished(result.transpose())).await;
+    }
+}
+
+/// A file being written in place of another, removed unless kept.
+pub struct Partial {
+    path: PathBuf,
+    kept: bool,
+}
+
+impl Partial {
+    pub fn next_to(target: &Path) -> Self {
+        let name = target
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+
+        Self {
+            path: target.with_file_name(format!(".{name}.part")),
+            kept: false,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Marks the file as moved into place.
+    pub fn keep(mut self) {
+        self.kept = true;
+    }
+}
+
+impl Drop for Partial {
+    fn drop(&mut self) {
+        if !self.kept {
+            _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
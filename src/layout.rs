@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The breakpoint between the usual layout and the one for narrow windows.
+//!
+//! The app follows the width of each of its windows from their resize events,
+//! and tells the views it draws in a window whether it is narrow, so content
+//! placed side by side can be stacked instead where it would not fit.
+
+/// Windows narrower than this, in logical pixels, use the narrow layout.
+pub const NARROW_WIDTH: f32 = 560.0;
+
+/// Windows narrower than this condense the nav bar to its icons, when it
+/// is set to condense automatically.
+pub const CONDENSED_NAV_WIDTH: f32 = 840.0;
+
+/// Whether a window `width` logical pixels wide uses the narrow layout.
+pub fn is_narrow(width: f32) -> bool {
+    width < NARROW_WIDTH
+}
@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The guessing game's leaderboard, one file shared by every user of the machine.
+//!
+//! The file lives in a world-writable directory with the sticky bit set, so
+//! anyone may add a result but only its owner may remove the file. It holds
+//! one JSON entry per line. Readers take a shared lock and writers an
+//! exclusive one, so two players saving at once do not lose either result.
+//!
+//! Any user can write anything to the file, so entries are checked and names
+//! cleaned up each time it is read, and only the best [`TOP`] results of each
+//! difficulty are kept.
+//!
+//! Another user could also make the directory first, or put a link to one of
+//! the player's own files in place of the leaderboard, to have it overwritten.
+//! So the directory is only used if it is shared as expected and owned by the
+//! system or the player, and the file is never opened through a link. A lock
+//! held for too long, as by someone keeping others off the leaderboard, makes
+//! reading or saving fail rather than wait forever.
+
+use crate::app::AppModel;
+use crate::error::{Code, Error};
+use crate::game::Difficulty;
+use chrono::NaiveDate;
+use cosmic::Application;
+use serde::{Deserialize, Serialize};
+use std::fs::{DirBuilder, File, OpenOptions, Permissions, TryLockError};
+use std::io::{Read, Seek, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Results shown, and kept, for each difficulty.
+pub const TOP: usize = 10;
+/// Longest player name kept, in characters.
+pub const NAME_LENGTH: usize = 24;
+/// Most of the file read, so that a huge file cannot exhaust memory.
+const MAX_FILE_SIZE: u64 = 256 * 1024;
+/// Longest wait for another player to let go of the file.
+const LOCK_TIMEOUT: Duration = Duration::from_secs(2);
+/// Pause between attempts to lock the file.
+const LOCK_RETRY: Duration = Duration::from_millis(50);
+
+/// One won round on the leaderboard.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Entry {
+    pub name: String,
+    pub difficulty: Difficulty,
+    pub attempts: u32,
+    pub date: NaiveDate,
+}
+
+/// The machine-wide leaderboard file, under `/var/tmp` since it survives
+/// reboots and every user may write there, unlike `$TMPDIR` which can differ
+/// between users.
+pub fn path() -> PathBuf {
+    PathBuf::from("/var/tmp").join(AppModel::APP_ID).join("leaderboard.jsonl")
+}
+
+/// Cleans up a name typed by a player, or found in the file.
+///
+/// Control and formatting characters, such as those reversing the text that
+/// follows them, are removed, runs of whitespace become single spaces, and
+/// the name is cut to [`NAME_LENGTH`] characters.
+pub fn sanitize(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .filter(|&c| !c.is_control() || c.is_whitespace())
+        .filter(|c| !matches!(c, '\u{200b}'..='\u{200f}' | '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}'))
+        .collect();
+
+    cleaned
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(NAME_LENGTH)
+        .collect()
+}
+
+/// Reads every result on the leaderboard, which is empty if nobody has played yet.
+pub fn load() -> Result<Vec<Entry>, Error> {
+    let path = path();
+
+    if let Some(dir) = path.parent() {
+        match std::fs::symlink_metadata(dir) {
+            Ok(_) => check_dir(dir)?,
+            Err(why) if why.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(why) => return Err(why.into()),
+        }
+    }
+
+    let mut file = match OpenOptions::new().read(true).custom_flags(libc::O_NOFOLLOW).open(&path) {
+        Ok(file) => file,
+        Err(why) if why.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(why) => return Err(why.into()),
+    };
+
+    check_file(&file)?;
+    lock(&file, false)?;
+    read(&mut file)
+}
+
+/// The best results at `difficulty`, best first.
+pub fn top(entries: &[Entry], difficulty: Difficulty) -> impl Iterator<Item = &Entry> {
+    entries.iter().filter(move |entry| entry.difficulty == difficulty).take(TOP)
+}
+
+/// Whether winning in `attempts` at `difficulty` earns a place on the leaderboard.
+pub fn qualifies(entries: &[Entry], difficulty: Difficulty, attempts: u32) -> bool {
+    let placed: Vec<_> = top(entries, difficulty).collect();
+    placed.len() < TOP || placed.last().is_some_and(|worst| attempts < worst.attempts)
+}
+
+/// Adds `entry` to the leaderboard, creating the file if needed, and gives
+/// back every result now on it.
+pub fn submit(mut entry: Entry) -> Result<Vec<Entry>, Error> {
+    let path = path();
+
+    if let Some(dir) = path.parent() {
+        if std::fs::symlink_metadata(dir).is_err() {
+            DirBuilder::new().mode(0o1777).create(dir)?;
+            // The umask may have taken away the write bits other users need.
+            _ = std::fs::set_permissions(dir, Permissions::from_mode(0o1777));
+        }
+
+        check_dir(dir)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o666)
+        .custom_flags(libc::O_NOFOLLOW)
+        .open(&path)?;
+    check_file(&file)?;
+    _ = file.set_permissions(Permissions::from_mode(0o666));
+
+    lock(&file, true)?;
+
+    let mut entries = read(&mut file)?;
+    entry.name = sanitize(&entry.name);
+    entries.push(entry);
+    let entries = ranked(entries);
+
+    let mut text = String::new();
+
+    for entry in &entries {
+        let line = serde_json::to_string(entry).map_err(std::io::Error::from)?;
+        text.push_str(&line);
+        text.push('\n');
+    }
+
+    // Written in place rather than renamed over, since the directory's
+    // sticky bit keeps other users from replacing the file.
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+
+    Ok(entries)
+}
+
+/// Refuses a leaderboard directory that is a link, is not shared with the
+/// sticky bit set, or belongs to someone other than the system or the player.
+fn check_dir(dir: &Path) -> Result<(), Error> {
+    let metadata = std::fs::symlink_metadata(dir)?;
+    // SAFETY: `geteuid` has no preconditions and cannot fail.
+    let uid = unsafe { libc::geteuid() };
+
+    let shared = metadata.is_dir() && metadata.mode() & 0o7777 == 0o1777;
+    let trusted = metadata.uid() == 0 || metadata.uid() == uid;
+
+    if shared && trusted {
+        Ok(())
+    } else {
+        Err(Error::new(
+            Code::Io,
+            format!("{} is not a shared directory owned by the system or you", dir.display()),
+        ))
+    }
+}
+
+/// Refuses a leaderboard that is not a regular file, such as a device.
+fn check_file(file: &File) -> Result<(), Error> {
+    if file.metadata()?.is_file() {
+        Ok(())
+    } else {
+        Err(Error::new(Code::Io, "the leaderboard is not a regular file"))
+    }
+}
+
+/// Locks `file`, for writing if `exclusive`, giving up after [`LOCK_TIMEOUT`].
+fn lock(file: &File, exclusive: bool) -> Result<(), Error> {
+    let started = Instant::now();
+
+    loop {
+        let result = if exclusive { file.try_lock() } else { file.try_lock_shared() };
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(TryLockError::Error(why)) => return Err(why.into()),
+            Err(TryLockError::WouldBlock) if started.elapsed() >= LOCK_TIMEOUT => {
+                return Err(Error::new(Code::Io, "the leaderboard is in use by someone else"));
+            }
+            Err(TryLockError::WouldBlock) => std::thread::sleep(LOCK_RETRY),
+        }
+    }
+}
+
+/// Reads the entries of a locked file, skipping lines that are not valid entries.
+fn read(file: &mut File) -> Result<Vec<Entry>, Error> {
+    let mut bytes = Vec::new();
+    file.rewind()?;
+    file.by_ref().take(MAX_FILE_SIZE).read_to_end(&mut bytes)?;
+
+    let entries = String::from_utf8_lossy(&bytes)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Entry>(line).ok())
+        .filter(|entry| entry.attempts > 0)
+        .map(|entry| Entry {
+            name: sanitize(&entry.name),
+            ..entry
+        })
+        .collect();
+
+    Ok(ranked(entries))
+}
+
+/// Sorts entries best first, earliest first among equals, keeping the best
+/// [`TOP`] of each difficulty.
+fn ranked(mut entries: Vec<Entry>) -> Vec<Entry> {
+    entries.sort_by_key(|entry| (entry.attempts, entry.date));
+
+    let mut kept: Vec<Entry> = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        if top(&kept, entry.difficulty).count() < TOP {
+            kept.push(entry);
+        }
+    }
+
+    kept
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Session lock detection through systemd-logind.
+
+use cosmic::iced::Subscription;
+use cosmic::iced_futures;
+use futures_util::{SinkExt, StreamExt};
+
+#[zbus::proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait Session {
+    /// Asks the session's screen locker to lock.
+    #[zbus(signal)]
+    fn lock(&self) -> zbus::Result<()>;
+
+    /// Asks the session's screen locker to unlock.
+    #[zbus(signal)]
+    fn unlock(&self) -> zbus::Result<()>;
+
+    /// Set by the screen locker while the session is locked.
+    #[zbus(property)]
+    fn locked_hint(&self) -> zbus::Result<bool>;
+}
+
+/// Emits `true` when the session locks and `false` when it unlocks.
+///
+/// Both logind's lock requests and the locker's hint are followed, since a
+/// locker unlocked by typing the password only clears the hint.
+pub fn watch() -> Subscription<bool> {
+    Subscription::run_with_id(
+        "session-lock",
+        iced_futures::stream::channel(1, |mut emitter| async move {
+            let proxy = match zbus::Connection::system().await {
+                Ok(connection) => SessionProxy::new(&connection).await,
+                Err(why) => Err(why),
+            };
+
+            let proxy = match proxy {
+                Ok(proxy) => proxy,
+                Err(why) => {
+                    eprintln!("session lock detection unavailable: {why}");
+                    return;
+                }
+            };
+
+            let (locks, unlocks) = match (proxy.receive_lock().await, proxy.receive_unlock().await) {
+                (Ok(locks), Ok(unlocks)) => (locks, unlocks),
+                (Err(why), _) | (_, Err(why)) => {
+                    eprintln!("session lock detection unavailable: {why}");
+                    return;
+                }
+            };
+
+            let hints = proxy
+                .receive_locked_hint_changed()
+                .await
+                .filter_map(|change| async move { change.get().await.ok() });
+
+            let mut changes = futures_util::stream::select_all([
+                locks.map(|_| true).boxed(),
+                unlocks.map(|_| false).boxed(),
+                hints.boxed(),
+            ]);
+
+            let mut locked = proxy.locked_hint().await.unwrap_or(false);
+
+            while let Some(now_locked) = changes.next().await {
+                if now_locked != locked {
+                    locked = now_locked;
+                    _ = emitter.send(locked).await;
+                }
+            }
+        }),
+    )
+}
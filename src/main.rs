@@ -1,8 +1,152 @@
 // SPDX-License-Identifier: GPL-3
 
+mod animation;
 mod app;
+mod audio;
+mod backup;
+mod clipboard;
 mod config;
+mod contrast;
+mod counters;
+mod daily;
+mod dbus;
+mod error;
+mod filter;
+mod format;
+mod game;
+mod generators;
+mod headers;
 mod i18n;
+mod icons;
+mod idle;
+mod io;
+mod layout;
+mod leaderboard;
+mod lock;
+mod menu_keys;
+mod mpris;
+mod notify;
+mod pages;
+mod palette;
+mod play;
+mod print;
+mod report;
+mod selection;
+mod sessions;
+mod shortcut;
+mod snapshot;
+mod speech;
+mod state;
+mod storage;
+mod tasks;
+mod timer;
+mod usage;
+mod widgets;
+mod workspace;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use cosmic::cosmic_config::{self, CosmicConfigEntry};
+use error::{Code, Error};
+use generators::password::CharClasses;
+use std::io::Write;
+
+/// Options given on the command line.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Open the page at this position in the nav bar, counting from 1.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u8).range(1..=app::Page::ALL.len() as i64)
+    )]
+    page: Option<u8>,
+
+    /// Run the timers, D-Bus signals, and notifications without opening a window.
+    #[arg(long)]
+    daemon: bool,
+
+    /// Run as a separate instance under this app ID, which is also its window class.
+    #[arg(
+        long,
+        visible_alias = "class",
+        value_name = "ID",
+        value_parser = clap::builder::NonEmptyStringValueParser::new()
+    )]
+    app_id: Option<String>,
+
+    /// Print a password generated with the saved options, and exit without opening a window.
+    #[arg(long)]
+    generate_password: bool,
+
+    /// Print the saved configuration and data as JSON, and exit without opening a window.
+    #[arg(long)]
+    dump_state: bool,
+
+    /// Files passed by the desktop entry, which are not used yet.
+    #[arg(hide = true)]
+    files: Vec<String>,
+}
+
+/// Tasks run from the terminal instead of opening a window.
+#[derive(Subcommand)]
+enum Command {
+    /// Print generated passwords, one per line.
+    Genpass(Genpass),
+    /// Play the guessing game in the terminal.
+    Play(Play),
+}
+
+/// Options of `genpass`. Those not given follow the options saved in the app.
+#[derive(clap::Args)]
+struct Genpass {
+    /// Number of characters in each password, within the lengths the app offers.
+    #[arg(
+        short,
+        long,
+        value_parser = clap::value_parser!(u32).range(
+            i64::from(*pages::password::PASSWORD_LENGTHS.start())..=i64::from(*pages::password::PASSWORD_LENGTHS.end())
+        )
+    )]
+    length: Option<u32>,
+
+    /// Character classes to draw from, separated by commas.
+    #[arg(short, long, value_name = "CLASS", value_delimiter = ',')]
+    classes: Option<Vec<CharClass>>,
+
+    /// Number of passwords to print.
+    #[arg(short = 'n', long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    count: u32,
+}
+
+/// Options of `play`. Without any, a free play round at the saved difficulty is played.
+#[derive(clap::Args)]
+struct Play {
+    /// Play this campaign level, counting from 1.
+    #[arg(
+        long,
+        value_name = "N",
+        conflicts_with = "daily",
+        value_parser = clap::value_parser!(u32).range(1..=i64::from(game::CAMPAIGN_LEVELS))
+    )]
+    level: Option<u32>,
+
+    /// Play today's daily challenge, recording it as solved if the number is found.
+    #[arg(long)]
+    daily: bool,
+}
+
+/// A character class named on the command line.
+#[derive(Clone, Copy, Eq, PartialEq, ValueEnum)]
+enum CharClass {
+    Lower,
+    Upper,
+    Digits,
+    Symbols,
+}
 
 fn main() -> cosmic::iced::Result {
     // Get the system's preferred languages.
@@ -11,13 +155,194 @@ fn main() -> cosmic::iced::Result {
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
+    // Bad options exit with the status of their error code, so scripts can tell why,
+    // while help and the version are printed as asked.
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(why) if !why.use_stderr() => why.exit(),
+        Err(why) => {
+            _ = why.print();
+            std::process::exit(Code::Usage.exit_status());
+        }
+    };
+
+    if cli.dump_state {
+        match dump_state() {
+            Ok(json) => {
+                println!("{json}");
+                std::process::exit(0);
+            }
+            Err(why) => {
+                eprintln!("counter: {why}");
+                std::process::exit(why.code.exit_status());
+            }
+        }
+    }
+
+    // `--generate-password` is `genpass` with the saved options.
+    let genpass = match cli.command {
+        Some(Command::Play(play)) => match play_game(&play) {
+            Ok(()) => std::process::exit(0),
+            Err(why) => {
+                eprintln!("counter: {why}");
+                std::process::exit(why.code.exit_status());
+            }
+        },
+        Some(Command::Genpass(genpass)) => Some(genpass),
+        None if cli.generate_password => Some(Genpass {
+            length: None,
+            classes: None,
+            count: 1,
+        }),
+        None => None,
+    };
+
+    if let Some(genpass) = genpass {
+        match print_passwords(&genpass) {
+            Ok(()) => std::process::exit(0),
+            Err(why) => {
+                eprintln!("counter: {why}");
+                std::process::exit(why.code.exit_status());
+            }
+        }
+    }
+
+    // Pages are numbered from 1, as in the nav bar.
+    let page = cli.page.map(|num| usize::from(num) - 1);
+
+    let flags = app::Flags {
+        page,
+        daemon: cli.daemon,
+        app_id: cli.app_id,
+        // An instance already running is asked to show the page instead.
+        action: page.map(|idx| app::MenuAction::ShowPage(app::Page::ALL[idx]).name()),
+    };
+
     // Settings for configuring the application window and iced runtime.
-    let settings = cosmic::app::Settings::default().size_limits(
-        cosmic::iced::Limits::NONE
-            .min_width(360.0)
-            .min_height(180.0),
-    );
-
-    // Starts the application's event loop with `()` as the application's flags.
-    cosmic::app::run::<app::AppModel>(settings, ())
+    let settings = cosmic::app::Settings::default()
+        .size_limits(
+            cosmic::iced::Limits::NONE
+                .min_width(360.0)
+                .min_height(180.0),
+        )
+        // A daemon starts without a window and outlives the ones it opens, and a
+        // window with another app ID is opened by the app itself.
+        .no_main_window(flags.daemon || flags.app_id.is_some())
+        .exit_on_close(!flags.daemon);
+
+    // Starts the application's event loop with the command line options as its flags.
+    // Launches with the usual app ID are handed to an instance already running, such as
+    // a daemon, which raises its window rather than a second one opening; those with
+    // their own app ID run as separate instances.
+    if flags.app_id.is_some() {
+        cosmic::app::run::<app::AppModel>(settings, flags)
+    } else {
+        cosmic::app::run_single_instance::<app::AppModel>(settings, flags)
+    }
+}
+
+/// Plays the round `options` ask for on the terminal, at the saved difficulty
+/// unless a campaign level or the daily challenge was chosen.
+///
+/// The daily challenge is played once a day, as in the app, so asking for it
+/// again once solved fails with the attempts it took.
+fn play_game(options: &Play) -> Result<(), Error> {
+    let today = chrono::Local::now().date_naive();
+
+    let (engine, intro) = if let Some(level) = options.level.and_then(game::campaign_level) {
+        let intro = fl!("level-start", upper = *level.range.end(), attempts = level.max_attempts);
+        (game::Engine::for_level(level), intro)
+    } else if options.daily {
+        if let Some(attempts) = daily::Completions::load().get(today) {
+            return Err(Error::new(Code::Usage, fl!("daily-played", attempts = attempts)));
+        }
+
+        (game::Engine::daily(today), fl!("daily-start"))
+    } else {
+        let range = load_config().game_difficulty.range();
+        let intro = fl!("free-play-start", low = *range.start(), high = *range.end());
+        (game::Engine::new(range), intro)
+    };
+
+    let attempts = play::run(engine, intro, std::io::stdin().lock(), std::io::stdout().lock())?;
+
+    // Solving the daily challenge here counts as solving it in the app.
+    if let (true, Some(attempts)) = (options.daily, attempts) {
+        daily::Completions::load().record(today, attempts);
+    }
+
+    Ok(())
+}
+
+/// Everything the app keeps between launches as one JSON document: the
+/// configuration with its counters and alarms, where the pages were left, the
+/// recorded sessions, solved daily challenges, and usage counts.
+fn dump_state() -> Result<String, Error> {
+    let usage = usage::Usage::load();
+
+    let dump = serde_json::json!({
+        "config": load_config(),
+        "state": state::State::load(),
+        "sessions": sessions::History::load().sessions,
+        "daily": daily::Completions::load().days(),
+        "usage": {
+            "launches": usage.launches,
+            "games_played": usage.games_played,
+            "page_visits": (0..app::Page::ALL.len()).map(|page| usage.visits(page)).collect::<Vec<_>>(),
+        },
+    });
+
+    serde_json::to_string_pretty(&dump).map_err(|why| Error::new(Code::InvalidData, why.to_string()))
+}
+
+/// The configuration saved by the app, or the defaults if it cannot be read.
+fn load_config() -> config::Config {
+    let app_id = <app::AppModel as cosmic::Application>::APP_ID;
+
+    cosmic_config::Config::new(app_id, config::Config::VERSION)
+        .ok()
+        .map(|context| config::Config::get_entry(&context).unwrap_or_else(|(_errors, config)| config))
+        .unwrap_or_default()
+}
+
+/// Prints passwords as `options` ask, with the length and character classes
+/// saved in the config unless given, each as soon as it is generated.
+fn print_passwords(options: &Genpass) -> Result<(), Error> {
+    let mut spec = pages::password::password_spec(&load_config());
+
+    if let Some(length) = options.length {
+        spec = spec.length(length as usize);
+    }
+
+    if let Some(classes) = &options.classes {
+        spec = spec.classes(CharClasses {
+            lowercase: classes.contains(&CharClass::Lower),
+            uppercase: classes.contains(&CharClass::Upper),
+            digits: classes.contains(&CharClass::Digits),
+            symbols: classes.contains(&CharClass::Symbols),
+        });
+    }
+
+    // A spec that cannot produce a password is the command line's fault when
+    // it changed the spec, and the saved options' otherwise.
+    let code = if options.length.is_some() || options.classes.is_some() {
+        Code::Usage
+    } else {
+        Code::InvalidData
+    };
+
+    let mut stdout = std::io::stdout().lock();
+
+    for _ in 0..options.count {
+        let password = spec.generate().map_err(|why| Error::new(code, why.to_string()))?;
+
+        match writeln!(stdout, "{password}") {
+            Ok(()) => {}
+            // A reader that has seen enough, such as `head`, closes the pipe.
+            Err(why) if why.kind() == std::io::ErrorKind::BrokenPipe => break,
+            Err(why) => return Err(why.into()),
+        }
+    }
+
+    Ok(())
 }
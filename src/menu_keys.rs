@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Moving through the header menus with the keyboard, so they work without a pointer.
+//!
+//! Menus are given as the labels of their entries, with `None` for dividers
+//! and anything else that cannot be chosen. Each menu and entry has the first
+//! letter of its label as its mnemonic.
+
+/// Which menu is open from the keyboard, and which of its entries is highlighted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct MenuFocus {
+    pub menu: usize,
+    pub entry: usize,
+}
+
+impl MenuFocus {
+    /// Opens `menu` with its first entry highlighted.
+    pub fn open(menu: usize, menus: &[Vec<Option<String>>]) -> Self {
+        let entry = menus
+            .get(menu)
+            .and_then(|entries| entries.iter().position(Option::is_some))
+            .unwrap_or(0);
+
+        Self { menu, entry }
+    }
+
+    /// Opens the menu `offset` places along, wrapping around the ends.
+    pub fn move_menu(self, offset: isize, menus: &[Vec<Option<String>>]) -> Self {
+        if menus.is_empty() {
+            return self;
+        }
+
+        let menu = (self.menu as isize + offset).rem_euclid(menus.len() as isize) as usize;
+        Self::open(menu, menus)
+    }
+
+    /// Highlights the entry `offset` places along, skipping dividers and
+    /// wrapping around the ends.
+    pub fn move_entry(self, offset: isize, menus: &[Vec<Option<String>>]) -> Self {
+        let Some(entries) = menus.get(self.menu) else {
+            return self;
+        };
+
+        let len = entries.len() as isize;
+        let step = offset.signum();
+        let mut entry = self.entry as isize;
+        let mut left = offset.abs();
+
+        // One lap at most, in case no entry can be chosen.
+        for _ in 0..len * offset.abs() {
+            entry = (entry + step).rem_euclid(len);
+
+            if entries[entry as usize].is_some() {
+                left -= 1;
+
+                if left == 0 {
+                    return Self {
+                        entry: entry as usize,
+                        ..self
+                    };
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Highlights the next entry after the current one whose mnemonic is `letter`.
+    pub fn jump(self, letter: char, menus: &[Vec<Option<String>>]) -> Option<Self> {
+        let entries = menus.get(self.menu)?;
+        let len = entries.len();
+
+        (1..=len)
+            .map(|step| (self.entry + step) % len)
+            .find(|&entry| entries[entry].as_deref().is_some_and(|label| has_mnemonic(label, letter)))
+            .map(|entry| Self { entry, ..self })
+    }
+}
+
+/// The menu whose title has `letter` as its mnemonic.
+pub fn menu_with_mnemonic<'a>(titles: impl IntoIterator<Item = &'a str>, letter: char) -> Option<usize> {
+    titles.into_iter().position(|title| has_mnemonic(title, letter))
+}
+
+fn has_mnemonic(label: &str, letter: char) -> bool {
+    label
+        .chars()
+        .find(|c| c.is_alphanumeric())
+        .is_some_and(|first| first.to_lowercase().eq(letter.to_lowercase()))
+}
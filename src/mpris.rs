@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: GPL-3
+
+//! An MPRIS media player on the session bus, so that the desktop's Play/Pause
+//! media keys can control the app's timers.
+//!
+//! The COSMIC settings daemon sends media key presses to the active MPRIS
+//! player, so registering as one is how the app receives them.
+
+use crate::app::AppModel;
+use cosmic::Application;
+use cosmic::iced::Subscription;
+use cosmic::iced_futures;
+use futures_util::SinkExt;
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::{OwnedValue, Value};
+
+/// Prefix of the well-known name the player is registered under, which the
+/// app ID completes.
+const NAME_PREFIX: &str = "org.mpris.MediaPlayer2";
+/// Object path required by the MPRIS specification.
+const PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// A media key press, or the player becoming available.
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// The player is registered. Whether a timer is running should be sent
+    /// through this so the desktop shows the right playback status.
+    Ready(mpsc::UnboundedSender<bool>),
+    PlayPause,
+    Play,
+    Pause,
+    Stop,
+}
+
+struct Root;
+
+#[zbus::interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> String {
+        crate::fl!("app-title")
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+struct Player {
+    events: mpsc::UnboundedSender<Event>,
+    playing: bool,
+}
+
+#[zbus::interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn play_pause(&self) {
+        _ = self.events.send(Event::PlayPause);
+    }
+
+    fn play(&self) {
+        _ = self.events.send(Event::Play);
+    }
+
+    fn pause(&self) {
+        _ = self.events.send(Event::Pause);
+    }
+
+    fn stop(&self) {
+        _ = self.events.send(Event::Stop);
+    }
+
+    fn next(&self) {}
+
+    fn previous(&self) {}
+
+    #[zbus(property)]
+    fn playback_status(&self) -> &str {
+        if self.playing { "Playing" } else { "Paused" }
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        let title = Value::from(crate::fl!("app-title"));
+
+        title
+            .try_to_owned()
+            .map(|title| HashMap::from([(String::from("xesam:title"), title)]))
+            .unwrap_or_default()
+    }
+
+    #[zbus(property)]
+    fn can_control(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        false
+    }
+}
+
+/// Registers the player for as long as the subscription is active, emitting
+/// each media key press.
+pub fn serve() -> Subscription<Event> {
+    Subscription::run(|| {
+        iced_futures::stream::channel(1, |mut output| async move {
+            let (events, mut presses) = mpsc::unbounded_channel();
+
+            let name = format!("{NAME_PREFIX}.{}", AppModel::APP_ID);
+
+            let connection = zbus::connection::Builder::session()
+                .and_then(|builder| builder.name(name))
+                .and_then(|builder| builder.serve_at(PATH, Root))
+                .and_then(|builder| builder.serve_at(PATH, Player { events, playing: false }));
+
+            let connection = match connection {
+                Ok(builder) => builder.build().await,
+                Err(why) => Err(why),
+            };
+
+            let connection = match connection {
+                Ok(connection) => connection,
+                Err(why) => {
+                    eprintln!("media keys unavailable: {why}");
+                    return;
+                }
+            };
+
+            let (status, mut statuses) = mpsc::unbounded_channel();
+            _ = output.send(Event::Ready(status)).await;
+
+            loop {
+                tokio::select! {
+                    Some(event) = presses.recv() => {
+                        _ = output.send(event).await;
+                    }
+
+                    Some(playing) = statuses.recv() => {
+                        if let Err(why) = set_playing(&connection, playing).await {
+                            eprintln!("failed to update playback status: {why}");
+                        }
+                    }
+
+                    else => break,
+                }
+            }
+        })
+    })
+}
+
+/// Updates the playback status and notifies the desktop of the change.
+async fn set_playing(connection: &zbus::Connection, playing: bool) -> zbus::Result<()> {
+    let player = connection.object_server().interface::<_, Player>(PATH).await?;
+    player.get_mut().await.playing = playing;
+
+    let emitter = SignalEmitter::new(connection, PATH)?;
+    player.get().await.playback_status_changed(&emitter).await
+}
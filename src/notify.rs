@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Desktop notifications through the `org.freedesktop.Notifications` service.
+//!
+//! They carry messages that would be toasts while no window is open, and
+//! announcements of events when the user turned notifications on.
+
+use crate::app::AppModel;
+use crate::fl;
+use cosmic::Application;
+use std::collections::HashMap;
+use zbus::zvariant::Value;
+
+/// Shows `body` in a desktop notification from the app.
+pub async fn send(body: String) {
+    if let Err(why) = notify(&fl!("app-title"), &body).await {
+        eprintln!("failed to show notification: {why}");
+    }
+}
+
+async fn notify(summary: &str, body: &str) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+
+    // See the org.freedesktop.Notifications specification for the arguments.
+    connection
+        .call_method(
+            Some("org.freedesktop.Notifications"),
+            "/org/freedesktop/Notifications",
+            Some("org.freedesktop.Notifications"),
+            "Notify",
+            &(
+                AppModel::APP_ID,
+                0u32,
+                // Named after the app ID, the app's icon is found from its desktop entry.
+                AppModel::APP_ID,
+                summary,
+                body,
+                Vec::<&str>::new(),
+                HashMap::<&str, Value<'_>>::new(),
+                -1i32,
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
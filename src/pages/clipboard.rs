@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The clipboard page: recent copies from any app, each of which can be copied again.
+//!
+//! The history lives in memory only and is forgotten when the app closes or
+//! the session locks, since copies often hold passwords and other secrets.
+//! Copies marked as secret by a password manager are never recorded.
+
+use super::{Context, Page};
+use crate::clipboard;
+use crate::config::Config;
+use crate::fl;
+use crate::icons;
+use crate::lock;
+use crate::widgets;
+use chrono::{DateTime, Local};
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::{Length, Subscription, clipboard as window_clipboard};
+use cosmic::prelude::*;
+use cosmic::widget;
+
+/// Most copies kept in the history.
+const HISTORY_LIMIT: usize = 50;
+/// Characters of a copy shown in its row before it is cut short.
+const PREVIEW_LENGTH: usize = 80;
+
+pub struct ClipboardPage {
+    /// Whether copies are being recorded.
+    following: bool,
+    /// Copied texts, newest first.
+    history: Vec<Entry>,
+    /// Why the clipboard could not be followed, once it failed.
+    unavailable: Option<String>,
+}
+
+/// A text copied while the clipboard was followed.
+struct Entry {
+    text: String,
+    copied: DateTime<Local>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Clipboard(clipboard::Event),
+    SessionLocked(bool),
+    Follow(bool),
+    Copy(usize),
+    Remove(usize),
+    Clear,
+}
+
+impl ClipboardPage {
+    pub fn new() -> Self {
+        Self {
+            following: false,
+            history: Vec::new(),
+            unavailable: None,
+        }
+    }
+
+    fn entry_view(&self, idx: usize, entry: &Entry) -> Element<'_, Message> {
+        let first_line = entry.text.lines().next().unwrap_or_default();
+        let mut preview: String = first_line.chars().take(PREVIEW_LENGTH).collect();
+
+        if preview.chars().count() < entry.text.trim_end().chars().count() {
+            preview.push('…');
+        }
+
+        let description = fl!(
+            "clipboard-entry-details",
+            time = entry.copied.format("%X").to_string(),
+            characters = entry.text.chars().count()
+        );
+
+        let controls = widget::row::with_capacity(2)
+            .push(widget::button::icon(icons::handle("edit-copy-symbolic")).on_press(Message::Copy(idx)))
+            .push(widget::button::icon(icons::handle("edit-delete-symbolic")).on_press(Message::Remove(idx)))
+            .align_y(Vertical::Center)
+            .spacing(cosmic::theme::spacing().space_xxs);
+
+        cosmic::widget::settings::item::builder(preview)
+            .description(description)
+            .control(controls)
+            .into()
+    }
+}
+
+impl Page for ClipboardPage {
+    type Message = Message;
+
+    fn view<'a>(&'a self, _config: &'a Config, _narrow: bool) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let follow = cosmic::widget::settings::section().add(
+            cosmic::widget::settings::item::builder(fl!("follow-clipboard"))
+                .description(fl!("follow-clipboard-description"))
+                .toggler(self.following, Message::Follow),
+        );
+
+        let mut column = widget::column::with_capacity(5)
+            .push(super::header(fl!("clipboard-history"), 5))
+            .push(follow);
+
+        if let Some(why) = &self.unavailable {
+            column = column.push(widget::text::body(fl!("clipboard-unavailable", reason = why.as_str())));
+        }
+
+        if self.history.is_empty() {
+            let mut empty = widgets::empty_state(icons::handle("edit-paste-symbolic"), fl!("clipboard-empty"))
+                .description(fl!("clipboard-empty-description"));
+
+            if !self.following {
+                empty = empty.action(fl!("follow-clipboard"), Message::Follow(true));
+            }
+
+            column = column.push(empty);
+        } else {
+            let history = self
+                .history
+                .iter()
+                .enumerate()
+                .fold(cosmic::widget::settings::section(), |section, (idx, entry)| {
+                    section.add(self.entry_view(idx, entry))
+                });
+
+            column = column
+                .push(history)
+                .push(widget::button::standard(fl!("clear-clipboard-history")).on_press(Message::Clear));
+        }
+
+        column
+            .spacing(space_s)
+            .width(Length::Fill)
+            .apply(widget::scrollable)
+            .into()
+    }
+
+    fn update(&mut self, message: Message, ctx: &mut Context<'_>) -> Task<Message> {
+        match message {
+            Message::Clipboard(clipboard::Event::Copied(text)) => {
+                // A copy made again, as from this page, moves to the top.
+                self.history.retain(|entry| entry.text != text);
+                self.history.insert(
+                    0,
+                    Entry {
+                        text,
+                        copied: Local::now(),
+                    },
+                );
+                self.history.truncate(HISTORY_LIMIT);
+            }
+            Message::Clipboard(clipboard::Event::Unavailable(why)) => {
+                self.following = false;
+                self.unavailable = Some(why);
+            }
+            Message::SessionLocked(true) => {
+                // Whoever unlocks the session next should not find what was copied before.
+                self.history.clear();
+            }
+            Message::SessionLocked(false) => {}
+            Message::Follow(following) => {
+                self.following = following;
+                self.unavailable = None;
+            }
+            Message::Copy(idx) => {
+                if let Some(entry) = self.history.get(idx) {
+                    ctx.toast(fl!("clipboard-copied"));
+                    return window_clipboard::write(entry.text.clone());
+                }
+            }
+            Message::Remove(idx) => {
+                if idx < self.history.len() {
+                    self.history.remove(idx);
+                }
+            }
+            Message::Clear => {
+                self.history.clear();
+            }
+        }
+
+        Task::none()
+    }
+
+    fn subscription(&self, _config: &Config) -> Subscription<Message> {
+        let locks = lock::watch().map(Message::SessionLocked);
+
+        if self.following {
+            Subscription::batch([locks, clipboard::watch().map(Message::Clipboard)])
+        } else {
+            locks
+        }
+    }
+}
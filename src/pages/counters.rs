@@ -0,0 +1,952 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The counter page: groups of counters with goals, display options, and scheduled resets.
+
+use super::{Change, Context, HOURS, MINUTES, Page, Removed};
+use crate::audio::SoundEvent;
+use crate::config::Config;
+use crate::counters::{self, Counter, Group, ResetSchedule};
+use crate::dbus;
+use crate::error::Error;
+use crate::fl;
+use crate::format::{self, NumberFormat};
+use crate::icons;
+use crate::io;
+use crate::state::State;
+use crate::widgets::{self, NumpadKey};
+use chrono::Local;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::keyboard::{Key, key::Named};
+use cosmic::iced::{Length, Subscription};
+use cosmic::widget;
+use cosmic::{iced_futures, prelude::*};
+use futures_util::SinkExt;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Width of a counter's step and bound fields, enough for several digits.
+const LIMIT_INPUT_WIDTH: f32 = 96.0;
+/// Points in time drawn in a counter's history chart, evenly spread from its
+/// first recorded value to now.
+const SPARKLINE_POINTS: usize = 60;
+/// How long changes to the history gather before it is written to disk.
+const HISTORY_SAVE_DELAY: Duration = Duration::from_secs(2);
+/// Steps taken by Page Up and Page Down, rather than one as with the arrow keys.
+const PAGE_STEPS: i64 = 10;
+
+static VALUE_INPUT: LazyLock<widget::Id> = LazyLock::new(|| widget::Id::new("counter-value"));
+
+pub struct CountersPage {
+    /// The group or counter being renamed, with the name typed so far.
+    renaming: Option<(Renaming, String)>,
+    /// The group and counter whose display options are shown, if any.
+    formatting_counter: Option<(usize, usize)>,
+    /// Labels for the counter reset choices: never, daily, and weekly.
+    reset_labels: Vec<String>,
+    /// Localized weekday names, starting from Monday.
+    weekday_labels: Vec<String>,
+    /// The group and counter whose goal is being edited, with the goal text.
+    editing_goal: Option<((usize, usize), String)>,
+    /// The step, minimum, and maximum being typed for the counter whose
+    /// options are shown.
+    limits: [String; 3],
+    /// The group and counter the keyboard adjusts, if any.
+    selected: Option<(usize, usize)>,
+    /// The value being typed for the selected counter, while its field is shown.
+    value_input: Option<String>,
+    /// Every value the counters have had.
+    history: counters::History,
+    /// Whether the history changed since it was last written to disk.
+    history_unsaved: bool,
+}
+
+/// A setting limiting how a counter's value changes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Limit {
+    Step,
+    Min,
+    Max,
+}
+
+/// Something on the counter page which can be renamed in place.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Renaming {
+    Group(usize),
+    Counter(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Increment(usize, usize),
+    Decrement(usize, usize),
+    AddCounter(usize),
+    RemoveCounter(usize, usize),
+    AddGroup,
+    Rename(Renaming),
+    RenameInput(String),
+    CommitRename,
+    CancelRename,
+    DeleteGroup(usize),
+    EditGoal(usize, usize),
+    ToggleCounterFormat(usize, usize),
+    SetCounterFormat(usize, usize, NumberFormat),
+    SetCounterReset(usize, usize, Option<ResetSchedule>),
+    CheckCounterResets,
+    /// Returns every counter to zero.
+    ResetAll,
+    GoalInput(String),
+    CommitGoal,
+    LimitInput(Limit, String),
+    CommitLimits,
+    SelectCounter(usize, usize),
+    Deselect,
+    StepSelected(i64),
+    /// Shows the selected counter's value field, holding the text typed so far
+    /// or else its current value.
+    EditValue(Option<String>),
+    ValueInput(String),
+    /// A key pressed on the number pad beneath the value field.
+    Numpad(NumpadKey),
+    CommitValue,
+    ExportHistory(usize, usize),
+    HistoryExported(Option<Result<PathBuf, Error>>),
+    Transferred(io::Transferred),
+    SaveHistory,
+}
+
+impl CountersPage {
+    pub fn new() -> Self {
+        Self {
+            renaming: None,
+            formatting_counter: None,
+            reset_labels: reset_labels(),
+            weekday_labels: super::weekday_labels(),
+            editing_goal: None,
+            limits: Default::default(),
+            selected: None,
+            value_input: None,
+            history: counters::History::load(),
+            history_unsaved: false,
+        }
+    }
+
+    /// Records in the history every counter whose value is no longer the one
+    /// in `before`, as a reset if `reset`.
+    fn record(&mut self, before: &HashMap<u64, i64>, groups: &[Group], reset: bool) {
+        let now = Local::now().timestamp();
+
+        for counter in groups.iter().flat_map(|group| &group.counters) {
+            if let Some(&value) = before.get(&counter.id).filter(|&&value| value != counter.value) {
+                self.history.record(counter.id, now, value, counter.value, reset);
+                self.history_unsaved = true;
+            }
+        }
+    }
+
+    /// The message for a key pressed while a counter is selected, if the key adjusts it.
+    ///
+    /// The arrow keys step the counter, Page Up and Page Down step it further,
+    /// and Enter or typing a number opens its value field.
+    pub fn key(&self, key: &Key) -> Option<Message> {
+        self.selected?;
+
+        match key {
+            Key::Named(Named::ArrowUp) => Some(Message::StepSelected(1)),
+            Key::Named(Named::ArrowDown) => Some(Message::StepSelected(-1)),
+            Key::Named(Named::PageUp) => Some(Message::StepSelected(PAGE_STEPS)),
+            Key::Named(Named::PageDown) => Some(Message::StepSelected(-PAGE_STEPS)),
+            Key::Named(Named::Escape) => Some(Message::Deselect),
+            Key::Named(Named::Enter) if self.value_input.is_none() => Some(Message::EditValue(None)),
+            Key::Character(text)
+                if self.value_input.is_none() && text.chars().all(|c| c.is_ascii_digit() || c == '-') =>
+            {
+                Some(Message::EditValue(Some(text.to_string())))
+            }
+            _ => None,
+        }
+    }
+
+    /// Changes a counter by the amount `delta` gives for it, announcing when
+    /// it reaches its goal.
+    fn change_counter(
+        ctx: &mut Context<'_>,
+        group: usize,
+        counter: usize,
+        delta: impl FnOnce(&Counter) -> i64,
+    ) {
+        let Some(counter) = counter_mut(ctx.config, group, counter) else {
+            return;
+        };
+
+        let reached = counter.add(delta(counter));
+        let name = counter.name.clone();
+        let value = counter.value;
+        ctx.save_config();
+
+        if let Some(group) = ctx.config.counter_groups.get(group) {
+            let event = dbus::Event::CounterChanged {
+                group: group.name.clone(),
+                counter: name.clone(),
+                value,
+            };
+
+            ctx.emit(event);
+        }
+
+        if reached {
+            ctx.toast(fl!("goal-reached", name = name));
+            ctx.play(SoundEvent::GoalReached);
+        }
+    }
+
+    /// The name of a group or counter, editable in place.
+    fn rename_view<'a>(&'a self, target: Renaming, name: &'a str) -> widgets::InlineEdit<'a, Message> {
+        let editing = match &self.renaming {
+            Some((renaming, text)) if *renaming == target => Some(text.as_str()),
+            _ => None,
+        };
+
+        widgets::inline_edit(name, editing)
+            .on_edit(Message::Rename(target))
+            .on_input(Message::RenameInput)
+            .on_commit(Message::CommitRename)
+            .on_cancel(Message::CancelRename)
+    }
+
+    /// Fields for the step and bounds of the counter whose options are shown.
+    fn limits_view(&self) -> Element<'_, Message> {
+        let [step, min, max] = &self.limits;
+
+        widgets::wrapping_row(vec![
+            widget::text::body(fl!("counter-step")).into(),
+            limit_input(fl!("counter-step-placeholder"), step, Limit::Step),
+            widget::text::body(fl!("counter-bounds")).into(),
+            limit_input(fl!("counter-min"), min, Limit::Min),
+            limit_input(fl!("counter-max"), max, Limit::Max),
+        ])
+    }
+
+    /// Choices for when a counter automatically resets.
+    fn reset_options(&self, g: usize, c: usize, reset: Option<ResetSchedule>) -> Element<'_, Message> {
+        let (hour, minute) = reset.map_or((0, 0), |reset| (reset.hour(), reset.minute()));
+        let weekday = match reset {
+            Some(ResetSchedule::Weekly { weekday, .. }) => weekday,
+            _ => 0,
+        };
+
+        let selected = match reset {
+            None => 0,
+            Some(ResetSchedule::Daily { .. }) => 1,
+            Some(ResetSchedule::Weekly { .. }) => 2,
+        };
+
+        let schedule = move |kind: usize, weekday: u32, hour: u32, minute: u32| match kind {
+            1 => Some(ResetSchedule::Daily { hour, minute }),
+            2 => Some(ResetSchedule::Weekly { weekday, hour, minute }),
+            _ => None,
+        };
+
+        let mut row: Vec<Element<'_, Message>> = vec![
+            widget::text::body(fl!("auto-reset")).into(),
+            widget::dropdown(&self.reset_labels, Some(selected), move |kind| {
+                Message::SetCounterReset(g, c, schedule(kind, weekday, hour, minute))
+            })
+            .into(),
+        ];
+
+        if selected == 2 {
+            row.push(
+                widget::dropdown(&self.weekday_labels, Some(weekday as usize), move |weekday| {
+                    Message::SetCounterReset(g, c, schedule(selected, weekday as u32, hour, minute))
+                })
+                .into(),
+            );
+        }
+
+        if selected != 0 {
+            row.push(
+                widget::dropdown(&HOURS[..], Some(hour as usize), move |hour| {
+                    Message::SetCounterReset(g, c, schedule(selected, weekday, hour as u32, minute))
+                })
+                .into(),
+            );
+            row.push(
+                widget::dropdown(
+                    &MINUTES[..],
+                    MINUTES.iter().position(|m| m.parse() == Ok(minute)),
+                    move |idx| {
+                        let minute = MINUTES[idx].parse().unwrap_or(0);
+                        Message::SetCounterReset(g, c, schedule(selected, weekday, hour, minute))
+                    },
+                )
+                .into(),
+            );
+        }
+
+        widgets::wrapping_row(row)
+    }
+}
+
+impl Page for CountersPage {
+    type Message = Message;
+
+    fn relabel(&mut self) {
+        self.reset_labels = reset_labels();
+        self.weekday_labels = super::weekday_labels();
+    }
+
+    /// Lists every counter group with its counters, subtotals, and the grand total.
+    fn view<'a>(&'a self, config: &'a Config, _narrow: bool) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let groups = &config.counter_groups;
+        let mut column = widget::column::with_capacity(groups.len() + 2).spacing(space_s);
+
+        for (g, group) in groups.iter().enumerate() {
+            let title = self
+                .rename_view(Renaming::Group(g), &group.name)
+                .title()
+                .placeholder(fl!("group-name"));
+
+            let group_header = widget::row::with_capacity(3)
+                .push(Element::from(title))
+                .push(
+                    widget::button::icon(icons::handle("list-add-symbolic"))
+                        .on_press(Message::AddCounter(g)),
+                )
+                .push(
+                    widget::button::icon(icons::handle("user-trash-symbolic"))
+                        .on_press(Message::DeleteGroup(g)),
+                )
+                .align_y(Vertical::Center)
+                .spacing(space_s);
+
+            let mut section = cosmic::widget::settings::section();
+
+            for (c, counter) in group.counters.iter().enumerate() {
+                let selected = self.selected == Some((g, c));
+
+                let value: Element<_> = match &self.value_input {
+                    Some(input) if selected => widget::text_input(fl!("counter-value"), input.as_str())
+                        .id(VALUE_INPUT.clone())
+                        .on_input(Message::ValueInput)
+                        .on_submit(|_| Message::CommitValue)
+                        .width(Length::Fixed(LIMIT_INPUT_WIDTH))
+                        .into(),
+                    _ => widget::button::custom(counter_value(counter))
+                        .class(if selected {
+                            cosmic::theme::Button::Suggested
+                        } else {
+                            cosmic::theme::Button::Text
+                        })
+                        .on_press(Message::SelectCounter(g, c))
+                        .into(),
+                };
+
+                let controls = widget::row::with_capacity(4)
+                    .push(widget::button::text("-").on_press(Message::Decrement(g, c)))
+                    .push(value)
+                    .push(widget::button::text("+").on_press(Message::Increment(g, c)))
+                    .push(
+                        widget::button::icon(icons::handle("starred-symbolic"))
+                            .on_press(Message::EditGoal(g, c)),
+                    )
+                    .push(
+                        widget::button::icon(icons::handle("preferences-system-symbolic"))
+                            .on_press(Message::ToggleCounterFormat(g, c)),
+                    )
+                    .push(
+                        widget::button::icon(icons::handle("edit-delete-symbolic"))
+                            .on_press(Message::RemoveCounter(g, c)),
+                    )
+                    .align_y(Vertical::Center)
+                    .spacing(space_s);
+
+                let mut row = widget::column::with_capacity(3)
+                    .push(
+                        widget::row::with_capacity(2)
+                            .push(Element::from(
+                                self.rename_view(Renaming::Counter(g, c), &counter.name)
+                                    .placeholder(fl!("counter-name")),
+                            ))
+                            .push(controls)
+                            .align_y(Vertical::Center)
+                            .spacing(space_s),
+                    )
+                    .spacing(cosmic::theme::spacing().space_xxs);
+
+                let invalid = self
+                    .value_input
+                    .as_deref()
+                    .filter(|input| selected && !input.trim().is_empty())
+                    .is_some_and(|input| typed_value(counter, input).is_none());
+
+                if invalid {
+                    row = row.push(widget::text::caption(fl!("counter-value-invalid")));
+                }
+
+                if selected && self.value_input.is_some() && config.show_numpad {
+                    row = row.push(widgets::numpad(Message::Numpad));
+                }
+
+                match &self.editing_goal {
+                    Some((editing, goal)) if *editing == (g, c) => {
+                        row = row.push(
+                            widget::text_input(fl!("goal"), goal.as_str())
+                                .on_input(Message::GoalInput)
+                                .on_submit(|_| Message::CommitGoal),
+                        );
+                    }
+                    _ => {
+                        if let (Some(goal), Some(progress)) = (counter.goal, counter.progress()) {
+                            row = row
+                                .push(widget::progress_bar(0.0..=1.0, progress))
+                                .push(widget::text::caption(fl!("goal-progress", value = counter.value, goal = goal)));
+                        }
+                    }
+                }
+
+                if self.formatting_counter == Some((g, c)) {
+                    let format = counter.format;
+
+                    row = row.push(widgets::wrapping_row(vec![
+                        widget::checkbox(fl!("always-sign"), format.always_sign)
+                            .on_toggle(move |always_sign| {
+                                Message::SetCounterFormat(g, c, NumberFormat { always_sign, ..format })
+                            })
+                            .into(),
+                        widget::checkbox(fl!("red-negative"), format.red_negative)
+                            .on_toggle(move |red_negative| {
+                                Message::SetCounterFormat(g, c, NumberFormat { red_negative, ..format })
+                            })
+                            .into(),
+                        widget::checkbox(fl!("thousands-separators"), format.thousands_separators)
+                            .on_toggle(move |thousands_separators| {
+                                Message::SetCounterFormat(
+                                    g,
+                                    c,
+                                    NumberFormat {
+                                        thousands_separators,
+                                        ..format
+                                    },
+                                )
+                            })
+                            .into(),
+                    ]));
+                }
+
+                if self.formatting_counter == Some((g, c)) {
+                    row = row
+                        .push(self.limits_view())
+                        .push(self.reset_options(g, c, counter.reset))
+                        .push(history_view(g, c, counter, &self.history));
+                }
+
+                section = section.add(row);
+            }
+
+            section = section.add(
+                cosmic::widget::settings::item::builder(fl!("subtotal"))
+                    .control(widget::text::title4(group.subtotal().to_string())),
+            );
+
+            column = column.push(group_header).push(section);
+        }
+
+        if groups.len() > 1 {
+            column = column.push(
+                cosmic::widget::settings::section().add(
+                    cosmic::widget::settings::item::builder(fl!("grand-total"))
+                        .control(widget::text::title3(counters::grand_total(groups).to_string())),
+                ),
+            );
+        }
+
+        let counters = column
+            .push(widget::button::standard(fl!("add-group")).on_press(Message::AddGroup))
+            .apply(widget::scrollable);
+
+        widget::column::with_capacity(2)
+            .push(super::header(fl!("welcome"), 2))
+            .push(counters)
+            .spacing(space_s)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn update(&mut self, message: Message, ctx: &mut Context<'_>) -> Task<Message> {
+        // Scheduled resets are not the user's doing, so are not offered for undo.
+        let before = (!matches!(message, Message::CheckCounterResets)).then(|| ctx.config.counter_groups.clone());
+        // Every way a value changes is recorded here, rather than by each message.
+        let values = counter_values(&ctx.config.counter_groups);
+        let reset = matches!(message, Message::CheckCounterResets | Message::ResetAll);
+
+        match message {
+            Message::Increment(group, counter) => {
+                Self::change_counter(ctx, group, counter, Counter::step);
+            }
+            Message::Decrement(group, counter) => {
+                Self::change_counter(ctx, group, counter, |counter| -counter.step());
+            }
+            Message::EditGoal(group, counter) => {
+                if let Some(goal) = counter_mut(ctx.config, group, counter).map(|c| c.goal) {
+                    let goal = goal.map(|goal| goal.to_string()).unwrap_or_default();
+                    self.editing_goal = Some(((group, counter), goal));
+                }
+            }
+            Message::ToggleCounterFormat(group, counter) => {
+                self.formatting_counter = match self.formatting_counter {
+                    Some(shown) if shown == (group, counter) => None,
+                    _ => Some((group, counter)),
+                };
+
+                if let Some(counter) = counter_mut(ctx.config, group, counter) {
+                    let bound = |bound: Option<i64>| bound.map(|bound| bound.to_string()).unwrap_or_default();
+                    let step = if counter.step > 1 { counter.step.to_string() } else { String::new() };
+                    self.limits = [step, bound(counter.min), bound(counter.max)];
+                }
+            }
+            Message::SelectCounter(group, counter) => {
+                self.value_input = None;
+                self.selected = (self.selected != Some((group, counter))).then_some((group, counter));
+            }
+            Message::Deselect => {
+                self.selected = None;
+                self.value_input = None;
+            }
+            Message::StepSelected(steps) => {
+                if let Some((group, counter)) = self.selected {
+                    Self::change_counter(ctx, group, counter, |counter| steps.saturating_mul(counter.step()));
+
+                    // An open field follows the value, so typing starts from it.
+                    if self.value_input.is_some() {
+                        self.value_input = counter_mut(ctx.config, group, counter).map(|c| c.value.to_string());
+                    }
+                }
+            }
+            Message::EditValue(typed) => {
+                let Some((group, counter)) = self.selected else {
+                    return Task::none();
+                };
+
+                let value = counter_mut(ctx.config, group, counter).map(|counter| counter.value.to_string());
+                self.value_input = typed.or(value);
+                return widget::text_input::focus(VALUE_INPUT.clone());
+            }
+            Message::ValueInput(input) => {
+                if self.value_input.is_some() {
+                    self.value_input = Some(input);
+                }
+            }
+            Message::Numpad(NumpadKey::Digit(digit)) => {
+                if let Some(input) = &mut self.value_input {
+                    input.push(char::from(b'0' + digit));
+                }
+            }
+            Message::Numpad(NumpadKey::Backspace) => {
+                if let Some(input) = &mut self.value_input {
+                    input.pop();
+                }
+            }
+            Message::CommitValue | Message::Numpad(NumpadKey::Enter) => {
+                let (Some((group, counter)), Some(input)) = (self.selected, &self.value_input) else {
+                    return Task::none();
+                };
+
+                let Some(value) = counter_mut(ctx.config, group, counter).and_then(|c| typed_value(c, input)) else {
+                    return Task::none();
+                };
+
+                self.value_input = None;
+                Self::change_counter(ctx, group, counter, |counter| value.saturating_sub(counter.value));
+            }
+            Message::ExportHistory(group, counter) => {
+                let Some(counter) = counter_mut(ctx.config, group, counter) else {
+                    return Task::none();
+                };
+
+                let file_name = format!("{}-history.csv", counter.name);
+                let csv = self.history.csv(counter.id);
+
+                return Task::future(io::choose_save(fl!("export-history"), file_name)).then(move |chosen| {
+                    let path = match chosen {
+                        Ok(Some(path)) => path,
+                        Ok(None) => return Task::none(),
+                        Err(why) => return Task::done(Message::HistoryExported(Some(Err(why)))),
+                    };
+
+                    io::task(io::write(path.clone(), csv.clone()), Message::Transferred, move |result| {
+                        Message::HistoryExported(result.map(|result| result.map(|()| path.clone())))
+                    })
+                });
+            }
+            Message::HistoryExported(result) => match result {
+                Some(Ok(path)) => ctx.toast(fl!("saved-to", path = path.display().to_string())),
+                Some(Err(why)) => ctx.fail(fl!("history-export-failed", reason = why.to_string()), why),
+                None => {}
+            },
+            Message::Transferred(progress) => ctx.transferred(progress),
+            Message::SaveHistory => {
+                if std::mem::take(&mut self.history_unsaved) {
+                    let history = self.history.clone();
+
+                    return Task::future(async move {
+                        _ = tokio::task::spawn_blocking(move || history.save()).await;
+                    })
+                    .discard();
+                }
+            }
+            Message::LimitInput(limit, input) => {
+                let idx = match limit {
+                    Limit::Step => 0,
+                    Limit::Min => 1,
+                    Limit::Max => 2,
+                };
+
+                self.limits[idx] = input;
+            }
+            Message::CommitLimits => {
+                let Some((group, counter)) = self.formatting_counter else {
+                    return Task::none();
+                };
+
+                // Empty fields step by one and leave the value unbounded; anything
+                // unparsable, or bounds out of order, is discarded.
+                let bound = |text: &str| match text.trim() {
+                    "" => Ok(None),
+                    text => text.parse().map(Some),
+                };
+
+                let [step, min, max] = &self.limits;
+
+                let step = match step.trim() {
+                    "" => Ok(0),
+                    step => step.parse::<u32>(),
+                };
+
+                let (Ok(step), Ok(min), Ok(max)) = (step, bound(min), bound(max)) else {
+                    return Task::none();
+                };
+
+                if min.zip(max).is_some_and(|(min, max)| min > max) {
+                    return Task::none();
+                }
+
+                if let Some(counter) = counter_mut(ctx.config, group, counter) {
+                    counter.step = step;
+                    counter.min = min;
+                    counter.max = max;
+                    counter.value = counter.clamp(counter.value);
+                    ctx.save_config();
+                }
+            }
+            Message::SetCounterFormat(group, counter, format) => {
+                if let Some(counter) = counter_mut(ctx.config, group, counter) {
+                    counter.format = format;
+                    ctx.save_config();
+                }
+            }
+            Message::SetCounterReset(group, counter, reset) => {
+                if let Some(counter) = counter_mut(ctx.config, group, counter) {
+                    counter.set_reset(reset);
+                    ctx.save_config();
+                }
+            }
+            Message::CheckCounterResets => {
+                let now = Local::now();
+                let mut changed = Vec::new();
+
+                for group in &mut ctx.config.counter_groups {
+                    for counter in &mut group.counters {
+                        if counter.reset_if_due(now) {
+                            changed.push(dbus::Event::CounterChanged {
+                                group: group.name.clone(),
+                                counter: counter.name.clone(),
+                                value: counter.value,
+                            });
+                        }
+                    }
+                }
+
+                if !changed.is_empty() {
+                    ctx.save_config();
+                    changed.into_iter().for_each(|event| ctx.emit(event));
+                }
+            }
+            Message::ResetAll => {
+                let now = Local::now().timestamp();
+                let mut changed = Vec::new();
+
+                for group in &mut ctx.config.counter_groups {
+                    for counter in group.counters.iter_mut().filter(|counter| counter.value != counter.clamp(0)) {
+                        counter.reset();
+                        changed.push(dbus::Event::CounterChanged {
+                            group: group.name.clone(),
+                            counter: counter.name.clone(),
+                            value: counter.value,
+                        });
+                    }
+                }
+
+                if !changed.is_empty() {
+                    self.value_input = None;
+                    ctx.save_config();
+                    changed.into_iter().for_each(|event| ctx.emit(event));
+                }
+            }
+            Message::GoalInput(input) => {
+                if let Some((_, goal)) = &mut self.editing_goal {
+                    *goal = input;
+                }
+            }
+            Message::CommitGoal => {
+                if let Some(((group, counter), goal)) = self.editing_goal.take() {
+                    // An empty field clears the goal; anything unparsable is discarded.
+                    let goal = match goal.trim() {
+                        "" => None,
+                        goal => match goal.parse() {
+                            Ok(goal) => Some(goal),
+                            Err(_) => return Task::none(),
+                        },
+                    };
+
+                    if let Some(counter) = counter_mut(ctx.config, group, counter) {
+                        counter.goal = goal;
+                        ctx.save_config();
+                    }
+                }
+            }
+            Message::AddCounter(group) => {
+                let total = counters::count(&ctx.config.counter_groups);
+
+                if let Some(group) = ctx.config.counter_groups.get_mut(group) {
+                    group.counters.push(Counter::new(fl!("counter-id", num = total + 1)));
+                    ctx.save_config();
+                }
+            }
+            Message::RemoveCounter(g, c) => {
+                if let Some(group) = ctx.config.counter_groups.get_mut(g) {
+                    if c < group.counters.len() {
+                        let counter = group.counters.remove(c);
+                        self.renaming = None;
+                        self.selected = None;
+                        self.value_input = None;
+                        // Written to disk only once the removal can no longer be undone.
+                        ctx.removed(Removed::Counter {
+                            group: group.id,
+                            index: c,
+                            counter,
+                        });
+                    }
+                }
+            }
+            Message::AddGroup => {
+                let num = ctx.config.counter_groups.len() + 1;
+                ctx.config.counter_groups.push(counters::Group::new(fl!("group-id", num = num)));
+                ctx.save_config();
+            }
+            Message::Rename(target) => {
+                let name = match target {
+                    Renaming::Group(g) => ctx.config.counter_groups.get(g).map(|group| &group.name),
+                    Renaming::Counter(g, c) => counter_mut(ctx.config, g, c).map(|counter| &counter.name),
+                };
+
+                self.renaming = name.map(|name| (target, name.clone()));
+            }
+            Message::RenameInput(input) => {
+                if let Some((_, name)) = &mut self.renaming {
+                    *name = input;
+                }
+            }
+            Message::CommitRename => {
+                if let Some((target, name)) = self.renaming.take() {
+                    let name = name.trim();
+
+                    let current = match target {
+                        Renaming::Group(g) => {
+                            ctx.config.counter_groups.get_mut(g).map(|group| &mut group.name)
+                        }
+                        Renaming::Counter(g, c) => {
+                            counter_mut(ctx.config, g, c).map(|counter| &mut counter.name)
+                        }
+                    };
+
+                    if let Some(current) = current.filter(|_| !name.is_empty()) {
+                        *current = name.to_owned();
+                        ctx.save_config();
+                    }
+                }
+            }
+            Message::CancelRename => {
+                self.renaming = None;
+            }
+            Message::DeleteGroup(group) => {
+                if group < ctx.config.counter_groups.len() {
+                    let removed = ctx.config.counter_groups.remove(group);
+                    self.renaming = None;
+                    self.selected = None;
+                    self.value_input = None;
+                    ctx.removed(Removed::Group { index: group, group: removed });
+                }
+            }
+        }
+
+        if let Some(before) = before.filter(|before| *before != ctx.config.counter_groups) {
+            ctx.changed(Change::Counters(before));
+        }
+
+        self.record(&values, &ctx.config.counter_groups, reset);
+        Task::none()
+    }
+
+    fn revert(&mut self, change: Change, ctx: &mut Context<'_>) -> Option<Change> {
+        let Change::Counters(groups) = change else {
+            return None;
+        };
+
+        // Positions kept for editing may not exist in the other groups.
+        self.renaming = None;
+        self.formatting_counter = None;
+        self.editing_goal = None;
+        self.selected = None;
+        self.value_input = None;
+
+        let values = counter_values(&ctx.config.counter_groups);
+        let replaced = std::mem::replace(&mut ctx.config.counter_groups, groups);
+        self.record(&values, &ctx.config.counter_groups, false);
+        ctx.save_config();
+        Some(Change::Counters(replaced))
+    }
+
+    fn restore_state(&mut self, _state: &State, config: &Config) {
+        // Removed counters can no longer be brought back once the app closed.
+        let ids: Vec<u64> = config
+            .counter_groups
+            .iter()
+            .flat_map(|group| &group.counters)
+            .map(|counter| counter.id)
+            .collect();
+
+        self.history.retain(|id| ids.contains(&id));
+    }
+
+    fn save_state(&self, _state: &mut State) {
+        // Changes from just before the app closes would otherwise be lost.
+        if self.history_unsaved {
+            self.history.save();
+        }
+    }
+
+    fn subscription(&self, config: &Config) -> Subscription<Message> {
+        // Checks for due counter resets while any counter has a schedule.
+        let has_resets = config
+            .counter_groups
+            .iter()
+            .flat_map(|group| &group.counters)
+            .any(|counter| counter.reset.is_some());
+
+        let resets = if has_resets {
+            Subscription::run(|| {
+                iced_futures::stream::channel(1, |mut emitter| async move {
+                    let mut interval = tokio::time::interval(Duration::from_secs(30));
+
+                    loop {
+                        interval.tick().await;
+                        _ = emitter.send(Message::CheckCounterResets).await;
+                    }
+                })
+            })
+        } else {
+            Subscription::none()
+        };
+
+        // Writes the history once changes to it have had a moment to gather.
+        let history = if self.history_unsaved {
+            Subscription::run(|| {
+                iced_futures::stream::channel(1, |mut emitter| async move {
+                    loop {
+                        tokio::time::sleep(HISTORY_SAVE_DELAY).await;
+                        _ = emitter.send(Message::SaveHistory).await;
+                    }
+                })
+            })
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([resets, history])
+    }
+}
+
+/// Labels for the reset schedule choices: never, daily, then weekly.
+fn reset_labels() -> Vec<String> {
+    vec![fl!("reset-never"), fl!("reset-daily"), fl!("reset-weekly")]
+}
+
+/// The value of every counter in `groups`, by counter ID.
+fn counter_values(groups: &[Group]) -> HashMap<u64, i64> {
+    groups
+        .iter()
+        .flat_map(|group| &group.counters)
+        .map(|counter| (counter.id, counter.value))
+        .collect()
+}
+
+/// A chart of a counter's values over time, with a button to export all of them.
+fn history_view(g: usize, c: usize, counter: &Counter, history: &counters::History) -> Element<'static, Message> {
+    let recent = history.timeline(counter.id, Local::now().timestamp(), SPARKLINE_POINTS);
+
+    let mut column = widget::column::with_capacity(3)
+        .push(widget::text::body(fl!("counter-history")))
+        .spacing(cosmic::theme::spacing().space_xxs);
+
+    column = if recent.is_empty() {
+        column.push(widget::text::caption(fl!("counter-history-empty")))
+    } else {
+        column.push(widgets::sparkline(&recent))
+    };
+
+    column
+        .push(
+            widget::button::standard(fl!("export-history"))
+                .on_press_maybe(
+                    (!history.entries(counter.id).is_empty()).then_some(Message::ExportHistory(g, c)),
+                ),
+        )
+        .into()
+}
+
+/// A field for one of a counter's limits, applied when submitted.
+fn limit_input(placeholder: String, text: &str, limit: Limit) -> Element<'_, Message> {
+    widget::text_input(placeholder, text)
+        .on_input(move |input| Message::LimitInput(limit, input))
+        .on_submit(|_| Message::CommitLimits)
+        .width(Length::Fixed(LIMIT_INPUT_WIDTH))
+        .into()
+}
+
+/// The value typed for `counter`, if it is a whole number within its bounds.
+fn typed_value(counter: &Counter, text: &str) -> Option<i64> {
+    let value = text.trim().parse().ok()?;
+    (counter.clamp(value) == value).then_some(value)
+}
+
+fn counter_mut(config: &mut Config, group: usize, counter: usize) -> Option<&mut Counter> {
+    config
+        .counter_groups
+        .get_mut(group)
+        .and_then(|group| group.counters.get_mut(counter))
+}
+
+/// Draws a counter's value with its display options.
+fn counter_value(counter: &Counter) -> Element<'_, Message> {
+    let text = widget::text::title3(format::number(counter.value, counter.format));
+
+    if counter.format.red_negative && counter.value < 0 {
+        let color = cosmic::theme::active().cosmic().destructive_color();
+        text.class(cosmic::theme::Text::Color(color.into())).into()
+    } else {
+        text.into()
+    }
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The files page: files dropped onto a window, with their size and type.
+//!
+//! Dropped files arrive as window events, one for each file, which the page's
+//! subscription turns into messages. Each file is then looked at away from
+//! the interface, since a slow or network drive could hold it up.
+
+use super::{Context, Page};
+use crate::config::Config;
+use crate::error::{Code, Error};
+use crate::fl;
+use crate::icons;
+use crate::widgets;
+use cosmic::iced::{Event, Length, Subscription, event, window};
+use cosmic::prelude::*;
+use cosmic::widget;
+use std::path::PathBuf;
+
+/// Most dropped files kept in the list.
+const FILE_LIMIT: usize = 100;
+/// Units of file sizes, each 1024 times the one before.
+const SIZE_UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+pub struct FilesPage {
+    /// Dropped files, latest first.
+    files: Vec<DroppedFile>,
+    /// Files being dragged over a window and not yet dropped.
+    hovered: usize,
+}
+
+/// A file dropped onto a window.
+#[derive(Clone, Debug)]
+pub struct DroppedFile {
+    path: PathBuf,
+    /// Size in bytes, or `None` for a directory.
+    size: Option<u64>,
+    mime: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Hovered(PathBuf),
+    Left,
+    Dropped(PathBuf),
+    Inspected(Result<DroppedFile, Error>),
+    Remove(usize),
+    Clear,
+}
+
+impl FilesPage {
+    pub fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            hovered: 0,
+        }
+    }
+
+    fn file_view(&self, idx: usize, file: &DroppedFile) -> Element<'_, Message> {
+        let name = file
+            .path
+            .file_name()
+            .map_or_else(|| file.path.display().to_string(), |name| name.to_string_lossy().into_owned());
+
+        let size = file.size.map_or_else(|| fl!("directory"), format_size);
+        let description = fl!(
+            "dropped-file-details",
+            path = file.path.display().to_string(),
+            size = size,
+            mime = file.mime.as_str()
+        );
+
+        cosmic::widget::settings::item::builder(name)
+            .description(description)
+            .control(widget::button::icon(icons::handle("edit-delete-symbolic")).on_press(Message::Remove(idx)))
+            .into()
+    }
+}
+
+impl Page for FilesPage {
+    type Message = Message;
+
+    fn view<'a>(&'a self, _config: &'a Config, _narrow: bool) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let mut column = widget::column::with_capacity(4).push(super::header(fl!("dropped-files"), 6));
+
+        column = if self.hovered > 0 {
+            column.push(widget::text::title4(fl!("drop-files-now", files = self.hovered)))
+        } else if self.files.is_empty() {
+            column.push(
+                widgets::empty_state(icons::handle("document-open-symbolic"), fl!("no-dropped-files"))
+                    .description(fl!("drop-files-hint")),
+            )
+        } else {
+            column.push(widget::text::body(fl!("drop-files-hint")))
+        };
+
+        if !self.files.is_empty() {
+            let files = self
+                .files
+                .iter()
+                .enumerate()
+                .fold(cosmic::widget::settings::section(), |section, (idx, file)| {
+                    section.add(self.file_view(idx, file))
+                });
+
+            column = column
+                .push(files)
+                .push(widget::button::standard(fl!("clear-dropped-files")).on_press(Message::Clear));
+        }
+
+        column
+            .spacing(space_s)
+            .width(Length::Fill)
+            .apply(widget::scrollable)
+            .into()
+    }
+
+    fn update(&mut self, message: Message, ctx: &mut Context<'_>) -> Task<Message> {
+        match message {
+            Message::Hovered(_) => {
+                self.hovered += 1;
+            }
+            Message::Left => {
+                self.hovered = 0;
+            }
+            Message::Dropped(path) => {
+                // Every file of a drop is dropped together, so none is left hovering.
+                self.hovered = 0;
+
+                return Task::future(async move {
+                    let result = tokio::task::spawn_blocking(move || inspect(path))
+                        .await
+                        .unwrap_or_else(|why| Err(Error::new(Code::Io, why.to_string())));
+
+                    Message::Inspected(result)
+                });
+            }
+            Message::Inspected(Ok(file)) => {
+                // A file dropped again moves to the top.
+                self.files.retain(|dropped| dropped.path != file.path);
+                self.files.insert(0, file);
+                self.files.truncate(FILE_LIMIT);
+            }
+            Message::Inspected(Err(why)) => {
+                ctx.fail(fl!("dropped-file-failed", reason = why.to_string()), why);
+            }
+            Message::Remove(idx) => {
+                if idx < self.files.len() {
+                    self.files.remove(idx);
+                }
+            }
+            Message::Clear => {
+                self.files.clear();
+            }
+        }
+
+        Task::none()
+    }
+
+    fn subscription(&self, _config: &Config) -> Subscription<Message> {
+        event::listen_with(|event, _status, _window_id| match event {
+            Event::Window(window::Event::FileHovered(path)) => Some(Message::Hovered(path)),
+            Event::Window(window::Event::FilesHoveredLeft) => Some(Message::Left),
+            Event::Window(window::Event::FileDropped(path)) => Some(Message::Dropped(path)),
+            _ => None,
+        })
+    }
+}
+
+/// Looks up the size and type of a dropped file, the type being guessed from
+/// its extension.
+fn inspect(path: PathBuf) -> Result<DroppedFile, Error> {
+    let metadata = std::fs::metadata(&path)?;
+
+    let (size, mime) = if metadata.is_dir() {
+        (None, "inode/directory".to_owned())
+    } else {
+        let mime = mime_guess::from_path(&path).first_or_octet_stream();
+        (Some(metadata.len()), mime.essence_str().to_owned())
+    };
+
+    Ok(DroppedFile { path, size, mime })
+}
+
+/// Formats `bytes` in the largest unit it fills at least one of.
+fn format_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit = 0;
+
+    while size >= 1024.0 && unit < SIZE_UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} {}", SIZE_UNITS[0])
+    } else {
+        format!("{size:.1} {}", SIZE_UNITS[unit])
+    }
+}
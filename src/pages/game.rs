@@ -0,0 +1,898 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The guessing game page: free play, campaign levels, the daily challenge, and
+//! the timed challenge.
+
+use super::{Change, Context, Page};
+use crate::animation::{self, Animation};
+use crate::audio::SoundEvent;
+use crate::config::Config;
+use crate::daily;
+use crate::error::{Code, Error};
+use crate::fl;
+use crate::contrast;
+use crate::game::{self, Difficulty, Outcome, Proximity};
+use crate::leaderboard;
+use crate::state::State;
+use crate::usage;
+use crate::widgets::{self, NumpadKey};
+use chrono::{Datelike, Local};
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::{Border, Length, Subscription, window};
+use cosmic::prelude::*;
+use cosmic::{iced_futures, widget};
+use futures_util::SinkExt;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Width of the custom range's bound fields, enough for several digits.
+const RANGE_INPUT_WIDTH: f32 = 96.0;
+
+static PLAYER_NAME_INPUT: LazyLock<widget::Id> = LazyLock::new(|| widget::Id::new("player-name"));
+
+pub struct GamePage {
+    /// The current round of the guessing game.
+    game: game::Engine,
+    /// The kind of round being played.
+    game_mode: game::Mode,
+    /// Days on which the daily challenge was solved.
+    daily: daily::Completions,
+    /// Whether the campaign level selection is shown instead of the game.
+    level_select: bool,
+    number: String,
+    feedback: String,
+    /// Shakes the guess field after an invalid guess.
+    guess_shake: Option<Animation>,
+    /// Labels for the difficulty choices: each of `Difficulty::PRESETS`, then custom.
+    difficulty_labels: Vec<String>,
+    /// Bounds of a custom range being typed, before it is applied.
+    custom_range: (String, String),
+    /// When the timed challenge being played started.
+    challenge_started: Option<Instant>,
+    /// Time the finished timed challenge took.
+    challenge_used: Option<Duration>,
+    /// Whether the finished timed challenge's summary dialog is open.
+    show_summary: bool,
+    /// Results on the machine's shared leaderboard, best first.
+    leaderboard: Vec<leaderboard::Entry>,
+    /// A win that made the leaderboard, with the name being typed for it.
+    record: Option<leaderboard::Entry>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Input(String),
+    Clear,
+    Check,
+    Numpad(NumpadKey),
+    ShowNumpad(bool),
+    NewGame,
+    ShowLevelSelect,
+    StartLevel(u32),
+    StartFreePlay,
+    StartDaily,
+    StartChallenge,
+    ChallengeTick,
+    CloseSummary,
+    SelectDifficulty(usize),
+    CustomLowInput(String),
+    CustomHighInput(String),
+    ApplyCustomRange,
+    ClearRecords,
+    LeaderboardLoaded(Result<Vec<leaderboard::Entry>, Error>),
+    PlayerName(String),
+    SaveRecord,
+    SkipRecord,
+    RecordSaved(Result<Vec<leaderboard::Entry>, Error>),
+    AnimationFrame,
+}
+
+impl GamePage {
+    pub fn new(config: &Config) -> Self {
+        let range = config.game_difficulty.range();
+
+        Self {
+            game: game::Engine::new(range.clone()),
+            game_mode: game::Mode::Free,
+            daily: daily::Completions::load(),
+            level_select: false,
+            number: String::new(),
+            guess_shake: None,
+            feedback: fl!("free-play-start", low = *range.start(), high = *range.end()),
+            difficulty_labels: difficulty_labels(),
+            custom_range: (range.start().to_string(), range.end().to_string()),
+            challenge_started: None,
+            challenge_used: None,
+            show_summary: false,
+            // Filled in once `load_leaderboard` has read it.
+            leaderboard: Vec::new(),
+            record: None,
+        }
+    }
+
+    /// Reads the leaderboard on a blocking thread, for the page to show once it arrives.
+    pub fn load_leaderboard() -> Task<Message> {
+        blocking(leaderboard::load, Message::LeaderboardLoaded)
+    }
+
+    /// Time left in the timed challenge, which stops counting once the round is over.
+    fn challenge_left(&self) -> Duration {
+        let used = self
+            .challenge_used
+            .or_else(|| self.challenge_started.map(|started| started.elapsed()))
+            .unwrap_or_default();
+
+        game::CHALLENGE_TIME.saturating_sub(used)
+    }
+
+    /// Ends the timed challenge once it is won or its time has run out.
+    fn finish_challenge(&mut self, ctx: &mut Context<'_>) {
+        let Some(started) = self.challenge_started else {
+            return;
+        };
+
+        if self.game_mode != game::Mode::Timed || self.challenge_used.is_some() {
+            return;
+        }
+
+        let used = started.elapsed();
+
+        // Winning guesses count the round as played when they are checked.
+        if used >= game::CHALLENGE_TIME && !self.game.is_won() {
+            self.game.expire();
+            self.feedback = fl!("challenge-time-up", secret = self.game.secret());
+            ctx.record(usage::Event::GamePlayed);
+        }
+
+        if self.game.is_won() || self.game.is_lost() {
+            self.challenge_used = Some(used.min(game::CHALLENGE_TIME));
+            self.show_summary = true;
+        }
+    }
+
+    /// The difficulty dropdown, with the custom range inputs when it is chosen.
+    fn difficulty_view(&self, config: &Config) -> Element<'_, Message> {
+        let selected = Difficulty::PRESETS
+            .iter()
+            .position(|&difficulty| difficulty == config.game_difficulty)
+            .unwrap_or(Difficulty::PRESETS.len());
+
+        let mut row: Vec<Element<'_, Message>> = vec![
+            widget::text::body(fl!("difficulty")).into(),
+            widget::dropdown(&self.difficulty_labels, Some(selected), Message::SelectDifficulty).into(),
+        ];
+
+        if let Difficulty::Custom { .. } = config.game_difficulty {
+            row.push(
+                widget::text_input(fl!("range-from"), self.custom_range.0.as_str())
+                    .on_input(Message::CustomLowInput)
+                    .on_submit(|_| Message::ApplyCustomRange)
+                    .width(Length::Fixed(RANGE_INPUT_WIDTH))
+                    .into(),
+            );
+            row.push(
+                widget::text_input(fl!("range-to"), self.custom_range.1.as_str())
+                    .on_input(Message::CustomHighInput)
+                    .on_submit(|_| Message::ApplyCustomRange)
+                    .width(Length::Fixed(RANGE_INPUT_WIDTH))
+                    .into(),
+            );
+            row.push(widget::button::standard(fl!("apply")).on_press(Message::ApplyCustomRange).into());
+        }
+
+        widgets::wrapping_row(row)
+    }
+
+    /// The best results at `difficulty` on the shared leaderboard.
+    fn leaderboard_view(&self, difficulty: Difficulty) -> Element<'_, Message> {
+        let mut column = widget::column::with_capacity(leaderboard::TOP + 1)
+            .push(widget::text::title4(fl!("leaderboard")))
+            .spacing(cosmic::theme::spacing().space_xxs);
+
+        let mut placed = leaderboard::top(&self.leaderboard, difficulty).peekable();
+
+        if placed.peek().is_none() {
+            return column.push(widget::text::body(fl!("leaderboard-empty"))).into();
+        }
+
+        for (rank, entry) in placed.enumerate() {
+            column = column.push(widget::text::body(fl!(
+                "leaderboard-entry",
+                rank = rank + 1,
+                name = entry.name.as_str(),
+                attempts = entry.attempts,
+                date = entry.date.to_string()
+            )));
+        }
+
+        column.into()
+    }
+
+    /// Asks for the name to put on the leaderboard next to a new record.
+    fn record_dialog<'a>(&'a self, entry: &'a leaderboard::Entry) -> Element<'a, Message> {
+        let name = widget::text_input(fl!("player-name"), entry.name.as_str())
+            .id(PLAYER_NAME_INPUT.clone())
+            .on_input(Message::PlayerName)
+            .on_submit(|_| Message::SaveRecord);
+
+        widget::dialog()
+            .title(fl!("leaderboard-record"))
+            .body(fl!("leaderboard-record-body", attempts = entry.attempts))
+            .control(name)
+            .primary_action(widget::button::suggested(fl!("leaderboard-save")).on_press(Message::SaveRecord))
+            .secondary_action(widget::button::standard(fl!("leaderboard-skip")).on_press(Message::SkipRecord))
+            .into()
+    }
+
+    /// Guesses made in the current round.
+    pub fn attempts(&self) -> u32 {
+        self.game.attempts()
+    }
+
+    /// The guesses made this round, each colored by how close it came.
+    ///
+    /// With `explicit` set, each chip also shows how close it came as a row of
+    /// filled dots, and an arrow toward the secret number.
+    fn guesses_view(&self, explicit: bool) -> Element<'_, Message> {
+        let space_xxs = cosmic::theme::spacing().space_xxs;
+
+        let chips = self.game.guesses().iter().map(|&guess| {
+            let proximity = self.game.proximity(guess);
+
+            let label = match proximity {
+                Proximity::Correct => fl!("guess-correct", guess = guess),
+                Proximity::Hot => fl!("guess-hot", guess = guess),
+                Proximity::Warm => fl!("guess-warm", guess = guess),
+                Proximity::Cold => fl!("guess-cold", guess = guess),
+            };
+
+            let label = if explicit && proximity != Proximity::Correct {
+                let dots = match proximity {
+                    Proximity::Hot => "●●●",
+                    Proximity::Warm => "●●○",
+                    _ => "●○○",
+                };
+
+                let arrow = match self.game.outcome(guess) {
+                    Outcome::TooLow => '↑',
+                    _ => '↓',
+                };
+
+                format!("{label} {dots} {arrow}")
+            } else {
+                label
+            };
+
+            widget::container(widget::text::body(label))
+                .padding([space_xxs, cosmic::theme::spacing().space_xs])
+                .class(cosmic::theme::Container::custom(move |theme| {
+                    let cosmic = theme.cosmic();
+
+                    let color = match proximity {
+                        Proximity::Correct => cosmic.success_color(),
+                        Proximity::Hot => cosmic.destructive_color(),
+                        Proximity::Warm => cosmic.warning_color(),
+                        Proximity::Cold => cosmic.palette.accent_blue,
+                    };
+
+                    // Outline each chip so it stays distinct from its neighbours in high contrast.
+                    let width = if contrast::is_high_contrast(theme) { 1.0 } else { 0.0 };
+
+                    widget::container::Style {
+                        background: Some(cosmic::iced::Color::from(color).into()),
+                        text_color: Some(cosmic.on_accent_color().into()),
+                        border: Border {
+                            color: cosmic.background.on.into(),
+                            width,
+                            radius: cosmic.corner_radii.radius_s.into(),
+                        },
+                        ..Default::default()
+                    }
+                }))
+                .into()
+        });
+
+        widget::flex_row(chips.collect())
+            .row_spacing(space_xxs)
+            .column_spacing(space_xxs)
+            .into()
+    }
+
+    /// The campaign levels, free play, and the daily challenge calendar.
+    fn level_select_view(&self, config: &Config) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let unlocked = config.campaign_completed + 1;
+
+        let levels = widget::flex_row(
+            (1..=game::CAMPAIGN_LEVELS)
+                .map(|level| {
+                    widget::button::standard(fl!("level-id", num = level))
+                        .on_press_maybe((level <= unlocked).then_some(Message::StartLevel(level)))
+                        .into()
+                })
+                .collect(),
+        )
+        .row_spacing(space_s)
+        .column_spacing(space_s);
+
+        let modes = widgets::wrapping_row(vec![
+            widget::button::text(fl!("free-play")).on_press(Message::StartFreePlay).into(),
+            widget::button::text(fl!("timed-challenge")).on_press(Message::StartChallenge).into(),
+            // Today's number is known once solved, so the challenge is played once a day.
+            widget::button::suggested(fl!("daily-challenge"))
+                .on_press_maybe(self.daily.get(Local::now().date_naive()).is_none().then_some(Message::StartDaily))
+                .into(),
+        ]);
+
+        widget::column::with_capacity(5)
+            .push(super::header(fl!("campaign"), 4))
+            .push(levels)
+            .push(modes)
+            .push(widget::text::title4(fl!("daily-calendar")))
+            .push(self.daily_calendar())
+            .spacing(space_s)
+            .height(Length::Fill)
+            .into()
+    }
+
+    /// Shows the days of the current month, marking solved daily challenges.
+    fn daily_calendar(&self) -> Element<'_, Message> {
+        let today = Local::now().date_naive();
+        let first = today.with_day(1).unwrap_or(today);
+        let offset = first.weekday().num_days_from_monday() as usize;
+
+        let cell = |label: String| {
+            widget::container(widget::text::body(label))
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+        };
+
+        let days: Vec<_> = first
+            .iter_days()
+            .take_while(|date| date.month() == first.month())
+            .collect();
+
+        let mut cells = std::iter::repeat_with(|| cell(String::new()))
+            .take(offset)
+            .chain(days.into_iter().map(|date| {
+                cell(match self.daily.get(date) {
+                    Some(attempts) => fl!("daily-solved", day = date.day(), attempts = attempts),
+                    None => date.day().to_string(),
+                })
+            }))
+            .peekable();
+
+        let mut calendar = widget::column::with_capacity(6).spacing(cosmic::theme::spacing().space_xxs);
+
+        while cells.peek().is_some() {
+            let week = cells.by_ref().take(7).fold(widget::row::with_capacity(7), |row, cell| row.push(cell));
+            calendar = calendar.push(week);
+        }
+
+        calendar.into()
+    }
+}
+
+impl Page for GamePage {
+    type Message = Message;
+
+    fn relabel(&mut self) {
+        self.difficulty_labels = difficulty_labels();
+    }
+
+    fn restore_state(&mut self, state: &State, _config: &Config) {
+        if let Some((mode, engine)) = &state.game.round {
+            self.game_mode = *mode;
+            self.game = engine.clone();
+            self.feedback = fl!("round-resumed");
+        }
+
+        self.number.clone_from(&state.game.number);
+    }
+
+    fn revert(&mut self, change: Change, _ctx: &mut Context<'_>) -> Option<Change> {
+        let Change::Round { mode, engine } = change else {
+            return None;
+        };
+
+        // As when starting a new round, a timed challenge is not kept to come back to.
+        let replaced = (self.game_mode != game::Mode::Timed).then(|| Change::Round {
+            mode: self.game_mode,
+            engine: self.game.clone(),
+        });
+
+        self.game_mode = mode;
+        self.game = engine;
+        self.show_summary = false;
+        self.level_select = false;
+        self.challenge_started = None;
+        self.challenge_used = None;
+        self.number.clear();
+        self.feedback = fl!("round-resumed");
+        replaced
+    }
+
+    /// A timed challenge is not kept, since its clock cannot stop while the app is closed.
+    fn save_state(&self, state: &mut State) {
+        let over = self.game.is_won() || self.game.is_lost();
+
+        if self.game_mode != game::Mode::Timed && !over {
+            state.game.round = Some((self.game_mode, self.game.clone()));
+        }
+
+        state.game.number.clone_from(&self.number);
+    }
+
+    fn view<'a>(&'a self, config: &'a Config, narrow: bool) -> Element<'a, Message> {
+        if self.level_select {
+            return self.level_select_view(config);
+        }
+
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let number_text_input = widget::text_input("Enter your number", self.number.clone())
+            .on_input(Message::Input)
+            .on_clear(Message::Clear);
+
+        let check_button = widget::button::text("Check the number").on_press(Message::Check);
+
+        let row_number = widgets::adaptive_row(narrow, vec![
+            animation::shaking(number_text_input, self.guess_shake.as_ref()),
+            check_button.into(),
+        ]);
+
+        let feedback_text = widget::text::title3(self.feedback.clone());
+        let attempts_text = widget::text::title3(match self.game.attempts_left() {
+            Some(left) => fl!("attempts-left", attempts = left),
+            None => format!("Number of attempts: {}", self.game.attempts()),
+        });
+        let new_game_button = widget::button::text("Start a new game").on_press(Message::NewGame);
+
+        let mut buttons: Vec<Element<'_, Message>> = vec![
+            new_game_button.into(),
+            widget::button::text(fl!("levels")).on_press(Message::ShowLevelSelect).into(),
+        ];
+
+        if let game::Mode::Campaign(level) = self.game_mode {
+            if self.game.is_won() && level < game::CAMPAIGN_LEVELS {
+                buttons.push(
+                    widget::button::suggested(fl!("next-level"))
+                        .on_press(Message::StartLevel(level + 1))
+                        .into(),
+                );
+            }
+        }
+
+        let mut column = widget::column::with_capacity(10).push(super::header(fl!("welcome"), 4));
+
+        match self.game_mode {
+            game::Mode::Free => {}
+            game::Mode::Campaign(level) => {
+                column = column.push(widget::text::title4(fl!("level-id", num = level)));
+            }
+            game::Mode::Daily(date) => {
+                column = column.push(widget::text::title4(fl!("daily-id", date = date.to_string())));
+            }
+            game::Mode::Timed => {
+                let seconds = self.challenge_left().as_secs_f32().ceil() as u64;
+                column = column.push(widget::text::title4(fl!("challenge-time-left", seconds = seconds)));
+            }
+        }
+
+        if self.game_mode == game::Mode::Free {
+            column = column.push(self.difficulty_view(config)).push(widget::text::body(
+                match config.game_best.get(&config.game_difficulty) {
+                    Some(&attempts) => fl!("best-attempts", attempts = attempts),
+                    None => fl!("no-best-attempts"),
+                },
+            ));
+            column = column.push(self.leaderboard_view(config.game_difficulty));
+        }
+
+        column = column.push(row_number);
+
+        if config.show_numpad {
+            column = column.push(widgets::numpad(Message::Numpad));
+        }
+
+        let numpad_toggle = widget::row::with_capacity(2)
+            .push(widget::text::body(fl!("show-numpad")))
+            .push(widget::toggler(config.show_numpad).on_toggle(Message::ShowNumpad))
+            .align_y(Vertical::Center)
+            .spacing(space_s);
+
+        column = column.push(numpad_toggle).push(feedback_text);
+
+        if !self.game.guesses().is_empty() {
+            column = column.push(self.guesses_view(config.explicit_feedback));
+        }
+
+        column
+            .push(attempts_text)
+            .push(widgets::wrapping_row(buttons))
+            .spacing(space_s)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn update(&mut self, message: Message, ctx: &mut Context<'_>) -> Task<Message> {
+        match message {
+            Message::Input(v) => {
+                self.number = v;
+            }
+            Message::Clear => {
+                self.number.clear();
+            }
+            Message::Numpad(key) => match key {
+                NumpadKey::Digit(digit) => self.number.push(char::from(b'0' + digit)),
+                NumpadKey::Backspace => {
+                    self.number.pop();
+                }
+                NumpadKey::Enter => return self.update(Message::Check, ctx),
+            },
+            Message::ShowNumpad(show) => {
+                ctx.config.show_numpad = show;
+                ctx.save_config();
+            }
+            Message::Check => {
+                // A guess sent as time runs out must not count.
+                self.finish_challenge(ctx);
+
+                // A finished campaign or timed round must be restarted before guessing again.
+                if matches!(self.game_mode, game::Mode::Campaign(_) | game::Mode::Timed)
+                    && (self.game.is_won() || self.game.is_lost())
+                {
+                    return Task::none();
+                }
+
+                let finished = self.game.is_won() || self.game.is_lost();
+
+                match self.number.parse::<i64>() {
+                    Ok(num) if !self.game.range().contains(&num) => {
+                        let range = self.game.range();
+                        self.feedback = fl!("guess-out-of-range", low = *range.start(), high = *range.end());
+                        self.guess_shake = Some(Animation::new(animation::SHAKE_DURATION));
+                    }
+                    Ok(num) => {
+                        self.feedback = match self.game.guess(num) {
+                            Outcome::Correct => format!("✅ Right! This is the number {}", self.game.secret()),
+                            Outcome::TooLow => "⏫ My number is higher!".to_string(),
+                            Outcome::TooHigh => "⏬ My number is less!".to_string(),
+                        };
+
+                        // A new best announces the win itself, and the timed challenge has its summary.
+                        let mut announced = self.game_mode == game::Mode::Timed;
+                        let mut placed = false;
+
+                        match self.game_mode {
+                            game::Mode::Free => {
+                                let attempts = self.game.attempts();
+                                let best = ctx.config.game_best.get(&ctx.config.game_difficulty);
+
+                                if !finished && self.game.is_won() && best.is_none_or(|&best| attempts < best) {
+                                    ctx.config.game_best.insert(ctx.config.game_difficulty, attempts);
+                                    ctx.save_config();
+                                    ctx.toast(fl!("new-best", attempts = attempts));
+                                    announced = true;
+                                }
+
+                                let difficulty = ctx.config.game_difficulty;
+
+                                if !finished
+                                    && self.game.is_won()
+                                    && leaderboard::qualifies(&self.leaderboard, difficulty, attempts)
+                                {
+                                    let name = if ctx.config.player_name.is_empty() {
+                                        std::env::var("USER").unwrap_or_default()
+                                    } else {
+                                        ctx.config.player_name.clone()
+                                    };
+
+                                    self.record = Some(leaderboard::Entry {
+                                        name: leaderboard::sanitize(&name),
+                                        difficulty,
+                                        attempts,
+                                        date: Local::now().date_naive(),
+                                    });
+                                    placed = true;
+                                }
+                            }
+                            game::Mode::Campaign(level) => {
+                                if self.game.is_won() && level > ctx.config.campaign_completed {
+                                    ctx.config.campaign_completed = level;
+                                    ctx.save_config();
+                                } else if self.game.is_lost() {
+                                    self.feedback = fl!("out-of-attempts", secret = self.game.secret());
+                                }
+                            }
+                            game::Mode::Daily(date) => {
+                                if self.game.is_won() {
+                                    self.daily.record(date, self.game.attempts());
+                                }
+                            }
+                            game::Mode::Timed => self.finish_challenge(ctx),
+                        }
+
+                        if !finished && self.game.is_won() {
+                            ctx.play(SoundEvent::GameWon);
+
+                            if !announced {
+                                ctx.announce(fl!("game-won", attempts = self.game.attempts()));
+                            }
+                        }
+
+                        if !finished && (self.game.is_won() || self.game.is_lost()) {
+                            ctx.record(usage::Event::GamePlayed);
+                        }
+
+                        if placed {
+                            return widget::text_input::focus(PLAYER_NAME_INPUT.clone());
+                        }
+                    }
+                    Err(_) => {
+                        self.feedback = "❌ Enter a number!".to_string();
+                        self.guess_shake = Some(Animation::new(animation::SHAKE_DURATION));
+                    }
+                }
+            }
+            Message::NewGame => {
+                // A solved daily challenge would only be replayed with the number known.
+                if let game::Mode::Daily(date) = self.game_mode {
+                    if let Some(attempts) = self.daily.get(date) {
+                        self.feedback = fl!("daily-played", attempts = attempts);
+                        return Task::none();
+                    }
+                }
+
+                // A round given up partway may be taken back. Timed challenges are not,
+                // since their clock kept running.
+                let over = self.game.is_won() || self.game.is_lost();
+
+                if self.game_mode != game::Mode::Timed && !over && !self.game.guesses().is_empty() {
+                    ctx.changed(Change::Round {
+                        mode: self.game_mode,
+                        engine: self.game.clone(),
+                    });
+                }
+
+                self.game = match self.game_mode {
+                    game::Mode::Free => game::Engine::new(ctx.config.game_difficulty.range()),
+                    game::Mode::Campaign(num) => game::campaign_level(num)
+                        .map_or_else(|| game::Engine::new(1..=100), game::Engine::for_level),
+                    game::Mode::Daily(date) => game::Engine::daily(date),
+                    game::Mode::Timed => game::Engine::new(1..=100),
+                };
+                self.number.clear();
+
+                let range = self.game.range();
+                self.feedback = fl!("new-game", low = *range.start(), high = *range.end());
+
+                if self.game_mode == game::Mode::Timed {
+                    self.challenge_started = Some(Instant::now());
+                    self.challenge_used = None;
+                    self.show_summary = false;
+                    self.feedback = fl!("challenge-start", seconds = game::CHALLENGE_TIME.as_secs());
+                }
+
+                // Others on the machine may have played since the leaderboard was read.
+                if self.game_mode == game::Mode::Free {
+                    return Self::load_leaderboard();
+                }
+            }
+            Message::ShowLevelSelect => {
+                self.level_select = true;
+            }
+            Message::StartLevel(num) => {
+                if let Some(level) = game::campaign_level(num) {
+                    self.feedback = fl!("level-start", upper = *level.range.end(), attempts = level.max_attempts);
+                    self.game = game::Engine::for_level(level);
+                    self.game_mode = game::Mode::Campaign(num);
+                    self.level_select = false;
+                    self.number.clear();
+                }
+            }
+            Message::StartFreePlay => {
+                self.game_mode = game::Mode::Free;
+                self.level_select = false;
+                return self.update(Message::NewGame, ctx);
+            }
+            Message::StartDaily => {
+                let today = Local::now().date_naive();
+
+                if self.daily.get(today).is_some() {
+                    return Task::none();
+                }
+
+                self.game_mode = game::Mode::Daily(today);
+                self.game = game::Engine::daily(today);
+                self.level_select = false;
+                self.number.clear();
+                self.feedback = fl!("daily-start");
+            }
+            Message::StartChallenge => {
+                self.game_mode = game::Mode::Timed;
+                self.level_select = false;
+                return self.update(Message::NewGame, ctx);
+            }
+            Message::ChallengeTick => {
+                self.finish_challenge(ctx);
+            }
+            Message::CloseSummary => {
+                self.show_summary = false;
+            }
+            Message::SelectDifficulty(idx) => {
+                ctx.config.game_difficulty = match Difficulty::PRESETS.get(idx) {
+                    Some(&difficulty) => difficulty,
+                    None => {
+                        // Start the custom range from the one being played.
+                        let range = self.game.range();
+                        self.custom_range = (range.start().to_string(), range.end().to_string());
+                        Difficulty::Custom {
+                            low: *range.start(),
+                            high: *range.end(),
+                        }
+                    }
+                };
+
+                ctx.save_config();
+                return self.update(Message::NewGame, ctx);
+            }
+            Message::CustomLowInput(low) => {
+                self.custom_range.0 = low;
+            }
+            Message::CustomHighInput(high) => {
+                self.custom_range.1 = high;
+            }
+            Message::ApplyCustomRange => {
+                let bounds = (self.custom_range.0.trim().parse(), self.custom_range.1.trim().parse());
+
+                match bounds {
+                    (Ok(low), Ok(high)) if low < high => {
+                        ctx.config.game_difficulty = Difficulty::Custom { low, high };
+                        ctx.save_config();
+                        return self.update(Message::NewGame, ctx);
+                    }
+                    _ => {
+                        self.feedback = fl!("invalid-range");
+                        self.guess_shake = Some(Animation::new(animation::SHAKE_DURATION));
+                    }
+                }
+            }
+            Message::ClearRecords => {
+                ctx.config.game_best.clear();
+                ctx.save_config();
+            }
+            Message::LeaderboardLoaded(result) => match result {
+                Ok(entries) => self.leaderboard = entries,
+                Err(why) => eprintln!("failed to read the leaderboard: {why}"),
+            },
+            Message::PlayerName(name) => {
+                if let Some(entry) = &mut self.record {
+                    entry.name = name.chars().take(leaderboard::NAME_LENGTH).collect();
+                }
+            }
+            Message::SaveRecord => {
+                let Some(mut entry) = self.record.take() else {
+                    return Task::none();
+                };
+
+                entry.name = leaderboard::sanitize(&entry.name);
+
+                if entry.name.is_empty() {
+                    entry.name = fl!("anonymous-player");
+                } else if entry.name != ctx.config.player_name {
+                    ctx.config.player_name.clone_from(&entry.name);
+                    ctx.save_config();
+                }
+
+                return blocking(move || leaderboard::submit(entry), Message::RecordSaved);
+            }
+            Message::SkipRecord => {
+                self.record = None;
+            }
+            Message::RecordSaved(result) => match result {
+                Ok(entries) => {
+                    self.leaderboard = entries;
+                    ctx.toast(fl!("leaderboard-saved"));
+                }
+                Err(why) => ctx.fail(fl!("leaderboard-save-failed", reason = why.to_string()), why),
+            },
+            Message::AnimationFrame => {
+                self.guess_shake = self.guess_shake.filter(|animation| !animation.is_finished());
+            }
+        }
+
+        Task::none()
+    }
+
+    fn dialog(&self) -> Option<Element<'_, Message>> {
+        if let Some(entry) = &self.record {
+            return Some(self.record_dialog(entry));
+        }
+
+        if !self.show_summary {
+            return None;
+        }
+
+        let attempts = self.game.attempts();
+
+        let (title, body) = if self.game.is_won() {
+            let seconds = self.challenge_used.unwrap_or_default().as_secs();
+            (
+                fl!("challenge-won"),
+                fl!("challenge-won-summary", seconds = seconds, attempts = attempts),
+            )
+        } else {
+            (
+                fl!("challenge-lost"),
+                fl!("challenge-lost-summary", secret = self.game.secret(), attempts = attempts),
+            )
+        };
+
+        Some(
+            widget::dialog()
+                .title(title)
+                .body(body)
+                .primary_action(widget::button::suggested(fl!("play-again")).on_press(Message::StartChallenge))
+                .secondary_action(widget::button::standard(fl!("close")).on_press(Message::CloseSummary))
+                .into(),
+        )
+    }
+
+    fn subscription(&self, _config: &Config) -> Subscription<Message> {
+        let mut subscriptions = Vec::with_capacity(2);
+
+        // Redraws every frame while the guess field is shaking.
+        if self.guess_shake.is_some() {
+            subscriptions.push(window::frames().map(|_| Message::AnimationFrame));
+        }
+
+        // Counts down the timed challenge every second until the round is over.
+        if self.game_mode == game::Mode::Timed && self.challenge_used.is_none() {
+            subscriptions.push(Subscription::run(|| {
+                iced_futures::stream::channel(1, |mut emitter| async move {
+                    let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+                    loop {
+                        interval.tick().await;
+                        _ = emitter.send(Message::ChallengeTick).await;
+                    }
+                })
+            }));
+        }
+
+        Subscription::batch(subscriptions)
+    }
+}
+
+/// Runs `job` away from the interface, since the leaderboard may be locked by another player.
+fn blocking<T: Send + 'static>(
+    job: impl FnOnce() -> Result<T, Error> + Send + 'static,
+    done: fn(Result<T, Error>) -> Message,
+) -> Task<Message> {
+    Task::future(async move {
+        let result = tokio::task::spawn_blocking(job)
+            .await
+            .unwrap_or_else(|why| Err(Error::new(Code::Io, why.to_string())));
+
+        done(result)
+    })
+}
+
+/// Labels for the difficulty choices: each of `Difficulty::PRESETS`, then custom.
+fn difficulty_labels() -> Vec<String> {
+    Difficulty::PRESETS
+        .iter()
+        .map(|difficulty| {
+            let range = difficulty.range();
+            let (low, high) = (*range.start(), *range.end());
+
+            match difficulty {
+                Difficulty::Easy => fl!("difficulty-easy", low = low, high = high),
+                Difficulty::Hard => fl!("difficulty-hard", low = low, high = high),
+                _ => fl!("difficulty-normal", low = low, high = high),
+            }
+        })
+        .chain(std::iter::once(fl!("difficulty-custom")))
+        .collect()
+}
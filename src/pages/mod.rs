@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The pages listed in the nav bar.
+//!
+//! Each page owns its state and messages, and implements [`Page`] so that the
+//! application only has to route messages to it and draw what it returns.
+
+pub mod clipboard;
+pub mod counters;
+pub mod files;
+pub mod game;
+pub mod password;
+pub mod watch;
+
+pub use clipboard::ClipboardPage;
+pub use counters::CountersPage;
+pub use files::FilesPage;
+pub use game::GamePage;
+pub use password::PasswordPage;
+pub use watch::WatchPage;
+
+use crate::audio::SoundEvent;
+use crate::config::Config;
+use crate::counters::{Counter, Group};
+use crate::dbus;
+use crate::error::Error;
+use crate::fl;
+use crate::game;
+use crate::io;
+use crate::state::State;
+use crate::timer::Alarm;
+use crate::usage;
+use cosmic::iced::{Alignment, Subscription};
+use cosmic::prelude::*;
+use cosmic::widget;
+
+/// Hours of the day a counter reset or alarm may be scheduled for.
+pub const HOURS: [&str; 24] = [
+    "00", "01", "02", "03", "04", "05", "06", "07", "08", "09", "10", "11", "12", "13", "14", "15",
+    "16", "17", "18", "19", "20", "21", "22", "23",
+];
+/// Minutes past the hour a counter reset or alarm may be scheduled for.
+pub const MINUTES: [&str; 4] = ["00", "15", "30", "45"];
+
+/// A page of the application, with its own view, update, and subscriptions.
+pub trait Page {
+    type Message: Clone + std::fmt::Debug + Send + 'static;
+
+    /// Describes the page's interface, in the narrow layout if the window it
+    /// is drawn in is `narrow`.
+    fn view<'a>(&'a self, config: &'a Config, narrow: bool) -> Element<'a, Self::Message>;
+
+    /// Handles a message emitted by the page's widgets or subscriptions.
+    fn update(&mut self, message: Self::Message, ctx: &mut Context<'_>) -> Task<Self::Message>;
+
+    /// Builds again any text the page keeps from the translations, after the language changed.
+    fn relabel(&mut self) {}
+
+    /// A modal dialog to show over the page, if it needs one.
+    fn dialog(&self) -> Option<Element<'_, Self::Message>> {
+        None
+    }
+
+    /// Background work the page needs while it is in its current state.
+    fn subscription(&self, _config: &Config) -> Subscription<Self::Message> {
+        Subscription::none()
+    }
+
+    /// Picks up where the page was left when the app last closed.
+    fn restore_state(&mut self, _state: &State, _config: &Config) {}
+
+    /// Records where the page is, to pick up from at the next launch.
+    fn save_state(&self, _state: &mut State) {}
+
+    /// Reverses `change`, which this page made, returning the change that
+    /// reverses it in turn so that it can be redone.
+    fn revert(&mut self, _change: Change, _ctx: &mut Context<'_>) -> Option<Change> {
+        None
+    }
+}
+
+/// The application state a page may use while handling a message.
+pub struct Context<'a> {
+    /// Configuration shared by every page.
+    pub config: &'a mut Config,
+    effects: Effects,
+}
+
+/// What a page asked the application to do on its behalf.
+#[derive(Default)]
+pub struct Effects {
+    /// Notifications to show over the page content.
+    pub toasts: Vec<String>,
+    /// Notable events, toasted and also sent as desktop notifications if enabled.
+    pub announcements: Vec<String>,
+    /// Events whose chosen sounds should play.
+    pub sounds: Vec<SoundEvent>,
+    /// Texts to speak aloud, in the voice chosen for them.
+    pub spoken: Vec<String>,
+    /// Events to broadcast on the session bus.
+    pub events: Vec<dbus::Event>,
+    /// Usage to count in the local activity record.
+    pub usage: Vec<usage::Event>,
+    /// Items removed from the configuration, which the user may still bring back.
+    pub removed: Vec<Removed>,
+    /// Whether the configuration changed and should be written to disk.
+    pub save_config: bool,
+    /// Whether the pages' state should be written out now, rather than only as the app closes.
+    pub save_state: bool,
+    /// Changes the user may undo, oldest first.
+    pub changes: Vec<Change>,
+    /// How far a file the page is reading or writing has got.
+    pub transfer: Option<io::Transferred>,
+}
+
+/// A change a page made which the user may undo, holding what it replaced.
+///
+/// Pages offering undo add a variant here and reverse it in [`Page::revert`].
+#[derive(Clone, Debug)]
+pub enum Change {
+    /// The counter groups as they were before the change.
+    Counters(Vec<Group>),
+    /// A round of the guessing game that a new one replaced.
+    Round { mode: game::Mode, engine: game::Engine },
+}
+
+/// An item a page removed from the configuration, kept so that it can be put back.
+///
+/// The removal is only written to disk once it can no longer be undone; until
+/// then, saves put the item back into what they write.
+#[derive(Clone, Debug)]
+pub enum Removed {
+    /// A counter removed from the group with the ID `group`.
+    Counter { group: u64, index: usize, counter: Counter },
+    Group { index: usize, group: Group },
+    Alarm { index: usize, alarm: Alarm },
+}
+
+impl Removed {
+    /// Tells the user what was removed.
+    pub fn description(&self) -> String {
+        match self {
+            Removed::Counter { counter, .. } => fl!("removed-counter", name = counter.name.as_str()),
+            Removed::Group { group, .. } => fl!("removed-group", name = group.name.as_str()),
+            Removed::Alarm { alarm, .. } => fl!(
+                "removed-alarm",
+                time = format!("{:02}:{:02}", alarm.hour, alarm.minute)
+            ),
+        }
+    }
+
+    /// Puts the item back where it was, or as near as other changes since allow.
+    ///
+    /// A counter whose group has been removed since stays removed.
+    pub fn restore(self, config: &mut Config) {
+        match self {
+            Removed::Counter { group, index, counter } => {
+                if let Some(group) = config.counter_groups.iter_mut().find(|g| g.id == group) {
+                    group.counters.insert(index.min(group.counters.len()), counter);
+                }
+            }
+            Removed::Group { index, group } => {
+                config.counter_groups.insert(index.min(config.counter_groups.len()), group);
+            }
+            Removed::Alarm { index, alarm } => {
+                config.alarms.insert(index.min(config.alarms.len()), alarm);
+            }
+        }
+    }
+}
+
+impl Context<'_> {
+    /// Writes the current configuration to disk once the page has handled its message.
+    pub fn save_config(&mut self) {
+        self.effects.save_config = true;
+    }
+
+    /// Shows `text` in a toast once the page has handled its message.
+    pub fn toast(&mut self, text: String) {
+        self.effects.toasts.push(text);
+    }
+
+    /// Shows `text` in a toast, and in a desktop notification too if the user
+    /// turned them on, for events worth knowing about outside the app.
+    pub fn announce(&mut self, text: String) {
+        self.effects.announcements.push(text);
+    }
+
+    /// Plays the sound the user chose for `event`, if any.
+    pub fn play(&mut self, event: SoundEvent) {
+        self.effects.sounds.push(event);
+    }
+
+    /// Shows `text` in a toast for a failure, and broadcasts the failure's code.
+    pub fn fail(&mut self, text: String, error: Error) {
+        self.toast(text);
+        self.emit(dbus::Event::Failed(error));
+    }
+
+    /// Speaks `text` aloud, if the app was built able to.
+    pub fn speak(&mut self, text: String) {
+        self.effects.spoken.push(text);
+    }
+
+    /// Broadcasts `event` on the session bus, if it is connected.
+    pub fn emit(&mut self, event: dbus::Event) {
+        self.effects.events.push(event);
+    }
+
+    /// Offers to undo the removal of `item`.
+    pub fn removed(&mut self, item: Removed) {
+        self.effects.removed.push(item);
+    }
+
+    /// Counts `event` in the local activity record.
+    pub fn record(&mut self, event: usage::Event) {
+        self.effects.usage.push(event);
+    }
+
+    /// Writes the pages' state out once the page has handled its message.
+    pub fn save_state(&mut self) {
+        self.effects.save_state = true;
+    }
+
+    /// Offers to undo `change`, forgetting anything undone before it.
+    pub fn changed(&mut self, change: Change) {
+        self.effects.changes.push(change);
+    }
+
+    /// Shows how far a file transfer has got, from the progress sent by [`io::task`].
+    pub fn transferred(&mut self, progress: io::Transferred) {
+        self.effects.transfer = Some(progress);
+    }
+}
+
+/// Passes `message` to `page`, returning its task and the effects it asked for.
+pub fn update<P: Page>(
+    page: &mut P,
+    message: P::Message,
+    config: &mut Config,
+) -> (Task<P::Message>, Effects) {
+    let mut ctx = Context {
+        config,
+        effects: Effects::default(),
+    };
+
+    let task = page.update(message, &mut ctx);
+    (task, ctx.effects)
+}
+
+/// Has `page` reverse `change`, returning the change that redoes it and the
+/// effects it asked for.
+pub fn revert<P: Page>(
+    page: &mut P,
+    change: Change,
+    config: &mut Config,
+) -> (Option<Change>, Effects) {
+    let mut ctx = Context {
+        config,
+        effects: Effects::default(),
+    };
+
+    let change = page.revert(change, &mut ctx);
+    (change, ctx.effects)
+}
+
+/// The title row shown at the top of a page.
+pub fn header<'a, Message: 'static>(title: String, num: usize) -> Element<'a, Message> {
+    widget::row::with_capacity(2)
+        .push(widget::text::title1(title))
+        .push(widget::text::title3(fl!("page-id", num = num)))
+        .align_y(Alignment::End)
+        .spacing(cosmic::theme::spacing().space_s)
+        .into()
+}
+
+/// Localized weekday names, starting from Monday.
+pub fn weekday_labels() -> Vec<String> {
+    vec![
+        fl!("monday"),
+        fl!("tuesday"),
+        fl!("wednesday"),
+        fl!("thursday"),
+        fl!("friday"),
+        fl!("saturday"),
+        fl!("sunday"),
+    ]
+}
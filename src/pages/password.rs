@@ -0,0 +1,790 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The password page: random passwords and passphrases with a strength threshold.
+
+use super::{Context, Page};
+use crate::animation::{self, Animation};
+use crate::config::Config;
+use crate::dbus;
+use crate::error::Error;
+use crate::filter::Filter;
+use crate::fl;
+use crate::generators::passphrase::{PassphraseSpec, Wordlist};
+use crate::generators::password::{CharClasses, PasswordSpec};
+use crate::generators::phonetic::{self, Spelled};
+use crate::generators::strength;
+use crate::icons;
+use crate::lock;
+use crate::selection::{self, Bulk, Selection};
+use crate::widgets;
+use chrono::Local;
+use cosmic::dialog::file_chooser;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::{Length, Subscription, clipboard, window};
+use cosmic::prelude::*;
+use cosmic::widget;
+
+/// Password strength thresholds the user may choose from, in bits of entropy.
+const MIN_ENTROPY_BITS: [u32; 5] = [40, 60, 80, 100, 128];
+/// Most passwords generated while trying to reach the strength threshold.
+const MAX_GENERATE_ATTEMPTS: u32 = 100;
+/// Password lengths the user may choose between.
+pub const PASSWORD_LENGTHS: std::ops::RangeInclusive<u32> = 8..=128;
+/// Length of generated passwords until the user chooses one.
+const DEFAULT_PASSWORD_LENGTH: u32 = 16;
+/// Most generated passwords kept in the history.
+const HISTORY_LIMIT: usize = 20;
+/// Shown in place of a history entry until it is revealed.
+const MASK: &str = "••••••••••••";
+
+pub struct PasswordPage {
+    password: String,
+    /// Hides the password field's text until it is revealed again, after the session locked.
+    password_masked: bool,
+    /// Whether a password was copied since the session last locked, so the clipboard holds it.
+    copied: bool,
+    /// Shakes the password field after an invalid action.
+    password_shake: Option<Animation>,
+    /// Whether the password is spelled out in the phonetic alphabet beneath its field.
+    spelled_out: bool,
+    /// Generate passphrases from a word list instead of random characters.
+    passphrase: bool,
+    /// The bundled word list followed by any imported ones.
+    wordlists: Vec<Wordlist>,
+    /// Labels for the entries of `wordlists`.
+    wordlist_names: Vec<String>,
+    /// Index into `wordlists` of the list passphrases are drawn from.
+    wordlist: usize,
+    /// Why the last word list import failed, if it did.
+    wordlist_error: Option<Error>,
+    /// Generations needed to reach the strength threshold, and whether it was reached.
+    password_attempts: Option<(u32, bool)>,
+    /// Labels for the strength threshold choices in `MIN_ENTROPY_BITS`.
+    entropy_labels: Vec<String>,
+    /// Passwords generated since the app started, oldest first. Never saved to disk.
+    history: Vec<HistoryEntry>,
+    /// Whether the history list is expanded.
+    history_expanded: bool,
+    /// History entries selected for deleting or copying together.
+    history_selection: Selection,
+    /// Only history entries containing this text are listed.
+    history_search: Filter,
+}
+
+/// A generated password kept in the history.
+struct HistoryEntry {
+    password: String,
+    /// Shown as typed rather than masked.
+    revealed: bool,
+    /// When it was generated, shown beside it and searched while it is masked.
+    generated: String,
+}
+
+impl HistoryEntry {
+    /// Whether `search` finds the entry, which only looks at the password
+    /// itself once it is revealed, so a masked password cannot be guessed at
+    /// by searching for it.
+    fn matches(&self, search: &Filter) -> bool {
+        if self.revealed {
+            search.matches([self.password.as_str(), self.generated.as_str()])
+        } else {
+            search.matches([self.generated.as_str()])
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Input(String),
+    Copy,
+    Clear,
+    Generate,
+    RequireStrength(bool),
+    MinEntropyBits(u32),
+    UsePassphrase(bool),
+    SelectWordlist(usize),
+    PasswordLength(u32),
+    SavePasswordOptions,
+    PasswordClasses(CharClasses),
+    ImportWordlist,
+    WordlistImported(Option<Result<Wordlist, Error>>),
+    ToggleHistory,
+    RevealHistory(usize),
+    CopyHistory(usize),
+    DeleteHistory(usize),
+    HistorySelection(selection::Action),
+    HistorySearchInput(String),
+    ApplyHistorySearch(u64),
+    ClearHistorySearch,
+    AnimationFrame,
+    SessionLocked(bool),
+    RevealPassword,
+    ToggleSpelling,
+    CopySpelling,
+}
+
+impl PasswordPage {
+    pub fn new() -> Self {
+        let mut wordlists = vec![Wordlist::bundled()];
+        wordlists.extend(Wordlist::load_cached());
+
+        Self {
+            password: String::new(),
+            password_masked: false,
+            copied: false,
+            password_shake: None,
+            spelled_out: false,
+            passphrase: false,
+            wordlist_names: wordlist_labels(&wordlists),
+            wordlists,
+            wordlist: 0,
+            wordlist_error: None,
+            password_attempts: None,
+            entropy_labels: entropy_labels(),
+            history: Vec::new(),
+            history_expanded: false,
+            history_selection: Selection::default(),
+            history_search: Filter::default(),
+        }
+    }
+
+    /// Adds a generated password to the history, forgetting the oldest beyond the limit.
+    fn remember(&mut self, password: String) {
+        self.history.push(HistoryEntry {
+            password,
+            revealed: false,
+            generated: Local::now().format("%H:%M:%S").to_string(),
+        });
+
+        if self.history.len() > HISTORY_LIMIT {
+            self.history.remove(0);
+            // Removing the oldest entry shifts every index the selection refers to.
+            self.history_selection.cancel();
+        }
+    }
+
+    /// Puts `text` on the clipboard, remembering to clear it when the session locks.
+    ///
+    /// Every copy of a password goes through here, so none is left behind by a
+    /// lock. Copies are marked as secret to keep them out of clipboard histories.
+    fn copy(&mut self, text: String) -> Task<Message> {
+        self.copied = true;
+        crate::clipboard::write_secret(text)
+    }
+
+    /// Indices of the history entries the search finds, oldest first.
+    fn shown_history(&self) -> Vec<usize> {
+        (0..self.history.len())
+            .filter(|&idx| self.history[idx].matches(&self.history_search))
+            .collect()
+    }
+
+    /// The password read out one word per character, to dictate it over the phone.
+    fn spelling_view(&self) -> Element<'_, Message> {
+        let space_xxs = cosmic::theme::spacing().space_xxs;
+
+        let words = spelled_words(&self.password).into_iter().map(|word| {
+            widget::container(widget::text::body(word))
+                .padding([space_xxs, cosmic::theme::spacing().space_xs])
+                .class(cosmic::theme::Container::Card)
+                .into()
+        });
+
+        widget::column::with_capacity(2)
+            .push(
+                widget::flex_row(words.collect())
+                    .row_spacing(space_xxs)
+                    .column_spacing(space_xxs),
+            )
+            .push(widget::button::standard(fl!("copy-spelling")).on_press(Message::CopySpelling))
+            .spacing(cosmic::theme::spacing().space_xs)
+            .into()
+    }
+
+    /// The collapsible list of generated passwords, newest first.
+    fn history_view(&self) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let toggle = widget::button::text(fl!("password-history", count = self.history.len()))
+            .leading_icon(icons::handle(if self.history_expanded {
+                "pan-down-symbolic"
+            } else {
+                "pan-end-symbolic"
+            }))
+            .on_press(Message::ToggleHistory);
+
+        let mut column = widget::column::with_capacity(4).push(toggle).spacing(space_s);
+
+        if !self.history_expanded {
+            return column.into();
+        }
+
+        if self.history.is_empty() {
+            let empty = widgets::empty_state(icons::handle("dialog-password-symbolic"), fl!("no-passwords"))
+                .description(fl!("no-password-history"))
+                .action(fl!("generate-password"), Message::Generate);
+
+            return column.push(empty).into();
+        }
+
+        let shown = self.shown_history();
+
+        column = column.push(widgets::filter_input(
+            &self.history_search,
+            fl!("search-passwords"),
+            shown.len(),
+            self.history.len(),
+            Message::HistorySearchInput,
+            Message::ClearHistorySearch,
+        ));
+
+        if shown.is_empty() {
+            return column.push(widget::text::body(fl!("no-matching-passwords"))).into();
+        }
+
+        let mut list = cosmic::widget::settings::section();
+
+        for &idx in shown.iter().rev() {
+            let entry = &self.history[idx];
+            let text = if entry.revealed { entry.password.as_str() } else { MASK };
+
+            let controls = widget::row::with_capacity(3)
+                .push(
+                    widget::button::icon(icons::handle(if entry.revealed {
+                        "view-conceal-symbolic"
+                    } else {
+                        "view-reveal-symbolic"
+                    }))
+                    .on_press(Message::RevealHistory(idx)),
+                )
+                .push(
+                    widget::button::icon(icons::handle("edit-copy-symbolic"))
+                        .on_press(Message::CopyHistory(idx)),
+                )
+                .push(
+                    widget::button::icon(icons::handle("edit-delete-symbolic"))
+                        .on_press(Message::DeleteHistory(idx)),
+                )
+                .align_y(Vertical::Center)
+                .spacing(space_s);
+
+            let item = widget::row::with_capacity(3)
+                .push(widget::text::monotext(text).width(Length::Fill))
+                .push(widget::text::caption(entry.generated.as_str()))
+                .push(controls)
+                .align_y(Vertical::Center)
+                .spacing(space_s);
+
+            list = list.add(widgets::selectable(
+                &self.history_selection,
+                idx,
+                item,
+                Message::HistorySelection,
+            ));
+        }
+
+        column
+            .push(widgets::selection_bar(
+                &self.history_selection,
+                shown.len(),
+                Message::HistorySelection,
+            ))
+            .push(list)
+            .into()
+    }
+
+    /// Generates one password or passphrase with the current options.
+    fn generate_password(&self, config: &Config) -> Option<String> {
+        if self.passphrase {
+            return Some(PassphraseSpec::default().generate(&self.wordlists[self.wordlist]));
+        }
+
+        match password_spec(config).generate() {
+            Ok(password) => Some(password),
+            Err(why) => {
+                eprintln!("failed to generate password: {why}");
+                None
+            }
+        }
+    }
+
+    /// How many of `MIN_ENTROPY_BITS` generated passwords can reach, which
+    /// the settings limit unless passphrases are generated.
+    fn entropy_choices(&self, config: &Config) -> usize {
+        if self.passphrase {
+            return MIN_ENTROPY_BITS.len();
+        }
+
+        let spec = password_spec(config);
+        let max = strength::max_entropy_bits(&spec.alphabet(), spec.length);
+
+        MIN_ENTROPY_BITS.iter().take_while(|&&bits| f64::from(bits) <= max).count()
+    }
+
+    /// The strength threshold generation aims for: the chosen one, lowered to
+    /// the highest the settings can reach.
+    fn entropy_threshold(&self, config: &Config) -> Option<u32> {
+        let chosen = config.min_entropy_bits?;
+
+        MIN_ENTROPY_BITS[..self.entropy_choices(config)]
+            .iter()
+            .copied()
+            .take_while(|&bits| bits <= chosen)
+            .last()
+    }
+}
+
+impl Page for PasswordPage {
+    type Message = Message;
+
+    fn relabel(&mut self) {
+        self.wordlist_names = wordlist_labels(&self.wordlists);
+        self.entropy_labels = entropy_labels();
+    }
+
+    fn view<'a>(&'a self, config: &'a Config, narrow: bool) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let password_text_input = if self.password_masked {
+            widget::secure_input(
+                "Your password will be here!",
+                self.password.clone(),
+                Some(Message::RevealPassword),
+                true,
+            )
+        } else {
+            widget::text_input("Your password will be here!", self.password.clone())
+        }
+        .on_input(Message::Input)
+        .on_clear(Message::Clear);
+
+        let generate_button = widget::button::text("Generate password").on_press(Message::Generate);
+
+        let row_password = widgets::adaptive_row(narrow, vec![
+            animation::shaking(password_text_input, self.password_shake.as_ref()),
+            generate_button.into(),
+        ]);
+
+        let mut options = cosmic::widget::settings::section().add(
+            cosmic::widget::settings::item::builder(fl!("use-passphrase"))
+                .toggler(self.passphrase, Message::UsePassphrase),
+        );
+
+        if !self.passphrase {
+            options = password_options(options, config);
+        }
+
+        if self.passphrase {
+            options = options.add(
+                cosmic::widget::settings::item::builder(fl!("wordlist")).control(
+                    widget::row::with_capacity(2)
+                        .push(widget::dropdown(
+                            &self.wordlist_names,
+                            Some(self.wordlist),
+                            Message::SelectWordlist,
+                        ))
+                        .push(
+                            widget::button::standard(fl!("import-wordlist"))
+                                .on_press(Message::ImportWordlist),
+                        )
+                        .align_y(Vertical::Center)
+                        .spacing(space_s),
+                ),
+            );
+        }
+
+        // Thresholds beyond what the settings can produce would never be met.
+        let choices = self.entropy_choices(config);
+
+        if choices > 0 {
+            options = options.add(
+                cosmic::widget::settings::item::builder(fl!("require-strength"))
+                    .toggler(config.min_entropy_bits.is_some(), Message::RequireStrength),
+            );
+        }
+
+        if let Some(bits) = self.entropy_threshold(config) {
+            let selected = MIN_ENTROPY_BITS.iter().position(|&b| b == bits);
+
+            options = options.add(
+                cosmic::widget::settings::item::builder(fl!("min-strength")).control(
+                    widget::dropdown(&self.entropy_labels[..choices], selected, |idx| {
+                        Message::MinEntropyBits(MIN_ENTROPY_BITS[idx])
+                    }),
+                ),
+            );
+        }
+
+        let mut column = widget::column::with_capacity(9)
+            .push(super::header(fl!("welcome"), 3))
+            .push(row_password);
+
+        // A masked password stays hidden until revealed, so its spelling does too.
+        if !self.password.is_empty() && !self.password_masked {
+            let label = if self.spelled_out { fl!("hide-spelling") } else { fl!("spell-out") };
+            column = column.push(widget::button::text(label).on_press(Message::ToggleSpelling));
+
+            if self.spelled_out {
+                column = column.push(self.spelling_view());
+            }
+        }
+
+        column = column
+            .push(widgets::strength_meter(&self.password, config.explicit_feedback))
+            .push(options);
+
+        if let Some(why) = &self.wordlist_error {
+            column = column.push(widget::text::body(fl!("wordlist-invalid", reason = why.to_string())));
+        }
+
+        if let Some((attempts, reached)) = self.password_attempts {
+            column = column.push(widget::text::body(if reached {
+                fl!("strength-reached", attempts = attempts)
+            } else {
+                fl!("strength-not-reached", attempts = attempts)
+            }));
+        }
+
+        column
+            .push(self.history_view())
+            .spacing(space_s)
+            .apply(widget::scrollable)
+            .into()
+    }
+
+    fn update(&mut self, message: Message, ctx: &mut Context<'_>) -> Task<Message> {
+        match message {
+            Message::Input(v) => {
+                self.password = v;
+            }
+            Message::Clear => {
+                self.password.clear();
+                self.password_masked = false;
+            }
+            Message::Generate => {
+                self.password_masked = false;
+                let threshold = self.entropy_threshold(ctx.config).map(f64::from);
+                let mut attempts = 0;
+
+                // Regenerate until the threshold is met, within a bounded number of tries.
+                loop {
+                    attempts += 1;
+
+                    let Some(password) = self.generate_password(ctx.config) else {
+                        self.password_shake = Some(Animation::new(animation::SHAKE_DURATION));
+                        break;
+                    };
+
+                    let reached = threshold.is_none_or(|bits| strength::entropy_bits(&password) >= bits);
+                    self.password = password;
+
+                    if reached || attempts == MAX_GENERATE_ATTEMPTS {
+                        self.password_attempts = threshold.map(|_| (attempts, reached));
+                        self.remember(self.password.clone());
+                        break;
+                    }
+                }
+            }
+            Message::RequireStrength(enabled) => {
+                ctx.config.min_entropy_bits = enabled.then_some(MIN_ENTROPY_BITS[1]);
+                self.password_attempts = None;
+                ctx.save_config();
+            }
+            Message::MinEntropyBits(bits) => {
+                ctx.config.min_entropy_bits = Some(bits);
+                ctx.save_config();
+            }
+            Message::Copy => {
+                if self.password.is_empty() {
+                    self.password_shake = Some(Animation::new(animation::SHAKE_DURATION));
+                } else {
+                    ctx.toast(fl!("password-copied"));
+                    return self.copy(self.password.clone());
+                }
+            }
+            Message::UsePassphrase(enabled) => {
+                self.passphrase = enabled;
+            }
+            Message::SelectWordlist(idx) => {
+                self.wordlist = idx;
+            }
+            Message::PasswordLength(length) => {
+                // Saved once the slider is released rather than at every step.
+                ctx.config.password_length = length;
+            }
+            Message::SavePasswordOptions => {
+                ctx.save_config();
+            }
+            Message::PasswordClasses(classes) => {
+                // Lowercase letters are always included, so a password can always be made.
+                ctx.config.password_classes = CharClasses { lowercase: true, ..classes };
+                ctx.save_config();
+            }
+            Message::ImportWordlist => {
+                return cosmic::task::future(async move {
+                    let dialog = file_chooser::open::Dialog::new().title(fl!("import-wordlist"));
+
+                    let path = match dialog.open_file().await {
+                        Ok(response) => response.url().to_file_path().ok(),
+                        Err(file_chooser::Error::Cancelled) => return Message::WordlistImported(None),
+                        Err(why) => return Message::WordlistImported(Some(Err(Error::dialog(why)))),
+                    };
+
+                    let Some(path) = path else {
+                        return Message::WordlistImported(None);
+                    };
+
+                    Message::WordlistImported(Some(
+                        Wordlist::import(&path).await.map_err(Error::from),
+                    ))
+                });
+            }
+            Message::WordlistImported(result) => match result {
+                Some(Ok(wordlist)) => {
+                    self.wordlist_error = None;
+
+                    // Re-importing a list with the same name replaces it.
+                    let idx = match self.wordlists[1..].iter().position(|w| w.name() == wordlist.name()) {
+                        Some(idx) => {
+                            self.wordlists[idx + 1] = wordlist;
+                            idx + 1
+                        }
+                        None => {
+                            self.wordlists.push(wordlist);
+                            self.wordlists.len() - 1
+                        }
+                    };
+
+                    self.wordlist_names = wordlist_labels(&self.wordlists);
+                    self.wordlist = idx;
+                }
+                Some(Err(why)) => {
+                    ctx.emit(dbus::Event::Failed(why.clone()));
+                    self.wordlist_error = Some(why);
+                }
+                None => {}
+            },
+            Message::ToggleHistory => {
+                self.history_expanded = !self.history_expanded;
+            }
+            Message::SessionLocked(true) => {
+                // Whoever unlocks the session next has to reveal passwords again.
+                self.password_masked = !self.password.is_empty();
+
+                for entry in &mut self.history {
+                    entry.revealed = false;
+                }
+
+                self.history_selection.retain(self.shown_history());
+
+                if std::mem::take(&mut self.copied) {
+                    return clipboard::write(String::new());
+                }
+            }
+            Message::SessionLocked(false) => {}
+            Message::RevealPassword => {
+                self.password_masked = false;
+            }
+            Message::ToggleSpelling => {
+                self.spelled_out = !self.spelled_out;
+            }
+            Message::CopySpelling => {
+                if !self.password.is_empty() {
+                    ctx.toast(fl!("spelling-copied"));
+                    return self.copy(spelled_words(&self.password).join(" "));
+                }
+            }
+            Message::RevealHistory(idx) => {
+                if let Some(entry) = self.history.get_mut(idx) {
+                    entry.revealed = !entry.revealed;
+                    // Masking an entry may take it out of the search's results.
+                    self.history_selection.retain(self.shown_history());
+                }
+            }
+            Message::CopyHistory(idx) => {
+                if let Some(password) = self.history.get(idx).map(|entry| entry.password.clone()) {
+                    ctx.toast(fl!("password-copied"));
+                    return self.copy(password);
+                }
+            }
+            Message::DeleteHistory(idx) => {
+                if idx < self.history.len() {
+                    self.history.remove(idx);
+                    self.history_selection.cancel();
+                }
+            }
+            Message::HistorySelection(action) => {
+                match self.history_selection.update(action, self.shown_history()) {
+                    Some(Bulk::Delete(indices)) => {
+                        for idx in indices.into_iter().rev() {
+                            self.history.remove(idx);
+                        }
+                    }
+                    Some(Bulk::Export(indices)) => {
+                        let text: String = indices
+                            .into_iter()
+                            .map(|idx| format!("{}\n", self.history[idx].password))
+                            .collect();
+
+                        return self.copy(text);
+                    }
+                    None => {}
+                }
+            }
+            Message::HistorySearchInput(input) => {
+                return self.history_search.edit(input, Message::ApplyHistorySearch);
+            }
+            Message::ApplyHistorySearch(generation) => {
+                self.history_search.apply(generation);
+                self.history_selection.retain(self.shown_history());
+            }
+            Message::ClearHistorySearch => {
+                self.history_search.clear();
+            }
+            Message::AnimationFrame => {
+                self.password_shake = self.password_shake.filter(|animation| !animation.is_finished());
+            }
+        }
+
+        Task::none()
+    }
+
+    fn subscription(&self, _config: &Config) -> Subscription<Message> {
+        let locks = lock::watch().map(Message::SessionLocked);
+
+        // Redraws every frame while the password field is shaking.
+        if self.password_shake.is_some() {
+            Subscription::batch([locks, window::frames().map(|_| Message::AnimationFrame)])
+        } else {
+            locks
+        }
+    }
+}
+
+/// Adds the length and character class settings of generated passwords to `section`.
+///
+/// These are also offered as defaults in the app's settings.
+pub fn password_options<'a>(
+    section: cosmic::widget::settings::Section<'a, Message>,
+    config: &Config,
+) -> cosmic::widget::settings::Section<'a, Message> {
+    let length = password_length(config);
+    let classes = config.password_classes;
+
+    section
+        .add(
+            cosmic::widget::settings::item::builder(fl!("password-length", length = length)).control(
+                widget::slider(PASSWORD_LENGTHS, length, Message::PasswordLength)
+                    .on_release(Message::SavePasswordOptions),
+            ),
+        )
+        .add(
+            cosmic::widget::settings::item::builder(fl!("uppercase")).toggler(
+                classes.uppercase,
+                move |uppercase| Message::PasswordClasses(CharClasses { uppercase, ..classes }),
+            ),
+        )
+        .add(
+            cosmic::widget::settings::item::builder(fl!("digits")).toggler(
+                classes.digits,
+                move |digits| Message::PasswordClasses(CharClasses { digits, ..classes }),
+            ),
+        )
+        .add(
+            cosmic::widget::settings::item::builder(fl!("symbols")).toggler(
+                classes.symbols,
+                move |symbols| Message::PasswordClasses(CharClasses { symbols, ..classes }),
+            ),
+        )
+}
+
+/// Number of characters in generated passwords.
+/// The generator for passwords with the configured length and character classes.
+pub fn password_spec(config: &Config) -> PasswordSpec {
+    PasswordSpec::default()
+        .length(password_length(config) as usize)
+        .classes(config.password_classes)
+}
+
+fn password_length(config: &Config) -> u32 {
+    match config.password_length {
+        0 => DEFAULT_PASSWORD_LENGTH,
+        length => length.clamp(*PASSWORD_LENGTHS.start(), *PASSWORD_LENGTHS.end()),
+    }
+}
+
+/// Labels for the strength thresholds of `MIN_ENTROPY_BITS`.
+fn entropy_labels() -> Vec<String> {
+    MIN_ENTROPY_BITS
+        .iter()
+        .map(|&bits| fl!("entropy-bits", bits = bits))
+        .collect()
+}
+
+/// Labels word lists for the word list dropdown, the first being the bundled list.
+fn wordlist_labels(wordlists: &[Wordlist]) -> Vec<String> {
+    wordlists
+        .iter()
+        .enumerate()
+        .map(|(idx, wordlist)| {
+            if idx == 0 {
+                fl!("wordlist-bundled")
+            } else {
+                fl!("wordlist-imported", name = wordlist.name(), words = wordlist.len())
+            }
+        })
+        .collect()
+}
+
+/// The words `password` is read out with, one for each character.
+fn spelled_words(password: &str) -> Vec<String> {
+    phonetic::spell(password)
+        .into_iter()
+        .map(|spelled| match spelled {
+            Spelled::Letter { word, uppercase: true } => fl!("spelled-uppercase", word = word),
+            Spelled::Letter { word, uppercase: false } => fl!("spelled-lowercase", word = word),
+            Spelled::Digit(digit) => digit.to_string(),
+            Spelled::Space => fl!("spelled-space"),
+            Spelled::Symbol(symbol) => symbol_name(symbol),
+        })
+        .collect()
+}
+
+/// What to call `symbol` when reading it out, or the symbol itself if it has no name.
+fn symbol_name(symbol: char) -> String {
+    match symbol {
+        '!' => fl!("symbol-exclamation"),
+        '"' => fl!("symbol-quote"),
+        '#' => fl!("symbol-hash"),
+        '$' => fl!("symbol-dollar"),
+        '%' => fl!("symbol-percent"),
+        '&' => fl!("symbol-ampersand"),
+        '\'' => fl!("symbol-apostrophe"),
+        '(' => fl!("symbol-open-parenthesis"),
+        ')' => fl!("symbol-close-parenthesis"),
+        '*' => fl!("symbol-asterisk"),
+        '+' => fl!("symbol-plus"),
+        ',' => fl!("symbol-comma"),
+        '-' => fl!("symbol-hyphen"),
+        '.' => fl!("symbol-period"),
+        '/' => fl!("symbol-slash"),
+        ':' => fl!("symbol-colon"),
+        ';' => fl!("symbol-semicolon"),
+        '<' => fl!("symbol-less-than"),
+        '=' => fl!("symbol-equals"),
+        '>' => fl!("symbol-greater-than"),
+        '?' => fl!("symbol-question"),
+        '@' => fl!("symbol-at"),
+        '[' => fl!("symbol-open-bracket"),
+        '\\' => fl!("symbol-backslash"),
+        ']' => fl!("symbol-close-bracket"),
+        '^' => fl!("symbol-caret"),
+        '_' => fl!("symbol-underscore"),
+        '`' => fl!("symbol-backtick"),
+        '{' => fl!("symbol-open-brace"),
+        '|' => fl!("symbol-pipe"),
+        '}' => fl!("symbol-close-brace"),
+        '~' => fl!("symbol-tilde"),
+        symbol => symbol.to_string(),
+    }
+}
@@ -0,0 +1,1423 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The watch page: a stopwatch with session history, a countdown, and alarms.
+
+use super::{Context, HOURS, MINUTES, Page, Removed};
+use crate::audio::SoundEvent;
+use crate::config::Config;
+use crate::dbus;
+use crate::error::{Code, Error};
+use crate::filter::Filter;
+use crate::fl;
+use crate::icons;
+use crate::idle;
+use crate::io;
+use crate::mpris;
+use crate::selection::{self, Bulk, Selection};
+use crate::sessions;
+use crate::speech;
+use crate::state::State;
+use crate::timer;
+use crate::widgets::{self, pager};
+use chrono::{DateTime, Local};
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::platform_specific::runtime::wayland::layer_surface::SctkLayerSurfaceSettings;
+use cosmic::iced::platform_specific::shell::commands::layer_surface::{
+    Anchor, KeyboardInteractivity, Layer, destroy_layer_surface, get_layer_surface,
+};
+use cosmic::iced::{Length, Subscription, clipboard, window};
+use cosmic::widget::{self, segmented_button};
+use cosmic::{iced_futures, prelude::*};
+use futures_util::SinkExt;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Minutes of inactivity the user may choose from before the stopwatch pauses.
+const IDLE_PAUSE_MINUTES: [u32; 5] = [1, 5, 10, 15, 30];
+/// Stopwatch milestone intervals the user may choose from, in minutes.
+const MILESTONE_MINUTES: [u32; 3] = [15, 30, 60];
+/// Length of the countdown until the user chooses one.
+const DEFAULT_COUNTDOWN: Duration = Duration::from_secs(5 * 60);
+/// Height of the lap list, which scrolls once it has more laps than fit.
+const LAPS_HEIGHT: f32 = 200.0;
+/// The last seconds of a countdown which are spoken aloud, if chosen.
+const SPOKEN_SECONDS: u64 = 10;
+/// Width of the speech rate slider.
+const SPEECH_RATE_WIDTH: f32 = 160.0;
+/// Largest sessions file imported.
+const MAX_SESSIONS_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Whether the app runs on Wayland, the only backend with layer-shell
+/// surfaces for the countdown overlay.
+static ON_WAYLAND: LazyLock<bool> =
+    LazyLock::new(|| cosmic::cctk::wayland_client::Connection::connect_to_env().is_ok());
+/// Whether the app runs in a Flatpak sandbox, where commands see the
+/// sandbox's programs rather than the host's.
+static IN_SANDBOX: LazyLock<bool> = LazyLock::new(|| std::path::Path::new("/.flatpak-info").exists());
+
+pub struct WatchPage {
+    /// Stopwatches and countdowns shown on the page.
+    timers: timer::Service,
+    /// Tabs of the watch page.
+    tabs: segmented_button::SingleSelectModel,
+    /// The stopwatch shown on the watch page.
+    watch: timer::Id,
+    /// The countdown shown on the watch page.
+    countdown: timer::Id,
+    /// The countdown length being typed, before it is applied.
+    countdown_input: String,
+    /// The command to run when a countdown finishes, as typed.
+    command_input: String,
+    /// The typed command is waiting for the user to confirm it.
+    confirming_command: bool,
+    /// The time of day chosen for a new alarm.
+    new_alarm: (u32, u32),
+    /// When alarms were last checked for ringing.
+    alarms_checked: DateTime<Local>,
+    /// When the running stopwatch session started.
+    watch_started: Option<DateTime<Local>>,
+    /// Recorded stopwatch sessions.
+    sessions: sessions::History,
+    /// Label for the next recorded session.
+    session_label: String,
+    /// Comma-separated tags for the next recorded session.
+    session_tags: String,
+    /// Only sessions with this tag are listed, if set.
+    session_filter: Option<String>,
+    /// Labels for the session filter: all sessions, then each tag in use.
+    session_filter_labels: Vec<String>,
+    /// Only sessions whose label or tags contain this text are listed.
+    session_search: Filter,
+    /// The session being edited, with its label and tags text.
+    editing_session: Option<(usize, String, String)>,
+    /// Laps selected for deleting or copying together.
+    lap_selection: Selection,
+    /// Page of the lap list shown, counting from the newest laps.
+    lap_page: usize,
+    /// Page of the session history shown, counting from the newest sessions.
+    session_page: usize,
+    /// Sessions selected for deleting or exporting together.
+    session_selection: Selection,
+    /// Number of stopwatch milestones announced since it was last reset.
+    milestones_announced: u64,
+    /// Labels for the milestone choices: never, then each of `MILESTONE_MINUTES`.
+    milestone_labels: Vec<String>,
+    /// Whether the stopwatch was paused because the user went idle.
+    watch_paused_for_idle: bool,
+    /// Labels for the idle timeout choices in `IDLE_PAUSE_MINUTES`.
+    idle_minute_labels: Vec<String>,
+    /// Localized weekday names, starting from Monday.
+    weekday_labels: Vec<String>,
+    /// Tells the media player whether the active timer is running, once registered.
+    media_status: Option<tokio::sync::mpsc::UnboundedSender<bool>>,
+    /// Layer-shell surface showing the countdown above other windows, if shown.
+    overlay: Option<window::Id>,
+    /// The second of the countdown last spoken aloud, so each is spoken once.
+    countdown_spoken: Option<u64>,
+    /// Labels for the voice choices, in the order of `speech::Voice::ALL`.
+    voice_labels: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ToggleWatch,
+    Lap,
+    ResetWatch,
+    Tab(segmented_button::Entity),
+    CountdownInput(String),
+    SetCountdown,
+    CommandInput(String),
+    ConfirmCommand,
+    SaveCommand,
+    CancelCommand,
+    RemoveCommand,
+    CommandFinished(Result<(), Error>),
+    SpokenCountdown(bool),
+    SpeechVoice(usize),
+    SpeechRate(i32),
+    ToggleCountdown,
+    ResetCountdown,
+    NewAlarmHour(u32),
+    NewAlarmMinute(u32),
+    AddAlarm,
+    EnableAlarm(usize, bool),
+    RemoveAlarm(usize),
+    MilestoneMinutes(Option<u32>),
+    MediaKeys(bool),
+    ShowOverlay(bool),
+    Media(mpris::Event),
+    SessionLabelInput(String),
+    SessionTagsInput(String),
+    FilterSessions(usize),
+    SessionSearchInput(String),
+    ApplySessionSearch(u64),
+    ClearSessionSearch,
+    EditSession(usize),
+    EditSessionLabel(String),
+    EditSessionTags(String),
+    CommitSession,
+    LapSelection(selection::Action),
+    LapPage(usize),
+    SessionPage(usize),
+    SessionSelection(selection::Action),
+    SessionsExported(Option<Result<usize, Error>>),
+    Transferred(io::Transferred),
+    ImportSessions,
+    SessionsImported(Option<Result<Vec<sessions::Session>, Error>>),
+    PauseWhenIdle(bool),
+    IdlePauseMinutes(u32),
+    Idle(bool),
+    Tick,
+}
+
+/// The tabs of the watch page.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Tab {
+    Stopwatch,
+    Countdown,
+    Alarms,
+}
+
+impl Tab {
+    fn label(self) -> String {
+        match self {
+            Tab::Stopwatch => fl!("stopwatch"),
+            Tab::Countdown => fl!("countdown"),
+            Tab::Alarms => fl!("alarms"),
+        }
+    }
+}
+
+impl WatchPage {
+    pub fn new(config: &Config) -> Self {
+        let countdown_length = match config.countdown_secs {
+            0 => DEFAULT_COUNTDOWN,
+            secs => Duration::from_secs(secs),
+        };
+
+        let mut timers = timer::Service::default();
+        let watch = timers.add(timer::Kind::Stopwatch);
+        let countdown = timers.add(timer::Kind::Countdown(countdown_length));
+
+        let tabs = segmented_button::SingleSelectModel::builder()
+            .insert(|b| b.text(Tab::Stopwatch.label()).data(Tab::Stopwatch).activate())
+            .insert(|b| b.text(Tab::Countdown.label()).data(Tab::Countdown))
+            .insert(|b| b.text(Tab::Alarms.label()).data(Tab::Alarms))
+            .build();
+
+        let sessions = sessions::History::load();
+
+        Self {
+            timers,
+            tabs,
+            watch,
+            countdown,
+            countdown_input: timer::format_hms(countdown_length),
+            command_input: config.countdown_command.clone().unwrap_or_default(),
+            confirming_command: false,
+            new_alarm: (7, 0),
+            alarms_checked: Local::now(),
+            watch_started: None,
+            session_filter_labels: session_filter_labels(&sessions),
+            sessions,
+            session_label: String::new(),
+            session_tags: String::new(),
+            session_filter: None,
+            session_search: Filter::default(),
+            editing_session: None,
+            lap_selection: Selection::default(),
+            lap_page: 0,
+            session_page: 0,
+            session_selection: Selection::default(),
+            milestones_announced: 0,
+            milestone_labels: milestone_labels(),
+            watch_paused_for_idle: false,
+            idle_minute_labels: idle_minute_labels(),
+            weekday_labels: super::weekday_labels(),
+            media_status: None,
+            overlay: None,
+            countdown_spoken: None,
+            voice_labels: voice_labels(),
+        }
+    }
+
+    /// The stopwatch, for summarizing in reports.
+    pub fn stopwatch(&self) -> Option<&timer::Timer> {
+        self.timers.get(self.watch)
+    }
+
+    /// Describes the overlay surface, if `id` is the overlay.
+    pub fn overlay_view(&self, id: window::Id) -> Option<Element<'_, Message>> {
+        if self.overlay != Some(id) {
+            return None;
+        }
+
+        let remaining = self
+            .timers
+            .get(self.countdown)
+            .and_then(timer::Timer::remaining)
+            .unwrap_or_default();
+
+        Some(
+            widget::text::title1(timer::format_hms(remaining))
+                .apply(widget::container)
+                .padding(cosmic::theme::spacing().space_s)
+                .class(cosmic::theme::Container::Card)
+                .into(),
+        )
+    }
+
+    /// The timer media keys control: the countdown on its tab, and otherwise the stopwatch.
+    fn active_timer(&self) -> timer::Id {
+        match self.tabs.active_data::<Tab>() {
+            Some(Tab::Countdown) => self.countdown,
+            _ => self.watch,
+        }
+    }
+
+    /// Tells the media player whether the active timer is running.
+    fn sync_media_status(&self) {
+        if let Some(status) = &self.media_status {
+            _ = status.send(self.timers.is_running(self.active_timer()));
+        }
+    }
+
+    /// Adds the stopwatch run which just stopped to the session history.
+    fn record_session(&mut self) {
+        let Some(started) = self.watch_started.take() else {
+            return;
+        };
+
+        self.sessions.sessions.push(sessions::Session {
+            started: started.timestamp(),
+            duration_secs: self.timers.elapsed(self.watch).unwrap_or_default().as_secs(),
+            label: self.session_label.trim().to_owned(),
+            tags: sessions::parse_tags(&self.session_tags),
+        });
+
+        self.sessions_changed();
+    }
+
+    /// Saves the session history and refreshes what is derived from it.
+    fn sessions_changed(&mut self) {
+        self.sessions.save();
+        self.session_filter_labels = session_filter_labels(&self.sessions);
+    }
+
+    /// The stopwatch's Lap, Reset, and Start or Pause buttons.
+    fn stopwatch_controls(&self) -> Element<'_, Message> {
+        let running = self.timers.is_running(self.watch);
+        let has_time = self.timers.elapsed(self.watch).unwrap_or_default().as_secs() > 0
+            || self.stopwatch().is_some_and(|timer| !timer.laps().is_empty());
+
+        widgets::wrapping_row(vec![
+            widget::button::text(fl!("lap")).on_press_maybe(running.then_some(Message::Lap)).into(),
+            widget::button::text(fl!("reset"))
+                .on_press_maybe(has_time.then_some(Message::ResetWatch))
+                .into(),
+            widget::button::text(match (running, has_time) {
+                (true, _) => "Pause",
+                (false, true) => "Resume",
+                (false, false) => "Start",
+            })
+            .on_press(Message::ToggleWatch)
+            .into(),
+        ])
+    }
+
+    /// The stopwatch on its own, for a window detached from the main one.
+    pub fn detached_view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let elapsed = self.timers.elapsed(self.watch).unwrap_or_default();
+
+        widget::column::with_capacity(2)
+            .push(widget::text::title1(config.watch_format.format(elapsed)))
+            .push(self.stopwatch_controls())
+            .spacing(cosmic::theme::spacing().space_s)
+            .align_x(Horizontal::Center)
+            .apply(widget::container)
+            .center(Length::Fill)
+            .padding(cosmic::theme::spacing().space_s)
+            .into()
+    }
+
+    /// The stopwatch tab of the watch page.
+    fn stopwatch_view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let elapsed = self.timers.elapsed(self.watch).unwrap_or_default();
+        let counter_label = ["Watch: ", config.watch_format.format(elapsed).as_str()].concat();
+
+        // A flex item moves the buttons below the time once both no longer fit side by side.
+        let section = cosmic::widget::settings::section()
+            .add(cosmic::widget::settings::item::builder(counter_label).flex_control(self.stopwatch_controls()));
+
+        let milestone = config
+            .milestone_minutes
+            .and_then(|minutes| MILESTONE_MINUTES.iter().position(|&m| m == minutes))
+            .map_or(0, |idx| idx + 1);
+
+        let mut idle_section = cosmic::widget::settings::section()
+            .add(
+                cosmic::widget::settings::item::builder(fl!("milestones")).control(widget::dropdown(
+                    &self.milestone_labels,
+                    Some(milestone),
+                    |idx| Message::MilestoneMinutes(idx.checked_sub(1).map(|idx| MILESTONE_MINUTES[idx])),
+                )),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("media-keys"))
+                    .description(fl!("media-keys-description"))
+                    .toggler(config.media_keys, Message::MediaKeys),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("pause-when-idle"))
+                    .toggler(config.idle_pause_minutes.is_some(), Message::PauseWhenIdle),
+            );
+
+        if let Some(minutes) = config.idle_pause_minutes {
+            let selected = IDLE_PAUSE_MINUTES.iter().position(|&m| m == minutes);
+
+            idle_section = idle_section.add(
+                cosmic::widget::settings::item::builder(fl!("idle-timeout")).control(
+                    widget::dropdown(&self.idle_minute_labels, selected, |idx| {
+                        Message::IdlePauseMinutes(IDLE_PAUSE_MINUTES[idx])
+                    }),
+                ),
+            );
+        }
+
+        let next_session = cosmic::widget::settings::section()
+            .title(fl!("next-session"))
+            .add(
+                cosmic::widget::settings::item::builder(fl!("session-label")).control(
+                    widget::text_input(fl!("session-label"), self.session_label.as_str())
+                        .on_input(Message::SessionLabelInput),
+                ),
+            )
+            .add(
+                cosmic::widget::settings::item::builder(fl!("session-tags")).control(
+                    widget::text_input(fl!("session-tags-placeholder"), self.session_tags.as_str())
+                        .on_input(Message::SessionTagsInput),
+                ),
+            );
+
+        let mut column = widget::column::with_capacity(6).push(section);
+
+        if let Some(laps) = self.laps_view() {
+            column = column.push(laps);
+        }
+
+        column
+            .push(next_session)
+            .push(idle_section)
+            .push(self.week_view())
+            .push(self.sessions_view())
+            .spacing(space_s)
+            .apply(widget::scrollable)
+            .into()
+    }
+
+    /// The stopwatch's laps, newest first, with each split and the total at the time.
+    fn laps_view(&self) -> Option<Element<'_, Message>> {
+        let laps = self.stopwatch()?.laps();
+
+        if laps.is_empty() {
+            return None;
+        }
+
+        let page = pager::clamp_page(self.lap_page, laps.len());
+        let mut list = cosmic::widget::settings::section();
+
+        for (num, &total) in laps
+            .iter()
+            .enumerate()
+            .rev()
+            .skip(page * pager::PAGE_SIZE)
+            .take(pager::PAGE_SIZE)
+        {
+            let previous = num.checked_sub(1).map_or(Duration::ZERO, |prev| laps[prev]);
+
+            let item = cosmic::widget::settings::item::builder(fl!("lap-id", num = num + 1))
+                .description(fl!("lap-total", time = timer::format_hms(total)))
+                .control(widget::text::body(timer::format_hms(total.saturating_sub(previous))));
+
+            list = list.add(widgets::selectable(&self.lap_selection, num, item, Message::LapSelection));
+        }
+
+        Some(
+            widget::column::with_capacity(3)
+                .push(widgets::selection_bar(&self.lap_selection, laps.len(), Message::LapSelection))
+                .push(widget::scrollable(list).height(Length::Fixed(LAPS_HEIGHT)))
+                .push_maybe(pager::pager(page, laps.len(), Message::LapPage))
+                .spacing(cosmic::theme::spacing().space_s)
+                .into(),
+        )
+    }
+
+    /// Hours tracked on each day of the current week.
+    fn week_view(&self) -> Element<'_, Message> {
+        let totals = self.sessions.week_totals(Local::now().date_naive());
+
+        let bars = totals
+            .iter()
+            .zip(&self.weekday_labels)
+            .map(|(total, weekday)| {
+                let hours = total.as_secs_f32() / 3600.0;
+
+                widgets::Bar {
+                    label: weekday.chars().take(3).collect(),
+                    caption: format!("{hours:.1}"),
+                    value: hours,
+                }
+            })
+            .collect();
+
+        widget::column::with_capacity(2)
+            .push(widget::text::title4(fl!("this-week")))
+            .push(widgets::bar_chart(bars))
+            .spacing(cosmic::theme::spacing().space_s)
+            .into()
+    }
+
+    /// Recorded sessions, newest first, limited to the selected tag.
+    fn sessions_view(&self) -> Element<'_, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let selected = match &self.session_filter {
+            Some(tag) => self.sessions.tags().iter().position(|t| t == tag).map(|idx| idx + 1),
+            None => Some(0),
+        };
+
+        let shown: Vec<usize> = self.shown_sessions().collect();
+
+        let header = widget::row::with_capacity(2)
+            .push(widget::text::title4(fl!("session-history")).width(Length::Fill))
+            .push(widget::dropdown(&self.session_filter_labels, selected, Message::FilterSessions))
+            .align_y(Vertical::Center)
+            .spacing(space_s);
+
+        let page = pager::clamp_page(self.session_page, shown.len());
+        let mut list = cosmic::widget::settings::section();
+
+        // Only one page of rows is built, however long the history grows.
+        for &idx in shown.iter().rev().skip(page * pager::PAGE_SIZE).take(pager::PAGE_SIZE) {
+            let session = &self.sessions.sessions[idx];
+
+            let started = DateTime::from_timestamp(session.started, 0)
+                .map(|started| started.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+
+            let item: Element<_> = match &self.editing_session {
+                Some((editing, label, tags)) if *editing == idx => widget::row::with_capacity(3)
+                    .push(
+                        widget::text_input(fl!("session-label"), label.as_str())
+                            .on_input(Message::EditSessionLabel)
+                            .on_submit(|_| Message::CommitSession),
+                    )
+                    .push(
+                        widget::text_input(fl!("session-tags-placeholder"), tags.as_str())
+                            .on_input(Message::EditSessionTags)
+                            .on_submit(|_| Message::CommitSession),
+                    )
+                    .push(
+                        widget::button::icon(icons::handle("object-select-symbolic"))
+                            .on_press(Message::CommitSession),
+                    )
+                    .align_y(Vertical::Center)
+                    .spacing(space_s)
+                    .into(),
+                _ => {
+                    let title = if session.label.is_empty() {
+                        fl!("untitled-session")
+                    } else {
+                        session.label.clone()
+                    };
+
+                    let mut description = started;
+
+                    if !session.tags.is_empty() {
+                        description.push_str(" · ");
+                        description.push_str(&session.tags.join(", "));
+                    }
+
+                    cosmic::widget::settings::item::builder(title)
+                        .description(description)
+                        .control(
+                            widget::row::with_capacity(2)
+                                .push(widget::text::body(timer::format_hms(Duration::from_secs(
+                                    session.duration_secs,
+                                ))))
+                                .push(
+                                    widget::button::icon(icons::handle("document-edit-symbolic"))
+                                        .on_press(Message::EditSession(idx)),
+                                )
+                                .align_y(Vertical::Center)
+                                .spacing(space_s),
+                        )
+                        .into()
+                }
+            };
+
+            list = list.add(widgets::selectable(&self.session_selection, idx, item, Message::SessionSelection));
+        }
+
+        let mut column = widget::column::with_capacity(5).push(header).spacing(space_s);
+
+        if !self.sessions.sessions.is_empty() {
+            column = column.push(widgets::filter_input(
+                &self.session_search,
+                fl!("search-sessions"),
+                shown.len(),
+                self.sessions.sessions.len(),
+                Message::SessionSearchInput,
+                Message::ClearSessionSearch,
+            ));
+        }
+
+        if shown.is_empty() {
+            let empty = if self.sessions.sessions.is_empty() {
+                fl!("no-sessions")
+            } else {
+                fl!("no-matching-sessions")
+            };
+
+            column = column.push(widgets::empty_state(
+                icons::handle("document-open-recent-symbolic"),
+                empty,
+            ));
+        } else {
+            column = column
+                .push(widgets::selection_bar(&self.session_selection, shown.len(), Message::SessionSelection))
+                .push(list)
+                .push_maybe(pager::pager(page, shown.len(), Message::SessionPage));
+        }
+
+        column.into()
+    }
+
+    /// Indices of the recorded sessions shown by the tag filter and search, oldest first.
+    fn shown_sessions(&self) -> impl Iterator<Item = usize> + '_ {
+        self.sessions
+            .sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, session)| self.session_filter.as_ref().is_none_or(|tag| session.has_tag(tag)))
+            .filter(|(_, session)| {
+                let tags = session.tags.iter().map(String::as_str);
+                self.session_search.matches(std::iter::once(session.label.as_str()).chain(tags))
+            })
+            .map(|(idx, _)| idx)
+    }
+
+    /// The countdown tab of the watch page.
+    fn countdown_view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let countdown = self.timers.get(self.countdown);
+        let remaining = countdown.and_then(timer::Timer::remaining).unwrap_or_default();
+        let running = countdown.is_some_and(timer::Timer::is_running);
+
+        let controls = widget::row::with_capacity(2)
+            .push(
+                widget::button::suggested(if running { fl!("pause") } else { fl!("start") })
+                    .on_press(Message::ToggleCountdown),
+            )
+            .push(widget::button::standard(fl!("reset")).on_press(Message::ResetCountdown))
+            .spacing(space_s);
+
+        let length = widget::text_input(fl!("countdown-length"), self.countdown_input.as_str())
+            .on_input(Message::CountdownInput)
+            .on_submit(|_| Message::SetCountdown);
+
+        let mut timer = cosmic::widget::settings::section()
+            .add(
+                cosmic::widget::settings::item::builder(timer::format_hms(remaining)).control(controls),
+            )
+            .add(cosmic::widget::settings::item::builder(fl!("countdown-length")).control(length));
+
+        if *ON_WAYLAND {
+            timer = timer.add(
+                cosmic::widget::settings::item::builder(fl!("show-overlay"))
+                    .description(fl!("show-overlay-description"))
+                    .toggler(self.overlay.is_some(), Message::ShowOverlay),
+            );
+        }
+
+        let saved = config.countdown_command.as_deref();
+        let changed = !self.command_input.trim().is_empty() && Some(self.command_input.trim()) != saved;
+
+        let command = widget::text_input(fl!("command-placeholder"), self.command_input.as_str())
+            .on_input(Message::CommandInput)
+            .on_submit(|_| Message::ConfirmCommand);
+
+        let command_controls = widgets::wrapping_row(vec![
+            command.into(),
+            widget::button::standard(fl!("set-command"))
+                .on_press_maybe(changed.then_some(Message::ConfirmCommand))
+                .into(),
+            widget::button::icon(icons::handle("edit-delete-symbolic"))
+                .on_press_maybe(saved.is_some().then_some(Message::RemoveCommand))
+                .into(),
+        ]);
+
+        // Sandboxed commands only see the sandbox's programs, which surprises people.
+        let description = if *IN_SANDBOX {
+            fl!("run-command-sandboxed")
+        } else {
+            fl!("run-command-description")
+        };
+
+        let advanced = cosmic::widget::settings::section().title(fl!("when-finished")).add(
+            cosmic::widget::settings::item::builder(fl!("run-command"))
+                .description(description)
+                .flex_control(command_controls),
+        );
+
+        widget::column::with_capacity(3)
+            .push(timer)
+            .push(advanced)
+            .push(self.speech_view(config))
+            .spacing(space_s)
+            .into()
+    }
+
+    /// Settings for speaking the end of a countdown aloud.
+    fn speech_view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        // Without speech built in, the setting is shown off and cannot be turned on.
+        let available = speech::is_available();
+        let enabled = config.spoken_countdown && available;
+
+        let description = if available {
+            fl!("spoken-countdown-description")
+        } else {
+            fl!("speech-unavailable")
+        };
+
+        let speak = cosmic::widget::settings::item::builder(fl!("spoken-countdown")).description(description);
+        let toggler = widget::toggler(enabled).on_toggle_maybe(available.then_some(Message::SpokenCountdown));
+
+        let mut section = cosmic::widget::settings::section()
+            .title(fl!("speech"))
+            .add(speak.control(toggler));
+
+        if enabled {
+            let voice = speech::Voice::ALL.iter().position(|&voice| voice == config.speech_voice);
+
+            section = section
+                .add(
+                    cosmic::widget::settings::item::builder(fl!("speech-voice"))
+                        .control(widget::dropdown(&self.voice_labels, voice, Message::SpeechVoice)),
+                )
+                .add(
+                    cosmic::widget::settings::item::builder(fl!("speech-rate")).control(
+                        widget::slider(-100..=100, config.speech_rate, Message::SpeechRate)
+                            .step(10)
+                            .width(Length::Fixed(SPEECH_RATE_WIDTH)),
+                    ),
+                );
+        }
+
+        section.into()
+    }
+
+    /// The alarms tab of the watch page.
+    fn alarms_view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let space_s = cosmic::theme::spacing().space_s;
+        let mut alarms = cosmic::widget::settings::section();
+
+        for (idx, alarm) in config.alarms.iter().enumerate() {
+            let controls = widget::row::with_capacity(2)
+                .push(widget::toggler(alarm.enabled).on_toggle(move |enabled| Message::EnableAlarm(idx, enabled)))
+                .push(
+                    widget::button::icon(icons::handle("edit-delete-symbolic"))
+                        .on_press(Message::RemoveAlarm(idx)),
+                )
+                .align_y(Vertical::Center)
+                .spacing(space_s);
+
+            alarms = alarms.add(
+                cosmic::widget::settings::item::builder(format!("{:02}:{:02}", alarm.hour, alarm.minute))
+                    .control(controls),
+            );
+        }
+
+        let (hour, minute) = self.new_alarm;
+        let new_alarm = widget::row::with_capacity(3)
+            .push(widget::dropdown(&HOURS[..], Some(hour as usize), |hour| {
+                Message::NewAlarmHour(hour as u32)
+            }))
+            .push(widget::dropdown(
+                &MINUTES[..],
+                MINUTES.iter().position(|m| m.parse() == Ok(minute)),
+                |idx| Message::NewAlarmMinute(MINUTES[idx].parse().unwrap_or(0)),
+            ))
+            .push(widget::button::standard(fl!("add-alarm")).on_press(Message::AddAlarm))
+            .align_y(Vertical::Center)
+            .spacing(space_s);
+
+        let mut column = widget::column::with_capacity(2).spacing(space_s);
+
+        if config.alarms.is_empty() {
+            column = column.push(widgets::empty_state(
+                icons::handle("alarm-symbolic"),
+                fl!("no-alarms"),
+            ));
+        } else {
+            column = column.push(alarms);
+        }
+
+        column.push(new_alarm).into()
+    }
+}
+
+impl Page for WatchPage {
+    type Message = Message;
+
+    fn restore_state(&mut self, state: &State, config: &Config) {
+        let state = &state.watch;
+
+        self.timers.restore(self.watch, state.stopwatch, config.resume_timers);
+        let elapsed = self.timers.elapsed(self.watch).unwrap_or_default();
+        let milestone_secs = config.milestone_minutes.map_or(0, |minutes| u64::from(minutes) * 60);
+
+        self.watch_started = (!elapsed.is_zero())
+            .then(|| Local::now() - chrono::TimeDelta::from_std(elapsed).unwrap_or_default());
+        self.milestones_announced = elapsed.as_secs().checked_div(milestone_secs).unwrap_or(0);
+
+        if !state.countdown_input.is_empty() {
+            self.countdown_input.clone_from(&state.countdown_input);
+        }
+
+        self.session_label.clone_from(&state.session_label);
+        self.session_tags.clone_from(&state.session_tags);
+    }
+
+    fn save_state(&self, state: &mut State) {
+        if let Some(saved) = self.timers.save(self.watch) {
+            state.watch.stopwatch = saved;
+        }
+
+        state.watch.countdown_input.clone_from(&self.countdown_input);
+        state.watch.session_label.clone_from(&self.session_label);
+        state.watch.session_tags.clone_from(&self.session_tags);
+    }
+
+    fn relabel(&mut self) {
+        let tabs: Vec<_> = self.tabs.iter().collect();
+
+        for id in tabs {
+            if let Some(&tab) = self.tabs.data::<Tab>(id) {
+                self.tabs.text_set(id, tab.label());
+            }
+        }
+
+        self.session_filter_labels = session_filter_labels(&self.sessions);
+        self.milestone_labels = milestone_labels();
+        self.idle_minute_labels = idle_minute_labels();
+        self.weekday_labels = super::weekday_labels();
+        self.voice_labels = voice_labels();
+    }
+
+    fn view<'a>(&'a self, config: &'a Config, _narrow: bool) -> Element<'a, Message> {
+        let tab: Element<_> = match self.tabs.active_data::<Tab>() {
+            Some(Tab::Countdown) => self.countdown_view(config),
+            Some(Tab::Alarms) => self.alarms_view(config),
+            Some(Tab::Stopwatch) | None => self.stopwatch_view(config),
+        };
+
+        widget::column::with_capacity(3)
+            .push(super::header(fl!("welcome"), 1))
+            .push(widget::tab_bar::horizontal(&self.tabs).on_activate(Message::Tab))
+            .push(tab)
+            .spacing(cosmic::theme::spacing().space_s)
+            .height(Length::Fill)
+            .into()
+    }
+
+    fn update(&mut self, message: Message, ctx: &mut Context<'_>) -> Task<Message> {
+        match message {
+            Message::Tick => {
+                // Elapsed time is read from the timer service when drawing.
+                if let Some(minutes) = ctx.config.milestone_minutes.filter(|&m| m > 0) {
+                    let elapsed = self.timers.elapsed(self.watch).unwrap_or_default();
+                    let reached = elapsed.as_secs() / (u64::from(minutes) * 60);
+
+                    if reached > self.milestones_announced {
+                        self.milestones_announced = reached;
+                        ctx.toast(fl!("milestone-reached", time = timer::format_hms(elapsed)));
+                    }
+                }
+
+                let mut task = Task::none();
+                let countdown = self.timers.get(self.countdown).filter(|countdown| countdown.is_running());
+
+                // Each of the last seconds is spoken as it starts.
+                if let Some(remaining) = countdown.and_then(timer::Timer::remaining) {
+                    let second = remaining.as_secs_f64().ceil() as u64;
+
+                    if ctx.config.spoken_countdown
+                        && (1..=SPOKEN_SECONDS).contains(&second)
+                        && self.countdown_spoken != Some(second)
+                    {
+                        self.countdown_spoken = Some(second);
+                        ctx.speak(second.to_string());
+                    }
+                }
+
+                if self.timers.take_finished().contains(&self.countdown) {
+                    ctx.emit(dbus::Event::TimerFinished("countdown"));
+                    self.sync_media_status();
+                    ctx.announce(fl!("countdown-finished"));
+                    ctx.play(SoundEvent::TimerFinished);
+                    self.countdown_spoken = None;
+
+                    if ctx.config.spoken_countdown {
+                        ctx.speak(fl!("countdown-finished"));
+                    }
+
+                    if let Some(command) = ctx.config.countdown_command.clone() {
+                        task = cosmic::task::future(async move {
+                            Message::CommandFinished(run_command(command).await)
+                        });
+                    }
+                }
+
+                let now = Local::now();
+
+                let ringing: Vec<_> = ctx
+                    .config
+                    .alarms
+                    .iter()
+                    .filter(|alarm| alarm.enabled && alarm.rang_between(self.alarms_checked, now))
+                    .map(|alarm| format!("{:02}:{:02}", alarm.hour, alarm.minute))
+                    .collect();
+
+                if !ringing.is_empty() {
+                    ctx.play(SoundEvent::Alarm);
+                }
+
+                for time in ringing {
+                    ctx.toast(fl!("alarm-ringing", time = time));
+                }
+
+                self.alarms_checked = now;
+                return task;
+            }
+
+            Message::CommandInput(input) => self.command_input = input,
+
+            Message::ConfirmCommand => {
+                let command = self.command_input.trim();
+                let saved = ctx.config.countdown_command.as_deref();
+                self.confirming_command = !command.is_empty() && Some(command) != saved;
+            }
+
+            Message::SaveCommand => {
+                self.confirming_command = false;
+                self.command_input = self.command_input.trim().to_string();
+                ctx.config.countdown_command = Some(self.command_input.clone());
+                ctx.save_config();
+            }
+
+            Message::CancelCommand => self.confirming_command = false,
+
+            Message::RemoveCommand => {
+                self.command_input.clear();
+                ctx.config.countdown_command = None;
+                ctx.save_config();
+            }
+
+            Message::CommandFinished(result) => {
+                if let Err(why) = result {
+                    ctx.fail(fl!("command-failed", reason = why.to_string()), why);
+                }
+            }
+
+            Message::Tab(entity) => {
+                self.tabs.activate(entity);
+                self.sync_media_status();
+            }
+
+            Message::CountdownInput(input) => {
+                self.countdown_input = input;
+            }
+
+            Message::SetCountdown => {
+                if let Some(length) = timer::parse_hms(&self.countdown_input).filter(|d| !d.is_zero()) {
+                    self.timers.replace(self.countdown, timer::Kind::Countdown(length));
+                    self.countdown_input = timer::format_hms(length);
+                    ctx.config.countdown_secs = length.as_secs();
+                    ctx.save_config();
+                }
+            }
+
+            Message::ToggleCountdown => {
+                if self.timers.is_running(self.countdown) {
+                    self.timers.pause(self.countdown);
+                } else {
+                    if self.timers.get(self.countdown).is_some_and(timer::Timer::is_finished) {
+                        self.timers.reset(self.countdown);
+                    }
+                    self.timers.start(self.countdown);
+                    ctx.emit(dbus::Event::TimerStarted("countdown"));
+                }
+
+                self.sync_media_status();
+            }
+
+            Message::ResetCountdown => {
+                self.timers.reset(self.countdown);
+                self.countdown_spoken = None;
+            }
+
+            Message::SpokenCountdown(enabled) => {
+                ctx.config.spoken_countdown = enabled;
+                ctx.save_config();
+            }
+
+            Message::SpeechVoice(idx) => {
+                if let Some(&voice) = speech::Voice::ALL.get(idx) {
+                    ctx.config.speech_voice = voice;
+                    ctx.save_config();
+                    // Lets the user hear the voice they chose.
+                    ctx.speak(fl!("countdown"));
+                }
+            }
+
+            Message::SpeechRate(rate) => {
+                ctx.config.speech_rate = rate;
+                ctx.save_config();
+            }
+
+            Message::NewAlarmHour(hour) => {
+                self.new_alarm.0 = hour;
+            }
+
+            Message::NewAlarmMinute(minute) => {
+                self.new_alarm.1 = minute;
+            }
+
+            Message::AddAlarm => {
+                let (hour, minute) = self.new_alarm;
+                ctx.config.alarms.push(timer::Alarm {
+                    hour,
+                    minute,
+                    enabled: true,
+                });
+                ctx.config.alarms.sort_by_key(|alarm| (alarm.hour, alarm.minute));
+                ctx.save_config();
+            }
+
+            Message::EnableAlarm(idx, enabled) => {
+                if let Some(alarm) = ctx.config.alarms.get_mut(idx) {
+                    alarm.enabled = enabled;
+                    ctx.save_config();
+                }
+            }
+
+            Message::RemoveAlarm(idx) => {
+                if idx < ctx.config.alarms.len() {
+                    let alarm = ctx.config.alarms.remove(idx);
+                    // Written to disk only once the removal can no longer be undone.
+                    ctx.removed(Removed::Alarm { index: idx, alarm });
+                }
+            }
+
+            Message::SessionLabelInput(label) => {
+                self.session_label = label;
+            }
+
+            Message::SessionTagsInput(tags) => {
+                self.session_tags = tags;
+            }
+
+            Message::FilterSessions(idx) => {
+                self.session_filter = idx
+                    .checked_sub(1)
+                    .and_then(|idx| self.sessions.tags().into_iter().nth(idx));
+                self.session_page = 0;
+                self.session_selection.retain(self.shown_sessions().collect::<Vec<_>>());
+            }
+
+            Message::LapPage(page) => {
+                self.lap_page = page;
+            }
+
+            Message::SessionPage(page) => {
+                self.session_page = page;
+            }
+
+            Message::SessionSearchInput(input) => {
+                return self.session_search.edit(input, Message::ApplySessionSearch);
+            }
+
+            Message::ApplySessionSearch(generation) => {
+                self.session_search.apply(generation);
+                self.session_page = 0;
+                self.session_selection.retain(self.shown_sessions().collect::<Vec<_>>());
+            }
+
+            Message::ClearSessionSearch => {
+                self.session_search.clear();
+                self.session_page = 0;
+            }
+
+            Message::EditSession(idx) => {
+                if let Some(session) = self.sessions.sessions.get(idx) {
+                    self.editing_session = Some((idx, session.label.clone(), session.tags.join(", ")));
+                }
+            }
+
+            Message::EditSessionLabel(label) => {
+                if let Some((_, editing, _)) = &mut self.editing_session {
+                    *editing = label;
+                }
+            }
+
+            Message::EditSessionTags(tags) => {
+                if let Some((_, _, editing)) = &mut self.editing_session {
+                    *editing = tags;
+                }
+            }
+
+            Message::CommitSession => {
+                if let Some((idx, label, tags)) = self.editing_session.take() {
+                    if let Some(session) = self.sessions.sessions.get_mut(idx) {
+                        session.label = label.trim().to_owned();
+                        session.tags = sessions::parse_tags(&tags);
+                        self.sessions_changed();
+                    }
+                }
+            }
+
+            Message::LapSelection(action) => {
+                let laps = self.stopwatch().map_or(0, |timer| timer.laps().len());
+
+                match self.lap_selection.update(action, 0..laps) {
+                    Some(Bulk::Delete(indices)) => self.timers.remove_laps(self.watch, &indices),
+                    Some(Bulk::Export(indices)) => {
+                        let Some(laps) = self.stopwatch().map(timer::Timer::laps) else {
+                            return Task::none();
+                        };
+
+                        let text: String = indices
+                            .into_iter()
+                            .map(|num| {
+                                let previous = num.checked_sub(1).map_or(Duration::ZERO, |prev| laps[prev]);
+                                format!(
+                                    "{}\t{}\t{}\n",
+                                    fl!("lap-id", num = num + 1),
+                                    timer::format_hms(laps[num].saturating_sub(previous)),
+                                    timer::format_hms(laps[num]),
+                                )
+                            })
+                            .collect();
+
+                        ctx.toast(fl!("laps-copied"));
+                        return clipboard::write(text);
+                    }
+                    None => {}
+                }
+            }
+
+            Message::SessionSelection(action) => {
+                let shown: Vec<usize> = self.shown_sessions().collect();
+
+                match self.session_selection.update(action, shown) {
+                    Some(Bulk::Delete(indices)) => {
+                        for idx in indices.into_iter().rev() {
+                            self.sessions.sessions.remove(idx);
+                        }
+
+                        self.editing_session = None;
+                        self.sessions_changed();
+                    }
+                    Some(Bulk::Export(indices)) => {
+                        let selected: Vec<_> =
+                            indices.into_iter().map(|idx| self.sessions.sessions[idx].clone()).collect();
+
+                        return Task::future(io::choose_save(fl!("export-sessions"), "sessions.csv")).then(
+                            move |chosen| {
+                                let path = match chosen {
+                                    Ok(Some(path)) => path,
+                                    Ok(None) => return Task::none(),
+                                    Err(why) => return Task::done(Message::SessionsExported(Some(Err(why)))),
+                                };
+
+                                let text = match sessions::render(&path, &selected) {
+                                    Ok(text) => text,
+                                    Err(why) => return Task::done(Message::SessionsExported(Some(Err(why.into())))),
+                                };
+
+                                let count = selected.len();
+
+                                io::task(io::write(path, text), Message::Transferred, move |result| {
+                                    Message::SessionsExported(result.map(|result| result.map(|()| count)))
+                                })
+                            },
+                        );
+                    }
+                    None => {}
+                }
+            }
+
+            Message::SessionsExported(result) => match result {
+                Some(Ok(count)) => ctx.toast(fl!("sessions-exported", count = count)),
+                Some(Err(why)) => ctx.fail(fl!("sessions-export-failed", reason = why.to_string()), why),
+                None => {}
+            },
+
+            Message::Transferred(progress) => ctx.transferred(progress),
+
+            Message::ImportSessions => {
+                return Task::future(io::choose_open(fl!("import-sessions"))).then(|chosen| {
+                    let path = match chosen {
+                        Ok(Some(path)) => path,
+                        Ok(None) => return Task::none(),
+                        Err(why) => return Task::done(Message::SessionsImported(Some(Err(why)))),
+                    };
+
+                    io::task(io::read_to_string(path.clone(), MAX_SESSIONS_SIZE), Message::Transferred, move |result| {
+                        Message::SessionsImported(result.map(|result| {
+                            result.and_then(|text| sessions::parse(&path, &text).map_err(Error::from))
+                        }))
+                    })
+                });
+            }
+
+            Message::SessionsImported(result) => match result {
+                None => {}
+                Some(Ok(imported)) => {
+                    let found = imported.len();
+                    let added = self.sessions.merge(imported);
+                    // Merging sorts the history, so selected indices no longer match.
+                    self.session_selection.cancel();
+                    self.sessions_changed();
+                    ctx.toast(fl!("sessions-imported", added = added, skipped = found - added));
+                }
+                Some(Err(why)) => ctx.fail(fl!("sessions-import-failed", reason = why.to_string()), why),
+            },
+
+            Message::ShowOverlay(show) => {
+                if let Some(id) = self.overlay.take() {
+                    return destroy_layer_surface(id);
+                }
+
+                // Elsewhere there is no surface to show, and the toggle would stay on for nothing.
+                if show && *ON_WAYLAND {
+                    let id = window::Id::unique();
+                    self.overlay = Some(id);
+
+                    return get_layer_surface(SctkLayerSurfaceSettings {
+                        id,
+                        layer: Layer::Overlay,
+                        keyboard_interactivity: KeyboardInteractivity::None,
+                        anchor: Anchor::TOP | Anchor::RIGHT,
+                        size: Some((None, None)),
+                        namespace: String::from("counter-overlay"),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            Message::MediaKeys(enabled) => {
+                ctx.config.media_keys = enabled;
+                ctx.save_config();
+
+                if !enabled {
+                    self.media_status = None;
+                }
+            }
+
+            Message::Media(event) => {
+                let active = self.active_timer();
+                let running = self.timers.is_running(active);
+
+                let toggle = match event {
+                    mpris::Event::Ready(status) => {
+                        self.media_status = Some(status);
+                        false
+                    }
+                    mpris::Event::PlayPause => true,
+                    mpris::Event::Play => !running,
+                    mpris::Event::Pause | mpris::Event::Stop => running,
+                };
+
+                if toggle {
+                    let message = if active == self.countdown {
+                        Message::ToggleCountdown
+                    } else {
+                        Message::ToggleWatch
+                    };
+
+                    return self.update(message, ctx);
+                }
+
+                self.sync_media_status();
+            }
+
+            Message::MilestoneMinutes(minutes) => {
+                ctx.config.milestone_minutes = minutes;
+                self.milestones_announced = self.timers.elapsed(self.watch).unwrap_or_default().as_secs()
+                    / (u64::from(minutes.unwrap_or(0).max(1)) * 60);
+                ctx.save_config();
+            }
+
+            Message::PauseWhenIdle(enabled) => {
+                ctx.config.idle_pause_minutes = enabled.then_some(IDLE_PAUSE_MINUTES[1]);
+                self.watch_paused_for_idle = false;
+                ctx.save_config();
+            }
+
+            Message::IdlePauseMinutes(minutes) => {
+                ctx.config.idle_pause_minutes = Some(minutes);
+                ctx.save_config();
+            }
+
+            Message::Idle(idle) => {
+                if idle && self.timers.is_running(self.watch) {
+                    self.timers.pause(self.watch);
+                    self.watch_paused_for_idle = true;
+                } else if !idle && self.watch_paused_for_idle {
+                    self.timers.start(self.watch);
+                    self.watch_paused_for_idle = false;
+                }
+
+                ctx.save_state();
+                self.sync_media_status();
+            }
+
+            Message::Lap => {
+                if self.timers.is_running(self.watch) {
+                    self.timers.lap(self.watch);
+                }
+            }
+
+            Message::ResetWatch => {
+                // Resetting ends the run, so it is recorded first.
+                self.record_session();
+                self.timers.pause(self.watch);
+                self.timers.reset(self.watch);
+                self.lap_selection.cancel();
+                self.lap_page = 0;
+                self.milestones_announced = 0;
+                self.watch_paused_for_idle = false;
+                ctx.save_state();
+                self.sync_media_status();
+            }
+
+            Message::ToggleWatch => {
+                self.watch_paused_for_idle = false;
+
+                if self.timers.is_running(self.watch) {
+                    self.timers.pause(self.watch);
+                } else {
+                    // Resuming keeps the elapsed time; only a reset starts a new run.
+                    self.watch_started.get_or_insert_with(Local::now);
+                    self.timers.start(self.watch);
+                    ctx.emit(dbus::Event::TimerStarted("stopwatch"));
+                }
+
+                ctx.save_state();
+                self.sync_media_status();
+            }
+        }
+
+        Task::none()
+    }
+
+    /// Asks before saving a command to run, since it runs with the user's permissions.
+    fn dialog(&self) -> Option<Element<'_, Message>> {
+        if !self.confirming_command {
+            return None;
+        }
+
+        Some(
+            widget::dialog()
+                .title(fl!("confirm-command"))
+                .body(fl!("confirm-command-body", command = self.command_input.trim()))
+                .primary_action(widget::button::suggested(fl!("run-when-finished")).on_press(Message::SaveCommand))
+                .secondary_action(widget::button::standard(fl!("cancel")).on_press(Message::CancelCommand))
+                .into(),
+        )
+    }
+
+    fn subscription(&self, config: &Config) -> Subscription<Message> {
+        let mut subscriptions = Vec::new();
+
+        // Receives media key presses while they control the timers.
+        if config.media_keys {
+            subscriptions.push(mpris::serve().map(Message::Media));
+        }
+
+        // Conditionally enables a timer that emits a message every second.
+        if self.timers.any_running() || config.alarms.iter().any(|alarm| alarm.enabled) {
+            subscriptions.push(Subscription::run(|| {
+                iced_futures::stream::channel(1, |mut emitter| async move {
+                    let mut interval = tokio::time::interval(Duration::from_secs(1));
+
+                    loop {
+                        interval.tick().await;
+                        _ = emitter.send(Message::Tick).await;
+                    }
+                })
+            }));
+        }
+
+        // Watches for inactivity while the stopwatch is running or paused by it.
+        if let Some(minutes) = config.idle_pause_minutes {
+            if self.timers.is_running(self.watch) || self.watch_paused_for_idle {
+                let threshold = Duration::from_secs(u64::from(minutes) * 60);
+                subscriptions.push(idle::watch(threshold).map(Message::Idle));
+            }
+        }
+
+        Subscription::batch(subscriptions)
+    }
+}
+
+/// Labels for the milestone choices: off, then each of `MILESTONE_MINUTES`.
+fn milestone_labels() -> Vec<String> {
+    std::iter::once(fl!("milestones-off"))
+        .chain(
+            MILESTONE_MINUTES
+                .iter()
+                .map(|&minutes| fl!("idle-minutes", minutes = minutes)),
+        )
+        .collect()
+}
+
+/// Labels for the voice choices, in the order of `speech::Voice::ALL`.
+fn voice_labels() -> Vec<String> {
+    speech::Voice::ALL
+        .iter()
+        .map(|voice| match voice {
+            speech::Voice::Default => fl!("voice-default"),
+            speech::Voice::Male => fl!("voice-male"),
+            speech::Voice::Female => fl!("voice-female"),
+            speech::Voice::Child => fl!("voice-child"),
+        })
+        .collect()
+}
+
+/// Labels for the inactivity thresholds of `IDLE_PAUSE_MINUTES`.
+fn idle_minute_labels() -> Vec<String> {
+    IDLE_PAUSE_MINUTES
+        .iter()
+        .map(|&minutes| fl!("idle-minutes", minutes = minutes))
+        .collect()
+}
+
+/// Labels for the session filter: all sessions, then each tag in use.
+fn session_filter_labels(sessions: &sessions::History) -> Vec<String> {
+    std::iter::once(fl!("all-sessions")).chain(sessions.tags()).collect()
+}
+
+/// Runs `command` through the shell, as chosen to run when a countdown finishes.
+async fn run_command(command: String) -> Result<(), Error> {
+    let status = tokio::process::Command::new("sh").arg("-c").arg(&command).status().await?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::new(Code::CommandFailed, status.to_string()))
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Fuzzy searching the app's commands, as typed into the command palette.
+
+/// The query typed into the command palette, and the entry highlighted for Enter.
+#[derive(Debug, Default)]
+pub struct Palette {
+    query: String,
+    selected: usize,
+}
+
+impl Palette {
+    /// The text typed into the palette.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Updates the typed text, highlighting the best match again.
+    pub fn set_query(&mut self, query: String) {
+        self.query = query;
+        self.selected = 0;
+    }
+
+    /// Position of the highlighted entry among `len` matches.
+    pub fn selected(&self, len: usize) -> usize {
+        self.selected.min(len.saturating_sub(1))
+    }
+
+    /// Moves the highlight by `offset` entries among `len` matches, wrapping around the ends.
+    pub fn move_selection(&mut self, offset: isize, len: usize) {
+        if len > 0 {
+            let selected = self.selected(len) as isize + offset;
+            self.selected = selected.rem_euclid(len as isize) as usize;
+        }
+    }
+
+    /// The entries whose labels match the query, best match first.
+    ///
+    /// Entries matching equally well keep their order.
+    pub fn filter<T>(&self, entries: impl IntoIterator<Item = (String, T)>) -> Vec<(String, T)> {
+        let mut matches: Vec<_> = entries
+            .into_iter()
+            .filter_map(|(label, entry)| Some((score(&self.query, &label)?, label, entry)))
+            .collect();
+
+        matches.sort_by(|a, b| b.0.cmp(&a.0));
+        matches.into_iter().map(|(_, label, entry)| (label, entry)).collect()
+    }
+}
+
+/// How well `text` matches `query`, if every character of the query appears in
+/// it in order, ignoring case. Higher is better.
+///
+/// Matches earn more for following the previous match directly, and for
+/// starting a word, so that "ep" ranks "Export page" above "Keep".
+pub fn score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = next + text[next..].iter().position(|&c| c == wanted)?;
+
+        score += 1;
+
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 2;
+        }
+
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+
+        previous = Some(found);
+        next = found + 1;
+    }
+
+    Some(score)
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3
+
+//! The guessing game played in a terminal, one guess per line.
+//!
+//! Rounds come from the same [`game`] engine the game page uses, so only the
+//! reading of guesses and the wording of replies live here.
+
+use crate::error::Error;
+use crate::fl;
+use crate::game::{self, Outcome};
+use std::io::{BufRead, Write};
+
+/// Plays `engine` to the end, reading guesses from `input` and answering on
+/// `output`. Typing `q` or closing the input gives up the round.
+///
+/// Returns the number of attempts the number took to find, if it was found.
+pub fn run(
+    mut engine: game::Engine,
+    intro: String,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> Result<Option<u32>, Error> {
+    writeln!(output, "{intro}")?;
+    let mut lines = input.lines();
+
+    while !engine.is_won() && !engine.is_lost() {
+        write!(output, "{} ", fl!("play-prompt"))?;
+        output.flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            // Ends the prompt's line before the shell's comes back.
+            writeln!(output)?;
+            return Ok(None);
+        };
+
+        let line = line.trim();
+
+        if line.eq_ignore_ascii_case("q") || line.eq_ignore_ascii_case("quit") {
+            writeln!(output, "{}", fl!("play-gave-up", secret = engine.secret()))?;
+            return Ok(None);
+        }
+
+        let Ok(guess) = line.parse::<i64>() else {
+            writeln!(output, "{}", fl!("play-not-a-number"))?;
+            continue;
+        };
+
+        if !engine.range().contains(&guess) {
+            let range = engine.range();
+            writeln!(output, "{}", fl!("guess-out-of-range", low = *range.start(), high = *range.end()))?;
+            continue;
+        }
+
+        let reply = match engine.guess(guess) {
+            Outcome::TooLow => fl!("play-higher"),
+            Outcome::TooHigh => fl!("play-lower"),
+            Outcome::Correct => fl!("game-won", attempts = engine.attempts()),
+        };
+        writeln!(output, "{reply}")?;
+
+        if engine.is_lost() {
+            writeln!(output, "{}", fl!("out-of-attempts", secret = engine.secret()))?;
+        } else if let (false, Some(left)) = (engine.is_won(), engine.attempts_left()) {
+            writeln!(output, "{}", fl!("attempts-left", attempts = left))?;
+        }
+    }
+
+    Ok(engine.is_won().then(|| engine.attempts()))
+}
@@ -0,0 +1,352 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Printing text documents through the desktop's print portal.
+//!
+//! Documents are laid out on A4 pages in the PDF base font Helvetica, which
+//! every print system provides, so no fonts need embedding. Characters outside
+//! its Latin character set print as `?`.
+
+use crate::error::{Code, Error};
+use crate::fl;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, Write as _};
+use std::os::unix::fs::OpenOptionsExt;
+use zbus::zvariant::{Fd, Value};
+
+/// A4 paper, in PDF points.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+/// Space kept clear around the text on every side.
+const MARGIN: f32 = 56.0;
+
+/// Approximate advance of a Helvetica character, as a share of the font size.
+///
+/// Real widths vary from a third to almost the whole size; the average keeps
+/// lines wrapped well inside the margins without per-glyph metrics.
+const CHAR_WIDTH: f32 = 0.52;
+
+/// A part of a document, laid out one below the other.
+#[derive(Clone, Debug)]
+pub enum Block {
+    Heading(String),
+    Text(String),
+}
+
+impl Block {
+    fn font_size(&self) -> f32 {
+        match self {
+            Block::Heading(_) => 16.0,
+            Block::Text(_) => 11.0,
+        }
+    }
+
+    fn line_height(&self) -> f32 {
+        self.font_size() * 1.4
+    }
+
+    /// Space left above the block, unless it starts a page.
+    fn space_before(&self) -> f32 {
+        match self {
+            Block::Heading(_) => 12.0,
+            Block::Text(_) => 0.0,
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Block::Heading(text) | Block::Text(text) => text,
+        }
+    }
+}
+
+/// A titled document to print.
+#[derive(Clone, Debug)]
+pub struct Document {
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+/// A line placed on a page, with its baseline measured from the page's bottom.
+struct Line {
+    font_size: f32,
+    baseline: f32,
+    text: String,
+}
+
+impl Document {
+    pub fn new(title: String) -> Self {
+        Self {
+            title,
+            blocks: Vec::new(),
+        }
+    }
+
+    pub fn heading(mut self, text: String) -> Self {
+        self.blocks.push(Block::Heading(text));
+        self
+    }
+
+    pub fn text(mut self, text: String) -> Self {
+        self.blocks.push(Block::Text(text));
+        self
+    }
+
+    /// Wraps every block to the text width and breaks the lines into pages.
+    ///
+    /// A heading is moved to the next page rather than left alone at the
+    /// bottom of one.
+    fn paginate(&self) -> Vec<Vec<Line>> {
+        let top = PAGE_HEIGHT - MARGIN;
+        // Leaves room for the page number below the text.
+        let bottom = MARGIN + 24.0;
+
+        let mut pages = vec![Vec::new()];
+        let mut y = top;
+
+        for block in std::iter::once(Block::Heading(self.title.clone())).chain(self.blocks.iter().cloned()) {
+            let size = block.font_size();
+            let height = block.line_height();
+            let columns = ((PAGE_WIDTH - 2.0 * MARGIN) / (size * CHAR_WIDTH)) as usize;
+            let lines = wrap(block.text(), columns);
+
+            if y < top {
+                y -= block.space_before();
+            }
+
+            let keep = if matches!(block, Block::Heading(_)) { 2 } else { 1 };
+
+            if y - height * keep as f32 < bottom {
+                pages.push(Vec::new());
+                y = top;
+            }
+
+            for text in lines {
+                if y - height < bottom {
+                    pages.push(Vec::new());
+                    y = top;
+                }
+
+                y -= height;
+                pages.last_mut().unwrap().push(Line {
+                    font_size: size,
+                    baseline: y,
+                    text,
+                });
+            }
+        }
+
+        pages
+    }
+
+    /// Renders the document as a PDF file.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let pages = self.paginate();
+        let count = pages.len();
+
+        // Objects 1 to 3 are the catalog, the page tree and the font; each
+        // page then takes two objects, itself and its content stream.
+        let mut objects = vec![
+            String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+            format!(
+                "<< /Type /Pages /Count {count} /Kids [{}] >>",
+                (0..count).map(|idx| format!("{} 0 R", 4 + 2 * idx)).collect::<Vec<_>>().join(" ")
+            ),
+            String::from("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"),
+        ];
+
+        for (idx, lines) in pages.iter().enumerate() {
+            let mut content = String::new();
+
+            for line in lines {
+                _ = writeln!(
+                    content,
+                    "BT /F1 {} Tf {MARGIN} {} Td ({}) Tj ET",
+                    line.font_size,
+                    line.baseline,
+                    escape(&line.text)
+                );
+            }
+
+            let footer = fl!("page-of", page = idx + 1, pages = count);
+            _ = writeln!(content, "BT /F1 9 Tf {MARGIN} {MARGIN} Td ({}) Tj ET", escape(&footer));
+
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+                5 + 2 * idx
+            ));
+            objects.push(content);
+        }
+
+        let mut out = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+
+        for (idx, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            _ = writeln!(out, "{} 0 obj", idx + 1);
+
+            // From the first page on, every other object is a content stream.
+            if idx >= 4 && idx % 2 == 0 {
+                let bytes = encode(object);
+                _ = writeln!(out, "<< /Length {} >>\nstream", bytes.len());
+                out.extend_from_slice(&bytes);
+                out.extend_from_slice(b"endstream");
+            } else {
+                out.extend_from_slice(object.as_bytes());
+            }
+
+            out.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref = out.len();
+        _ = writeln!(out, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1);
+
+        for offset in offsets {
+            _ = writeln!(out, "{offset:010} 00000 n ");
+        }
+
+        _ = writeln!(
+            out,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF",
+            objects.len() + 1
+        );
+
+        out
+    }
+}
+
+/// Breaks `text` into lines of at most `columns` characters, at spaces where
+/// possible. Line breaks in the text are kept.
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let columns = columns.max(1);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            let mut word = word;
+
+            // Words longer than a whole line are split wherever they reach the margin.
+            while word.chars().count() > columns {
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+
+                let split = word.char_indices().nth(columns).map_or(word.len(), |(idx, _)| idx);
+                lines.push(word[..split].to_string());
+                word = &word[split..];
+            }
+
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > columns {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(word);
+        }
+
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Escapes the characters with a meaning inside a PDF string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)")
+}
+
+/// Encodes content for the font's WinAnsi encoding, which matches Latin-1
+/// apart from some punctuation in place of its control characters.
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            '\u{2026}' => 0x85,
+            '\u{2022}' => 0x95,
+            '\u{2013}' => 0x96,
+            '\u{2014}' => 0x97,
+            '\u{2018}' => 0x91,
+            '\u{2019}' => 0x92,
+            '\u{201C}' => 0x93,
+            '\u{201D}' => 0x94,
+            '\u{20AC}' => 0x80,
+            c if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => c as u8,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Sends `document` to the print portal, which asks the user for a printer
+/// and its settings before printing.
+///
+/// Returns once the portal has the document; whether the user then printed
+/// or cancelled is not reported back.
+pub async fn print(document: Document) -> Result<(), Error> {
+    let pdf = document.to_pdf();
+    let file = tokio::task::spawn_blocking(move || spool(&pdf))
+        .await
+        .unwrap_or_else(|why| Err(Error::new(Code::Io, why.to_string())))?;
+
+    let unavailable = |why: zbus::Error| Error::new(Code::Unavailable, why.to_string());
+    let connection = zbus::Connection::session().await.map_err(unavailable)?;
+
+    // The window identifier is left empty; without a token from PreparePrint
+    // in the options, the portal shows its own print dialog first.
+    connection
+        .call_method(
+            Some("org.freedesktop.portal.Desktop"),
+            "/org/freedesktop/portal/desktop",
+            Some("org.freedesktop.portal.Print"),
+            "Print",
+            &(
+                "",
+                document.title.as_str(),
+                Fd::from(std::os::fd::OwnedFd::from(file)),
+                HashMap::<&str, Value<'_>>::new(),
+            ),
+        )
+        .await
+        .map_err(unavailable)?;
+
+    Ok(())
+}
+
+/// Writes `pdf` to a file for the portal, which reads the document from a
+/// file descriptor, and removes the file again so only the descriptor is left.
+///
+/// The file is made in the user's runtime directory, which no one else may
+/// write to, and only ever created anew rather than opened, so a file or link
+/// someone put in its place is never written through.
+fn spool(pdf: &[u8]) -> Result<File, Error> {
+    let dir = dirs::runtime_dir().unwrap_or_else(std::env::temp_dir);
+
+    for attempt in 0..16 {
+        let path = dir.join(format!("counter-print-{}-{attempt}.pdf", std::process::id()));
+
+        let mut file = match OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .custom_flags(libc::O_NOFOLLOW)
+            .open(&path)
+        {
+            Ok(file) => file,
+            Err(why) if why.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(why) => return Err(why.into()),
+        };
+
+        _ = std::fs::remove_file(&path);
+        file.write_all(pdf)?;
+        file.rewind()?;
+        return Ok(file);
+    }
+
+    Err(Error::new(Code::Io, "no file could be created for printing"))
+}
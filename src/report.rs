@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Renders a shareable summary of the application's state.
+
+use crate::fl;
+use crate::print::Document;
+use crate::timer;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// The document format a report is rendered to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    /// Picks the format matching a file's extension, defaulting to Markdown.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => {
+                Format::Html
+            }
+            _ => Format::Markdown,
+        }
+    }
+}
+
+/// A snapshot of the values included in a report.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub stopwatch: Duration,
+    pub laps: Vec<Duration>,
+    /// Name and subtotal of each counter group.
+    pub counters: Vec<(String, i64)>,
+    pub game_attempts: u32,
+}
+
+impl Report {
+    fn grand_total(&self) -> i64 {
+        self.counters.iter().map(|(_, subtotal)| *subtotal).fold(0, i64::saturating_add)
+    }
+
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Markdown => self.to_markdown(),
+            Format::Html => self.to_html(),
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+
+        _ = writeln!(out, "# {}\n", fl!("report-title"));
+        _ = writeln!(out, "## {}\n", fl!("report-timer"));
+        _ = writeln!(out, "- {}: {}", fl!("report-elapsed"), timer::format_hms(self.stopwatch));
+
+        for (num, lap) in self.laps.iter().enumerate() {
+            _ = writeln!(out, "- {}: {}", fl!("lap-id", num = num + 1), timer::format_hms(*lap));
+        }
+
+        _ = writeln!(out, "\n## {}\n", fl!("report-counter"));
+        for (name, subtotal) in &self.counters {
+            _ = writeln!(out, "- {name}: {subtotal}");
+        }
+
+        _ = writeln!(out, "- {}: {}", fl!("grand-total"), self.grand_total());
+        _ = writeln!(out, "\n## {}\n", fl!("report-game"));
+        _ = writeln!(out, "- {}: {}", fl!("report-attempts"), self.game_attempts);
+
+        out
+    }
+
+    /// Lays the report out for printing, with the same sections as the exported files.
+    pub fn to_document(&self) -> Document {
+        let mut document = Document::new(fl!("report-title"))
+            .heading(fl!("report-timer"))
+            .text(format!("{}: {}", fl!("report-elapsed"), timer::format_hms(self.stopwatch)));
+
+        for (num, lap) in self.laps.iter().enumerate() {
+            document = document.text(format!("{}: {}", fl!("lap-id", num = num + 1), timer::format_hms(*lap)));
+        }
+
+        document = document.heading(fl!("report-counter"));
+        for (name, subtotal) in &self.counters {
+            document = document.text(format!("{name}: {subtotal}"));
+        }
+
+        document
+            .text(format!("{}: {}", fl!("grand-total"), self.grand_total()))
+            .heading(fl!("report-game"))
+            .text(format!("{}: {}", fl!("report-attempts"), self.game_attempts))
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::from("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\">");
+
+        _ = writeln!(out, "<title>{}</title></head>\n<body>", escape(&fl!("report-title")));
+        _ = writeln!(out, "<h1>{}</h1>", escape(&fl!("report-title")));
+        _ = writeln!(out, "<h2>{}</h2>\n<ul>", escape(&fl!("report-timer")));
+        _ = writeln!(
+            out,
+            "<li>{}: {}</li>",
+            escape(&fl!("report-elapsed")),
+            timer::format_hms(self.stopwatch)
+        );
+
+        for (num, lap) in self.laps.iter().enumerate() {
+            _ = writeln!(
+                out,
+                "<li>{}: {}</li>",
+                escape(&fl!("lap-id", num = num + 1)),
+                timer::format_hms(*lap)
+            );
+        }
+
+        _ = writeln!(out, "</ul>\n<h2>{}</h2>\n<ul>", escape(&fl!("report-counter")));
+        for (name, subtotal) in &self.counters {
+            _ = writeln!(out, "<li>{}: {subtotal}</li>", escape(name));
+        }
+
+        _ = writeln!(out, "<li>{}: {}</li>", escape(&fl!("grand-total")), self.grand_total());
+        _ = writeln!(out, "</ul>\n<h2>{}</h2>\n<ul>", escape(&fl!("report-game")));
+        _ = writeln!(
+            out,
+            "<li>{}: {}</li>",
+            escape(&fl!("report-attempts")),
+            self.game_attempts
+        );
+        out.push_str("</ul>\n</body>\n</html>\n");
+
+        out
+    }
+}
+
+/// Escapes text for inclusion in HTML.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
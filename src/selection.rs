@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Selecting several rows of a list at once, to delete or export them together.
+//!
+//! Rows are identified by their index in the list they are drawn from. Lists
+//! which only show some of their rows, such as filtered session history, pass
+//! the indices of the shown rows so that selecting all leaves hidden rows alone.
+
+use std::collections::BTreeSet;
+
+/// Which rows of a list are selected, while its selection mode is on.
+#[derive(Debug, Default)]
+pub struct Selection {
+    active: bool,
+    selected: BTreeSet<usize>,
+}
+
+/// A change to a [`Selection`], or a request to act on the selected rows.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Shows a checkbox on every row.
+    Start,
+    /// Hides the checkboxes and forgets the selection.
+    Cancel,
+    Toggle(usize, bool),
+    SelectAll(bool),
+    Delete,
+    Export,
+}
+
+/// What to do with the rows that were selected.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Bulk {
+    /// Rows to remove, in ascending order.
+    Delete(Vec<usize>),
+    /// Rows to export, in ascending order.
+    Export(Vec<usize>),
+}
+
+impl Selection {
+    /// Whether rows show a checkbox for selecting them.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn is_selected(&self, idx: usize) -> bool {
+        self.selected.contains(&idx)
+    }
+
+    /// Number of selected rows.
+    pub fn len(&self) -> usize {
+        self.selected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selected.is_empty()
+    }
+
+    /// Leaves selection mode, as when the list changes under it.
+    pub fn cancel(&mut self) {
+        self.active = false;
+        self.selected.clear();
+    }
+
+    /// Keeps only the selected rows among `shown`, as when a filter hides some,
+    /// so that deleting or exporting never acts on rows out of sight.
+    pub fn retain(&mut self, shown: impl IntoIterator<Item = usize>) {
+        let shown: BTreeSet<usize> = shown.into_iter().collect();
+        self.selected.retain(|idx| shown.contains(idx));
+    }
+
+    /// Applies `action`, with `shown` being the indices of the rows on screen.
+    ///
+    /// Returns the rows to act on when the action was a bulk delete or export,
+    /// which also ends selection mode.
+    pub fn update(&mut self, action: Action, shown: impl IntoIterator<Item = usize>) -> Option<Bulk> {
+        match action {
+            Action::Start => self.active = true,
+            Action::Cancel => self.cancel(),
+            Action::Toggle(idx, true) => {
+                self.selected.insert(idx);
+            }
+            Action::Toggle(idx, false) => {
+                self.selected.remove(&idx);
+            }
+            Action::SelectAll(true) => self.selected.extend(shown),
+            Action::SelectAll(false) => self.selected.clear(),
+            Action::Delete | Action::Export => {
+                let rows: Vec<usize> = std::mem::take(&mut self.selected).into_iter().collect();
+                self.active = false;
+
+                if rows.is_empty() {
+                    return None;
+                }
+
+                return Some(if action == Action::Delete {
+                    Bulk::Delete(rows)
+                } else {
+                    Bulk::Export(rows)
+                });
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: GPL-3
+
+//! History of timed stopwatch sessions, kept in the application's data directory.
+
+use crate::storage;
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// One run of the stopwatch, from start to stop.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Session {
+    /// Unix time the session started.
+    pub started: i64,
+    /// Time spent running, in seconds.
+    pub duration_secs: u64,
+    /// What the session was spent on.
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Session {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+}
+
+/// Every recorded session, oldest first.
+#[derive(Debug, Default)]
+pub struct History {
+    pub sessions: Vec<Session>,
+}
+
+impl History {
+    /// Loads the history from disk, starting empty if it is missing or unreadable.
+    ///
+    /// A history that cannot be understood is moved aside to a `.bad` file
+    /// next to it, so that saving the new history does not destroy it.
+    pub fn load() -> Self {
+        let Some(path) = path() else {
+            return Self::default();
+        };
+
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+
+        match serde_json::from_str(&text) {
+            Ok(sessions) => Self { sessions },
+            Err(why) => {
+                let bad = path.with_extension("json.bad");
+                eprintln!("failed to read session history, moving it to {}: {why}", bad.display());
+
+                if let Err(why) = std::fs::rename(&path, &bad) {
+                    eprintln!("failed to move the session history aside: {why}");
+                }
+
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = path() else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string_pretty(&self.sessions)?;
+                std::fs::write(&path, json)
+            });
+
+        if let Err(why) = result {
+            eprintln!("failed to save session history: {why}");
+        }
+    }
+
+    /// Adds `sessions` that are not already recorded, returning how many were added.
+    ///
+    /// A session is a duplicate when one with the same start time and duration exists.
+    pub fn merge(&mut self, sessions: Vec<Session>) -> usize {
+        let before = self.sessions.len();
+
+        for session in sessions {
+            let duplicate = self.sessions.iter().any(|existing| {
+                existing.started == session.started && existing.duration_secs == session.duration_secs
+            });
+
+            if !duplicate {
+                self.sessions.push(session);
+            }
+        }
+
+        self.sessions.sort_by_key(|session| session.started);
+        self.sessions.len() - before
+    }
+
+    /// Time tracked on each day of the week containing `today`, from Monday.
+    ///
+    /// Sessions count towards the local day they started on.
+    pub fn week_totals(&self, today: NaiveDate) -> [Duration; 7] {
+        let monday = today - Days::new(u64::from(today.weekday().num_days_from_monday()));
+        let mut totals = [Duration::ZERO; 7];
+
+        for session in &self.sessions {
+            let Some(started) = DateTime::from_timestamp(session.started, 0) else {
+                continue;
+            };
+
+            let day = started.with_timezone(&Local).date_naive();
+
+            if let Some(total) = usize::try_from((day - monday).num_days())
+                .ok()
+                .and_then(|idx| totals.get_mut(idx))
+            {
+                *total += Duration::from_secs(session.duration_secs);
+            }
+        }
+
+        totals
+    }
+
+    /// Every tag used by any session, sorted and without duplicates.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .sessions
+            .iter()
+            .flat_map(|session| session.tags.iter().cloned())
+            .collect();
+
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        tags
+    }
+}
+
+/// Splits comma-separated text into distinct, trimmed tags.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_owned());
+        }
+    }
+
+    tags
+}
+
+/// Reads sessions from the text of a JSON or CSV file, chosen by the extension of its `path`.
+///
+/// CSV files have a `started,duration_secs,label,tags` header, with tags
+/// separated by semicolons.
+pub fn parse(path: &Path, text: &str) -> Result<Vec<Session>, ImportError> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+    if is_json {
+        serde_json::from_str(text).map_err(ImportError::Json)
+    } else {
+        parse_csv(text)
+    }
+}
+
+/// Renders `sessions` as the text of a JSON or CSV file, chosen by the extension of its `path`,
+/// as [`parse`] expects.
+pub fn render(path: &Path, sessions: &[Session]) -> std::io::Result<String> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+    if is_json {
+        Ok(serde_json::to_string_pretty(sessions)?)
+    } else {
+        Ok(to_csv(sessions))
+    }
+}
+
+fn to_csv(sessions: &[Session]) -> String {
+    let quote = |field: &str| {
+        if field.contains([',', '"']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_owned()
+        }
+    };
+
+    std::iter::once("started,duration_secs,label,tags\n".to_owned())
+        .chain(sessions.iter().map(|session| {
+            format!(
+                "{},{},{},{}\n",
+                session.started,
+                session.duration_secs,
+                quote(&session.label),
+                quote(&session.tags.join(";")),
+            )
+        }))
+        .collect()
+}
+
+fn parse_csv(text: &str) -> Result<Vec<Session>, ImportError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+
+    // The header row is optional.
+    if lines
+        .peek()
+        .is_some_and(|(_, line)| line.trim_start().starts_with("started"))
+    {
+        lines.next();
+    }
+
+    lines
+        .map(|(idx, line)| {
+            let fields = csv_fields(line);
+            let invalid = || ImportError::InvalidRow(idx + 1);
+
+            let started = fields.first().and_then(|f| f.trim().parse().ok()).ok_or_else(invalid)?;
+            let duration_secs = fields.get(1).and_then(|f| f.trim().parse().ok()).ok_or_else(invalid)?;
+
+            Ok(Session {
+                started,
+                duration_secs,
+                label: fields.get(2).map_or_else(String::new, |f| f.trim().to_owned()),
+                tags: fields
+                    .get(3)
+                    .map(|f| parse_tags(&f.replace(';', ",")))
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Splits a CSV row into fields, honouring double-quoted fields.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+/// Reasons a session file cannot be imported.
+#[derive(Debug)]
+pub enum ImportError {
+    /// A CSV row, numbered from one, is missing its start time or duration.
+    InvalidRow(usize),
+    Json(serde_json::Error),
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(why: std::io::Error) -> Self {
+        ImportError::Io(why)
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::InvalidRow(row) => write!(f, "line {row} is not a valid session"),
+            ImportError::Json(why) => write!(f, "invalid session JSON: {why}"),
+            ImportError::Io(why) => why.fmt(f),
+        }
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join("sessions.json"))
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Reading key bindings written as text, such as `Ctrl+Shift+C` or `F1`.
+
+use cosmic::iced::keyboard::{Key, key::Named};
+use cosmic::widget::menu::key_bind::{KeyBind, Modifier};
+
+/// Parses a shortcut of `+`-separated modifiers followed by a key.
+///
+/// Modifiers are `Ctrl`, `Shift`, `Alt` and `Super`, and keys are single
+/// characters or the names of `F1` to `F12`, `Escape`, `Enter`, `Tab`,
+/// `Space`, `Backspace`, `Delete`, `Home`, `End`, `PageUp` and `PageDown`.
+/// Case is ignored. Returns `None` for anything else.
+pub fn parse(text: &str) -> Option<KeyBind> {
+    let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+
+    // A trailing `+`, as in `Ctrl++`, binds the plus key itself.
+    if parts.len() > 1 && parts.last() == Some(&"") {
+        parts.pop();
+        *parts.last_mut()? = "+";
+    }
+
+    let (key, modifiers) = parts.split_last()?;
+
+    let modifiers = modifiers
+        .iter()
+        .map(|modifier| match modifier.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => Some(Modifier::Ctrl),
+            "shift" => Some(Modifier::Shift),
+            "alt" => Some(Modifier::Alt),
+            "super" | "logo" => Some(Modifier::Super),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(KeyBind {
+        modifiers,
+        key: key_named(key)?,
+    })
+}
+
+fn key_named(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Character(c.to_lowercase().collect::<String>().into()));
+    }
+
+    let named = match name.to_ascii_lowercase().as_str() {
+        "f1" => Named::F1,
+        "f2" => Named::F2,
+        "f3" => Named::F3,
+        "f4" => Named::F4,
+        "f5" => Named::F5,
+        "f6" => Named::F6,
+        "f7" => Named::F7,
+        "f8" => Named::F8,
+        "f9" => Named::F9,
+        "f10" => Named::F10,
+        "f11" => Named::F11,
+        "f12" => Named::F12,
+        "escape" | "esc" => Named::Escape,
+        "enter" | "return" => Named::Enter,
+        "tab" => Named::Tab,
+        "space" => Named::Space,
+        "backspace" => Named::Backspace,
+        "delete" | "del" => Named::Delete,
+        "home" => Named::Home,
+        "end" => Named::End,
+        "pageup" => Named::PageUp,
+        "pagedown" => Named::PageDown,
+        _ => return None,
+    };
+
+    Some(Key::Named(named))
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Saving captures of the window as images, for bug reports and documentation.
+
+use crate::error::{Code, Error};
+use cosmic::iced::Rectangle;
+use cosmic::iced::window::Screenshot;
+
+/// Cuts a capture of the window down to `bounds`, in logical pixels, or keeps
+/// all of it if the bounds are unknown or lie outside of it.
+pub fn crop(screenshot: &Screenshot, bounds: Option<Rectangle>) -> Screenshot {
+    let Some(bounds) = bounds else {
+        return screenshot.clone();
+    };
+
+    // The capture is in physical pixels.
+    let scale = screenshot.scale_factor as f32;
+    let x = (bounds.x * scale).round().max(0.0) as u32;
+    let y = (bounds.y * scale).round().max(0.0) as u32;
+
+    let region = Rectangle {
+        x,
+        y,
+        width: ((bounds.width * scale).round() as u32).min(screenshot.size.width.saturating_sub(x)),
+        height: ((bounds.height * scale).round() as u32).min(screenshot.size.height.saturating_sub(y)),
+    };
+
+    if region.width == 0 || region.height == 0 {
+        return screenshot.clone();
+    }
+
+    screenshot.crop(region).unwrap_or_else(|why| {
+        eprintln!("failed to crop the capture: {why:?}");
+        screenshot.clone()
+    })
+}
+
+/// Encodes a capture of the window, whose pixels are 8-bit RGBA, as a PNG image.
+pub fn encode_png(screenshot: &Screenshot) -> Result<Vec<u8>, Error> {
+    let mut image = Vec::new();
+
+    let mut encoder = png::Encoder::new(&mut image, screenshot.size.width, screenshot.size.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    // Dropping the writer finishes the image.
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(screenshot.as_ref()))
+        .map_err(|why| Error::new(Code::Io, why.to_string()))?;
+
+    Ok(image)
+}
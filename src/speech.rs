@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Speaking announcements aloud through Speech Dispatcher.
+//!
+//! Speech needs the `speech` feature, which links to libspeechd. Built
+//! without it, [`is_available`] is false and [`say`] does nothing.
+//!
+//! One thread holds the connection to the speech service and speaks each
+//! text it is sent, so that the connection outlives every announcement.
+
+use serde::{Deserialize, Serialize};
+
+/// The kind of voice announcements are spoken in.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Voice {
+    /// Whichever voice the speech service is set up to use.
+    #[default]
+    Default,
+    Male,
+    Female,
+    Child,
+}
+
+impl Voice {
+    pub const ALL: [Voice; 4] = [Voice::Default, Voice::Male, Voice::Female, Voice::Child];
+}
+
+/// Whether the app was built able to speak.
+pub fn is_available() -> bool {
+    cfg!(feature = "speech")
+}
+
+/// Speaks `text` in `voice` at `rate`, from -100 for slowest to 100 for
+/// fastest, cutting off anything still being spoken so a countdown keeps pace.
+#[cfg(feature = "speech")]
+pub fn say(text: String, voice: Voice, rate: i32) {
+    _ = speaker::SPEAKER.send(speaker::Request { text, voice, rate });
+}
+
+#[cfg(not(feature = "speech"))]
+pub fn say(_text: String, _voice: Voice, _rate: i32) {}
+
+#[cfg(feature = "speech")]
+mod speaker {
+    use super::Voice;
+    use crate::app::AppModel;
+    use cosmic::Application;
+    use speech_dispatcher::{Connection, Mode, Priority, VoiceType};
+    use std::sync::LazyLock;
+    use std::sync::mpsc;
+
+    pub struct Request {
+        pub text: String,
+        pub voice: Voice,
+        pub rate: i32,
+    }
+
+    pub static SPEAKER: LazyLock<mpsc::Sender<Request>> = LazyLock::new(|| {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || run(receiver));
+        sender
+    });
+
+    fn run(receiver: mpsc::Receiver<Request>) {
+        let Some(mut connection) = open() else {
+            return;
+        };
+
+        let mut voice = Voice::Default;
+
+        for request in receiver {
+            // A connection keeps the last voice type set on it, and starts with
+            // the one the speech service is set up to use, so going back to
+            // that takes a new connection.
+            if request.voice == Voice::Default && voice != Voice::Default {
+                let Some(reopened) = open() else {
+                    return;
+                };
+
+                connection = reopened;
+            }
+
+            voice = request.voice;
+            _ = connection.cancel();
+            _ = connection.set_voice_rate(request.rate.clamp(-100, 100));
+
+            let kind = match request.voice {
+                Voice::Default => None,
+                Voice::Male => Some(VoiceType::Male1),
+                Voice::Female => Some(VoiceType::Female1),
+                Voice::Child => Some(VoiceType::ChildFemale),
+            };
+
+            if let Some(kind) = kind {
+                _ = connection.set_voice_type(kind);
+            }
+
+            _ = connection.say(Priority::Important, request.text);
+        }
+    }
+
+    fn open() -> Option<Connection> {
+        match Connection::open(AppModel::APP_ID, "announcements", "", Mode::Threaded) {
+            Ok(connection) => Some(connection),
+            Err(why) => {
+                eprintln!("speech unavailable: {why:?}");
+                None
+            }
+        }
+    }
+}
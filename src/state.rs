@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Where the pages were left, kept across launches apart from the preferences.
+//!
+//! [`Config`](crate::config::Config) holds what the user chose, while the
+//! state holds what they were in the middle of, such as the stopwatch's time,
+//! the round being played, or a half-typed label. It is written to a file of
+//! its own as the app closes, and read back at launch. Passwords are never kept.
+
+use crate::game;
+use crate::storage;
+use crate::timer;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The transient state of every page.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct State {
+    pub watch: WatchState,
+    pub game: GameState,
+}
+
+/// Where the watch page was left.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WatchState {
+    /// The stopwatch's elapsed time.
+    pub stopwatch: timer::Saved,
+    /// The countdown length being typed, before it was applied.
+    pub countdown_input: String,
+    /// Label typed for the next recorded session.
+    pub session_label: String,
+    /// Tags typed for the next recorded session.
+    pub session_tags: String,
+}
+
+/// Where the game page was left.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameState {
+    /// The round being played and its kind, unless it was over.
+    pub round: Option<(game::Mode, game::Engine)>,
+    /// The guess being typed.
+    pub number: String,
+}
+
+impl State {
+    /// Reads the state saved when the app last closed, if there is one.
+    pub fn load() -> Option<Self> {
+        let text = std::fs::read_to_string(path()?).ok()?;
+
+        match serde_json::from_str(&text) {
+            Ok(state) => Some(state),
+            Err(why) => {
+                eprintln!("failed to read saved state: {why}");
+                None
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let Some(path) = path() else {
+            return;
+        };
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string_pretty(self)?;
+                std::fs::write(&path, json)
+            });
+
+        if let Err(why) = result {
+            eprintln!("failed to save state: {why}");
+        }
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join("state.json"))
+}
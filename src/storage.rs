@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Locations of the application's data files.
+
+use crate::app::AppModel;
+use crate::config::Config;
+use cosmic::Application;
+use cosmic::cosmic_config::CosmicConfigEntry;
+use std::path::PathBuf;
+
+/// The application's directory under `$XDG_DATA_HOME`.
+pub fn data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(AppModel::APP_ID))
+}
+
+/// The directory cosmic-config keeps the current configuration version in,
+/// under `$XDG_CONFIG_HOME`.
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| {
+        dir.join("cosmic")
+            .join(AppModel::APP_ID)
+            .join(format!("v{}", Config::VERSION))
+    })
+}
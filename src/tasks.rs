@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Cancellation of long-running background tasks.
+
+use crate::io;
+use cosmic::Task;
+use cosmic::iced::task;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Work in flight, keyed by its owner.
+///
+/// An owner may have several tasks and file transfers going at once, and
+/// cancelling it stops all of them: tasks are aborted, and transfers are told
+/// to stop at their next chunk.
+#[derive(Debug)]
+pub struct Tasks<K> {
+    work: HashMap<K, Vec<Work>>,
+    next_id: u64,
+}
+
+#[derive(Debug)]
+enum Work {
+    Task(u64, task::Handle),
+    Transfer(io::Cancel),
+}
+
+impl<K> Default for Tasks<K> {
+    fn default() -> Self {
+        Self {
+            work: HashMap::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash> Tasks<K> {
+    /// Makes `task` abortable and tracks it under `key`.
+    ///
+    /// The returned task ends with the message made by `finished`, which is
+    /// handed to [`Tasks::finish`] to stop tracking it. Tasks with nothing to
+    /// run are returned as they are.
+    pub fn spawn<T: Send + 'static>(&mut self, key: K, task: Task<T>, finished: impl FnOnce(u64) -> T) -> Task<T> {
+        if task.units() == 0 {
+            return task;
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let (task, handle) = task.abortable();
+        self.work.entry(key).or_default().push(Work::Task(id, handle));
+
+        task.chain(Task::done(finished(id)))
+    }
+
+    /// Tracks a file transfer under `key`, or stops tracking it once it has ended.
+    pub fn transfer(&mut self, key: K, transferred: &io::Transferred) {
+        let work = self.work.entry(key).or_default();
+        work.retain(|work| !matches!(work, Work::Transfer(cancel) if *cancel == transferred.cancel));
+
+        if !transferred.ended {
+            work.push(Work::Transfer(transferred.cancel.clone()));
+        }
+    }
+
+    /// Aborts every task and transfer tracked under `key`.
+    pub fn cancel(&mut self, key: &K) {
+        for work in self.work.remove(key).unwrap_or_default() {
+            match work {
+                Work::Task(_, handle) => handle.abort(),
+                Work::Transfer(cancel) => cancel.cancel(),
+            }
+        }
+    }
+
+    /// The key a transfer is tracked under, if any.
+    pub fn owner(&self, cancel: &io::Cancel) -> Option<&K> {
+        self.work.iter().find_map(|(key, work)| {
+            work.iter()
+                .any(|work| matches!(work, Work::Transfer(tracked) if tracked == cancel))
+                .then_some(key)
+        })
+    }
+
+    /// Stops tracking a task which has completed on its own.
+    pub fn finish(&mut self, key: &K, id: u64) {
+        if let Some(work) = self.work.get_mut(key) {
+            work.retain(|work| !matches!(work, Work::Task(task, _) if *task == id));
+        }
+    }
+}
@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Stopwatches and countdowns, independent of any user interface.
+//!
+//! Time is measured from [`Instant`]s rather than counted ticks, so consumers
+//! only need to redraw periodically and never drift when ticks are missed.
+
+use chrono::{DateTime, Days, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Identifies a timer owned by a [`Service`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Id(u32);
+
+/// Whether a timer counts up or down.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Kind {
+    /// Counts up from zero without end.
+    Stopwatch,
+    /// Counts down from the given duration and finishes at zero.
+    Countdown(Duration),
+}
+
+/// A single stopwatch or countdown.
+#[derive(Clone, Debug)]
+pub struct Timer {
+    kind: Kind,
+    /// Time accumulated before the current run.
+    accumulated: Duration,
+    /// When the current run began, if running.
+    started: Option<Instant>,
+    /// Elapsed time at each recorded lap.
+    laps: Vec<Duration>,
+}
+
+impl Timer {
+    fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            accumulated: Duration::ZERO,
+            started: None,
+            laps: Vec::new(),
+        }
+    }
+
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Total time this timer has been running.
+    pub fn elapsed(&self) -> Duration {
+        let current = self.started.map_or(Duration::ZERO, |started| started.elapsed());
+        let elapsed = self.accumulated + current;
+
+        match self.kind {
+            Kind::Stopwatch => elapsed,
+            Kind::Countdown(total) => elapsed.min(total),
+        }
+    }
+
+    /// Time left on a countdown, or `None` for a stopwatch.
+    pub fn remaining(&self) -> Option<Duration> {
+        match self.kind {
+            Kind::Stopwatch => None,
+            Kind::Countdown(total) => Some(total.saturating_sub(self.elapsed())),
+        }
+    }
+
+    /// Whether a countdown has reached zero.
+    pub fn is_finished(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    /// Elapsed time at each recorded lap, oldest first.
+    pub fn laps(&self) -> &[Duration] {
+        &self.laps
+    }
+
+    fn start(&mut self) {
+        if self.started.is_none() && !self.is_finished() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    fn pause(&mut self) {
+        if let Some(started) = self.started.take() {
+            self.accumulated += started.elapsed();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.accumulated = Duration::ZERO;
+        self.started = self.started.map(|_| Instant::now());
+        self.laps.clear();
+    }
+
+    fn lap(&mut self) -> Duration {
+        let elapsed = self.elapsed();
+        self.laps.push(elapsed);
+        elapsed
+    }
+}
+
+/// A timer's elapsed time saved between runs of the application.
+///
+/// A running timer is saved with the wall-clock time its current run began,
+/// so that it can account for the time the application was closed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Saved {
+    /// Time accumulated before the current run, in milliseconds.
+    pub accumulated_ms: u64,
+    /// When the current run began, in milliseconds since the Unix epoch, if running.
+    pub running_since_ms: Option<i64>,
+    /// When the timer was saved, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    pub saved_at_ms: Option<i64>,
+}
+
+impl Saved {
+    pub fn is_running(&self) -> bool {
+        self.running_since_ms.is_some()
+    }
+}
+
+/// Owns every timer in the application.
+#[derive(Debug, Default)]
+pub struct Service {
+    timers: BTreeMap<Id, Timer>,
+    next_id: u32,
+}
+
+impl Service {
+    /// Adds a stopped timer and returns its ID.
+    pub fn add(&mut self, kind: Kind) -> Id {
+        let id = Id(self.next_id);
+        self.next_id += 1;
+        self.timers.insert(id, Timer::new(kind));
+        id
+    }
+
+    /// Replaces a timer with a stopped one of the given kind.
+    pub fn replace(&mut self, id: Id, kind: Kind) {
+        self.timers.insert(id, Timer::new(kind));
+    }
+
+    /// Removes a timer, returning it if it existed.
+    pub fn remove(&mut self, id: Id) -> Option<Timer> {
+        self.timers.remove(&id)
+    }
+
+    pub fn get(&self, id: Id) -> Option<&Timer> {
+        self.timers.get(&id)
+    }
+
+    /// Iterates over all timers in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, &Timer)> {
+        self.timers.iter().map(|(id, timer)| (*id, timer))
+    }
+
+    /// Whether any timer is currently running.
+    pub fn any_running(&self) -> bool {
+        self.timers.values().any(Timer::is_running)
+    }
+
+    pub fn is_running(&self, id: Id) -> bool {
+        self.get(id).is_some_and(Timer::is_running)
+    }
+
+    pub fn elapsed(&self, id: Id) -> Option<Duration> {
+        self.get(id).map(Timer::elapsed)
+    }
+
+    pub fn start(&mut self, id: Id) {
+        if let Some(timer) = self.timers.get_mut(&id) {
+            timer.start();
+        }
+    }
+
+    pub fn pause(&mut self, id: Id) {
+        if let Some(timer) = self.timers.get_mut(&id) {
+            timer.pause();
+        }
+    }
+
+    /// Clears the elapsed time and laps, keeping the timer running if it was.
+    pub fn reset(&mut self, id: Id) {
+        if let Some(timer) = self.timers.get_mut(&id) {
+            timer.reset();
+        }
+    }
+
+    /// Captures a timer's elapsed time, and when its current run began if running.
+    pub fn save(&self, id: Id) -> Option<Saved> {
+        let timer = self.get(id)?;
+        let now = Local::now().timestamp_millis();
+        let since = |started: Instant| now - started.elapsed().as_millis() as i64;
+
+        Some(Saved {
+            accumulated_ms: timer.accumulated.as_millis() as u64,
+            running_since_ms: timer.started.map(since),
+            saved_at_ms: Some(now),
+        })
+    }
+
+    /// Restores a timer's elapsed time from `saved`, clearing its laps.
+    ///
+    /// A timer saved while running keeps running if `resume` is set, counting
+    /// the time since its run began. Otherwise it is paused with the time it
+    /// had when it was saved, which includes that run up to the save.
+    pub fn restore(&mut self, id: Id, saved: Saved, resume: bool) {
+        let Some(timer) = self.timers.get_mut(&id) else {
+            return;
+        };
+
+        timer.accumulated = Duration::from_millis(saved.accumulated_ms);
+        timer.started = None;
+        timer.laps.clear();
+
+        let Some(since) = saved.running_since_ms else {
+            return;
+        };
+
+        // Saves from before the save time was recorded only know the run began.
+        let until = match (resume, saved.saved_at_ms) {
+            (false, Some(saved_at)) => saved_at,
+            (false, None) => since,
+            (true, _) => Local::now().timestamp_millis(),
+        };
+
+        let ran = until.saturating_sub(since).max(0);
+        timer.accumulated += Duration::from_millis(ran as u64);
+
+        if resume {
+            timer.start();
+        }
+    }
+
+    /// Records a lap, returning the elapsed time at which it was taken.
+    pub fn lap(&mut self, id: Id) -> Option<Duration> {
+        self.timers.get_mut(&id).map(Timer::lap)
+    }
+
+    /// Forgets the laps at `indices`, so the following lap's split covers theirs.
+    pub fn remove_laps(&mut self, id: Id, indices: &[usize]) {
+        if let Some(timer) = self.timers.get_mut(&id) {
+            let mut idx = 0;
+
+            timer.laps.retain(|_| {
+                idx += 1;
+                !indices.contains(&(idx - 1))
+            });
+        }
+    }
+
+    /// Pauses any countdowns which have reached zero, returning their IDs.
+    pub fn take_finished(&mut self) -> Vec<Id> {
+        self.timers
+            .iter_mut()
+            .filter(|(_, timer)| timer.is_running() && timer.is_finished())
+            .map(|(id, timer)| {
+                timer.pause();
+                *id
+            })
+            .collect()
+    }
+}
+
+/// An alarm which rings every day at a local time of day.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Alarm {
+    pub hour: u32,
+    pub minute: u32,
+    pub enabled: bool,
+}
+
+impl Alarm {
+    /// Whether the alarm's time of day fell after `since` and at or before `now`.
+    pub fn rang_between(&self, since: DateTime<Local>, now: DateTime<Local>) -> bool {
+        let Some(time) = NaiveTime::from_hms_opt(self.hour, self.minute, 0) else {
+            return false;
+        };
+
+        // Checks were missed if the system slept; only the last day is considered.
+        let yesterday = now.date_naive().checked_sub_days(Days::new(1));
+
+        [yesterday, Some(now.date_naive())]
+            .into_iter()
+            .flatten()
+            .filter_map(|date| Local.from_local_datetime(&date.and_time(time)).earliest())
+            .any(|ring| since < ring && ring <= now)
+    }
+}
+
+/// How the stopwatch's elapsed time is written.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WatchFormat {
+    /// Whole seconds, as `125`.
+    #[default]
+    Seconds,
+    /// Hours, minutes, and seconds, as `0:02:05`.
+    Clock,
+}
+
+impl WatchFormat {
+    /// Every format, in the order offered in the settings.
+    pub const ALL: [WatchFormat; 2] = [WatchFormat::Seconds, WatchFormat::Clock];
+
+    /// Writes `duration` in this format.
+    pub fn format(self, duration: Duration) -> String {
+        match self {
+            WatchFormat::Seconds => duration.as_secs().to_string(),
+            WatchFormat::Clock => format_hms(duration),
+        }
+    }
+}
+
+/// Formats a duration as `H:MM:SS`.
+pub fn format_hms(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Parses a duration written as `SS`, `MM:SS`, or `H:MM:SS`.
+pub fn parse_hms(text: &str) -> Option<Duration> {
+    let mut secs = 0u64;
+    let mut fields = 0;
+
+    for field in text.trim().split(':') {
+        secs = secs.checked_mul(60)?.checked_add(field.trim().parse().ok()?)?;
+        fields += 1;
+    }
+
+    (fields <= 3).then(|| Duration::from_secs(secs))
+}
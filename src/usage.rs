@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Counts of how the application is used, kept in the application's data directory.
+//!
+//! These are only ever shown back to the user and are never sent anywhere.
+
+use crate::storage;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Something worth counting.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// The application was started.
+    Launch,
+    /// The page at this position in the nav bar was opened.
+    Visit(usize),
+    /// A round of the guessing game was finished.
+    GamePlayed,
+}
+
+/// How often each [`Event`] happened.
+#[derive(Debug, Default)]
+pub struct Usage {
+    pub launches: u64,
+    pub games_played: u64,
+    /// Visits to each page, by its position in the nav bar.
+    visits: BTreeMap<usize, u64>,
+}
+
+impl Usage {
+    /// Loads the counts from disk, ignoring malformed lines.
+    pub fn load() -> Self {
+        let mut usage = Self::default();
+
+        let Some(text) = path().and_then(|path| std::fs::read_to_string(path).ok()) else {
+            return usage;
+        };
+
+        for line in text.lines() {
+            let Some((key, count)) = line.split_once(' ') else {
+                continue;
+            };
+
+            let Ok(count) = count.parse() else {
+                continue;
+            };
+
+            match key {
+                "launches" => usage.launches = count,
+                "games" => usage.games_played = count,
+                _ => {
+                    if let Some(page) = key.strip_prefix("page-").and_then(|page| page.parse().ok()) {
+                        usage.visits.insert(page, count);
+                    }
+                }
+            }
+        }
+
+        usage
+    }
+
+    /// Visits to the page at position `page` in the nav bar.
+    pub fn visits(&self, page: usize) -> u64 {
+        self.visits.get(&page).copied().unwrap_or_default()
+    }
+
+    /// Counts `event` and saves the counts.
+    pub fn record(&mut self, event: Event) {
+        match event {
+            Event::Launch => self.launches += 1,
+            Event::Visit(page) => *self.visits.entry(page).or_default() += 1,
+            Event::GamePlayed => self.games_played += 1,
+        }
+
+        if let Err(why) = self.save() {
+            eprintln!("failed to save usage counts: {why}");
+        }
+    }
+
+    /// Forgets every count and removes the data file.
+    pub fn clear(&mut self) {
+        *self = Self::default();
+
+        if let Some(path) = path() {
+            if let Err(why) = std::fs::remove_file(path) {
+                if why.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("failed to remove usage counts: {why}");
+                }
+            }
+        }
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let Some(path) = path() else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let text: String = [
+            format!("launches {}\n", self.launches),
+            format!("games {}\n", self.games_played),
+        ]
+        .into_iter()
+        .chain(self.visits.iter().map(|(page, count)| format!("page-{page} {count}\n")))
+        .collect();
+
+        std::fs::write(path, text)
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    storage::data_dir().map(|dir| dir.join("usage.txt"))
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A row which becomes a column in narrow windows.
+
+use cosmic::iced::alignment::Vertical;
+use cosmic::widget;
+use cosmic::Element;
+
+/// Lays out `children` side by side, or one below the other in the `narrow`
+/// layout of a window narrower than the breakpoint.
+///
+/// Unlike [`super::wrapping_row`], the children keep their order and their
+/// widths are decided together, which suits an input with its button.
+pub fn adaptive_row<'a, Message: 'static>(narrow: bool, children: Vec<Element<'a, Message>>) -> Element<'a, Message> {
+    let space_s = cosmic::theme::spacing().space_s;
+
+    if narrow {
+        widget::column::with_children(children).spacing(space_s).into()
+    } else {
+        widget::row::with_children(children)
+            .align_y(Vertical::Center)
+            .spacing(space_s)
+            .into()
+    }
+}
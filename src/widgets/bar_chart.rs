@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A simple vertical bar chart built from containers.
+
+use crate::contrast;
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::{Alignment, Border, Length};
+use cosmic::widget;
+use cosmic::Element;
+
+/// Height of the tallest bar, in logical pixels.
+const CHART_HEIGHT: f32 = 120.0;
+/// Height given to bars with a value of zero, so that every column is visible.
+const MIN_BAR_HEIGHT: f32 = 2.0;
+
+/// One bar of a [`bar_chart`].
+pub struct Bar {
+    /// Shown beneath the bar.
+    pub label: String,
+    /// Shown above the bar.
+    pub caption: String,
+    pub value: f32,
+}
+
+/// Draws `bars` side by side, scaled so that the largest value fills the chart.
+pub fn bar_chart<'a, Message: 'static>(bars: Vec<Bar>) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let max = bars.iter().map(|bar| bar.value).fold(0.0_f32, f32::max);
+
+    let columns = bars.into_iter().map(|bar| {
+        let height = if max > 0.0 {
+            (bar.value / max * CHART_HEIGHT).max(MIN_BAR_HEIGHT)
+        } else {
+            MIN_BAR_HEIGHT
+        };
+
+        let fill = widget::container(widget::row::<Message>::new())
+            .width(Length::Fill)
+            .height(Length::Fixed(height))
+            .class(cosmic::theme::Container::custom(|theme| {
+                let cosmic = theme.cosmic();
+
+                // Outline bars so they stand out from the background in high contrast.
+                let width = if contrast::is_high_contrast(theme) { 1.0 } else { 0.0 };
+
+                widget::container::Style {
+                    background: Some(cosmic::iced::Color::from(cosmic.accent_color()).into()),
+                    border: Border {
+                        color: cosmic.background.on.into(),
+                        width,
+                        radius: cosmic.corner_radii.radius_xs.into(),
+                    },
+                    ..Default::default()
+                }
+            }));
+
+        widget::column::with_capacity(3)
+            .push(widget::text::caption(bar.caption))
+            .push(
+                widget::container(fill)
+                    .height(Length::Fixed(CHART_HEIGHT))
+                    .align_y(Vertical::Bottom),
+            )
+            .push(widget::text::caption(bar.label))
+            .align_x(Horizontal::Center)
+            .spacing(spacing.space_xxs)
+            .width(Length::Fill)
+            .into()
+    });
+
+    widget::row::with_children(columns.collect())
+        .align_y(Alignment::End)
+        .spacing(spacing.space_s)
+        .width(Length::Fill)
+        .into()
+}
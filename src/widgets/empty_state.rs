@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A placeholder shown in place of views which have no content yet.
+
+use cosmic::iced::alignment::Horizontal;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{self, icon};
+use cosmic::Element;
+
+/// Creates an empty-state placeholder with an icon and a title.
+pub fn empty_state<Message>(icon: icon::Handle, title: impl Into<String>) -> EmptyState<Message> {
+    EmptyState {
+        icon,
+        title: title.into(),
+        description: None,
+        action: None,
+    }
+}
+
+/// An icon, title, optional description, and optional call-to-action button,
+/// centered across the width available to it.
+///
+/// It takes only the height it needs, so it can stand in for a list on a
+/// scrolling page.
+pub struct EmptyState<Message> {
+    icon: icon::Handle,
+    title: String,
+    description: Option<String>,
+    action: Option<(String, Message)>,
+}
+
+impl<Message> EmptyState<Message> {
+    /// Sets the explanatory text shown beneath the title.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Adds a button which emits `message` when pressed.
+    #[must_use]
+    pub fn action(mut self, label: impl Into<String>, message: Message) -> Self {
+        self.action = Some((label.into(), message));
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<EmptyState<Message>> for Element<'a, Message> {
+    fn from(state: EmptyState<Message>) -> Self {
+        let spacing = cosmic::theme::spacing();
+
+        let mut column = widget::column::with_capacity(4)
+            .push(widget::icon(state.icon).size(64))
+            .push(widget::text::title3(state.title))
+            .align_x(Alignment::Center)
+            .spacing(spacing.space_s);
+
+        if let Some(description) = state.description {
+            column = column.push(widget::text::body(description));
+        }
+
+        if let Some((label, message)) = state.action {
+            column = column.push(widget::button::suggested(label).on_press(message));
+        }
+
+        widget::container(column)
+            .width(Length::Fill)
+            .padding([spacing.space_xl, 0])
+            .align_x(Horizontal::Center)
+            .into()
+    }
+}
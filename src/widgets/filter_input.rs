@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A search field above a list, with a count of the rows it lets through.
+
+use crate::filter::Filter;
+use crate::fl;
+use cosmic::iced::alignment::Vertical;
+use cosmic::widget;
+use cosmic::Element;
+
+/// A search input for `filter`, followed by how many of `total` rows are `shown`
+/// while a query is applied.
+pub fn filter_input<'a, Message: Clone + 'static>(
+    filter: &'a Filter,
+    placeholder: String,
+    shown: usize,
+    total: usize,
+    on_input: impl Fn(String) -> Message + 'a,
+    on_clear: Message,
+) -> Element<'a, Message> {
+    let mut row = widget::row::with_capacity(2)
+        .push(
+            widget::search_input(placeholder, filter.input())
+                .on_input(on_input)
+                .on_clear(on_clear),
+        )
+        .align_y(Vertical::Center)
+        .spacing(cosmic::theme::spacing().space_s);
+
+    if filter.is_active() {
+        row = row.push(widget::text::caption(fl!("filter-results", shown = shown, total = total)));
+    }
+
+    row.into()
+}
@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A label which turns into a text input for renaming in place.
+
+use crate::icons;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::Length;
+use cosmic::widget;
+use cosmic::Element;
+
+/// Creates an editable label showing `label`, or `editing` in a text input
+/// while it is being renamed.
+pub fn inline_edit<'a, Message>(label: &'a str, editing: Option<&'a str>) -> InlineEdit<'a, Message> {
+    InlineEdit {
+        label,
+        editing,
+        title: false,
+        placeholder: String::new(),
+        on_edit: None,
+        on_input: None,
+        on_commit: None,
+        on_cancel: None,
+    }
+}
+
+/// A label with a pencil button, or once editing, a text input with commit and
+/// cancel buttons. Double-clicking the label also starts editing.
+pub struct InlineEdit<'a, Message> {
+    label: &'a str,
+    editing: Option<&'a str>,
+    /// Show the label as a heading rather than body text.
+    title: bool,
+    placeholder: String,
+    on_edit: Option<Message>,
+    on_input: Option<Box<dyn Fn(String) -> Message + 'a>>,
+    on_commit: Option<Message>,
+    on_cancel: Option<Message>,
+}
+
+impl<'a, Message> InlineEdit<'a, Message> {
+    /// Shows the label as a heading, as for the name of a group.
+    #[must_use]
+    pub fn title(mut self) -> Self {
+        self.title = true;
+        self
+    }
+
+    /// Sets the text shown in the input while it is empty.
+    #[must_use]
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Emits `message` to start editing.
+    #[must_use]
+    pub fn on_edit(mut self, message: Message) -> Self {
+        self.on_edit = Some(message);
+        self
+    }
+
+    /// Emits the text typed into the input.
+    #[must_use]
+    pub fn on_input(mut self, on_input: impl Fn(String) -> Message + 'a) -> Self {
+        self.on_input = Some(Box::new(on_input));
+        self
+    }
+
+    /// Emits `message` to keep the typed text, from the button or by pressing Enter.
+    #[must_use]
+    pub fn on_commit(mut self, message: Message) -> Self {
+        self.on_commit = Some(message);
+        self
+    }
+
+    /// Emits `message` to stop editing without keeping the typed text.
+    #[must_use]
+    pub fn on_cancel(mut self, message: Message) -> Self {
+        self.on_cancel = Some(message);
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<InlineEdit<'a, Message>> for Element<'a, Message> {
+    fn from(edit: InlineEdit<'a, Message>) -> Self {
+        let space_s = cosmic::theme::spacing().space_s;
+
+        let Some(text) = edit.editing else {
+            let label: Element<_> = if edit.title {
+                widget::text::title4(edit.label).into()
+            } else {
+                widget::text::body(edit.label).into()
+            };
+
+            let mut label = cosmic::iced::widget::mouse_area(label);
+
+            if let Some(message) = edit.on_edit.clone() {
+                label = label.on_double_click(message);
+            }
+
+            return widget::row::with_capacity(2)
+                .push(widget::container(label).width(Length::Fill))
+                .push(
+                    widget::button::icon(icons::handle("document-edit-symbolic"))
+                        .on_press_maybe(edit.on_edit),
+                )
+                .align_y(Vertical::Center)
+                .spacing(space_s)
+                .into();
+        };
+
+        let mut input = widget::text_input(edit.placeholder, text);
+
+        if let Some(on_input) = edit.on_input {
+            input = input.on_input(on_input);
+        }
+
+        if let Some(message) = edit.on_commit.clone() {
+            input = input.on_submit(move |_| message.clone());
+        }
+
+        widget::row::with_capacity(3)
+            .push(input)
+            .push(
+                widget::button::icon(icons::handle("object-select-symbolic"))
+                    .on_press_maybe(edit.on_commit),
+            )
+            .push(
+                widget::button::icon(icons::handle("window-close-symbolic"))
+                    .on_press_maybe(edit.on_cancel),
+            )
+            .align_y(Vertical::Center)
+            .spacing(space_s)
+            .into()
+    }
+}
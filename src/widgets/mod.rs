@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Reusable widgets shared between the application's pages.
+
+pub mod adaptive_row;
+pub mod bar_chart;
+pub mod empty_state;
+pub mod filter_input;
+pub mod inline_edit;
+pub mod numpad;
+pub mod pager;
+pub mod selection_bar;
+pub mod sparkline;
+pub mod strength_meter;
+pub mod wrapping_row;
+
+pub use adaptive_row::adaptive_row;
+pub use bar_chart::{Bar, bar_chart};
+pub use empty_state::empty_state;
+pub use filter_input::filter_input;
+pub use inline_edit::{InlineEdit, inline_edit};
+pub use numpad::{NumpadKey, numpad};
+pub use selection_bar::{selectable, selection_bar};
+pub use sparkline::sparkline;
+pub use strength_meter::strength_meter;
+pub use wrapping_row::wrapping_row;
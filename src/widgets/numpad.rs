@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A touch-friendly numeric keypad.
+
+use cosmic::iced::Length;
+use cosmic::widget;
+use cosmic::Element;
+
+/// Width of each key.
+const KEY_WIDTH: f32 = 72.0;
+
+/// A key pressed on the [`numpad`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NumpadKey {
+    Digit(u8),
+    Backspace,
+    Enter,
+}
+
+impl NumpadKey {
+    fn label(self) -> String {
+        match self {
+            NumpadKey::Digit(digit) => digit.to_string(),
+            NumpadKey::Backspace => String::from("⌫"),
+            NumpadKey::Enter => String::from("↵"),
+        }
+    }
+}
+
+/// Lays out digit keys in a phone-style grid, with backspace and enter keys
+/// either side of zero.
+pub fn numpad<'a, Message: Clone + 'static>(
+    on_key: impl Fn(NumpadKey) -> Message,
+) -> Element<'a, Message> {
+    let space_xs = cosmic::theme::spacing().space_xs;
+
+    let rows = [
+        [NumpadKey::Digit(7), NumpadKey::Digit(8), NumpadKey::Digit(9)],
+        [NumpadKey::Digit(4), NumpadKey::Digit(5), NumpadKey::Digit(6)],
+        [NumpadKey::Digit(1), NumpadKey::Digit(2), NumpadKey::Digit(3)],
+        [NumpadKey::Backspace, NumpadKey::Digit(0), NumpadKey::Enter],
+    ];
+
+    rows.into_iter()
+        .fold(widget::column::with_capacity(4).spacing(space_xs), |column, row| {
+            column.push(row.into_iter().fold(
+                widget::row::with_capacity(3).spacing(space_xs),
+                |row, key| {
+                    let button = if key == NumpadKey::Enter {
+                        widget::button::suggested(key.label())
+                    } else {
+                        widget::button::standard(key.label())
+                    };
+
+                    row.push(button.width(Length::Fixed(KEY_WIDTH)).on_press(on_key(key)))
+                },
+            ))
+        })
+        .into()
+}
@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Splitting long lists into pages, so only one page of rows is built at a time.
+
+use crate::fl;
+use cosmic::iced::alignment::Vertical;
+use cosmic::widget;
+use cosmic::Element;
+
+/// Rows shown on each page of a paged list.
+pub const PAGE_SIZE: usize = 100;
+
+/// Number of pages needed for `len` rows, at least one.
+pub fn page_count(len: usize) -> usize {
+    len.div_ceil(PAGE_SIZE).max(1)
+}
+
+/// The page actually shown when `page` was asked for, in case the list shrank.
+pub fn clamp_page(page: usize, len: usize) -> usize {
+    page.min(page_count(len) - 1)
+}
+
+/// Previous and next buttons around the current page number, or nothing when
+/// `len` rows fit on one page.
+pub fn pager<'a, Message: Clone + 'static>(
+    page: usize,
+    len: usize,
+    on_page: impl Fn(usize) -> Message,
+) -> Option<Element<'a, Message>> {
+    let pages = page_count(len);
+
+    if pages == 1 {
+        return None;
+    }
+
+    let page = clamp_page(page, len);
+
+    Some(
+        widget::row::with_capacity(3)
+            .push(
+                widget::button::text(fl!("previous-page"))
+                    .on_press_maybe(page.checked_sub(1).map(&on_page)),
+            )
+            .push(widget::text::body(fl!("page-of", page = page + 1, pages = pages)))
+            .push(
+                widget::button::text(fl!("next-page"))
+                    .on_press_maybe((page + 1 < pages).then(|| on_page(page + 1))),
+            )
+            .align_y(Vertical::Center)
+            .spacing(cosmic::theme::spacing().space_s)
+            .into(),
+    )
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Controls for selecting rows of a list and acting on all of them at once.
+
+use crate::fl;
+use crate::selection::{Action, Selection};
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::Length;
+use cosmic::widget;
+use cosmic::Element;
+
+/// A button entering selection mode, or once in it, select-all with the bulk actions.
+///
+/// `shown` is the number of rows on screen, used to check select-all once every
+/// one of them is selected.
+pub fn selection_bar<'a, Message: Clone + 'static>(
+    selection: &Selection,
+    shown: usize,
+    on_action: impl Fn(Action) -> Message,
+) -> Element<'a, Message> {
+    let space_s = cosmic::theme::spacing().space_s;
+
+    if !selection.is_active() {
+        return widget::button::text(fl!("select"))
+            .on_press(on_action(Action::Start))
+            .into();
+    }
+
+    let all = shown > 0 && selection.len() >= shown;
+    let any = !selection.is_empty();
+
+    widget::row::with_capacity(5)
+        .push(widget::checkbox(fl!("select-all"), all).on_toggle({
+            let select_all = on_action(Action::SelectAll(!all));
+            move |_| select_all.clone()
+        }))
+        .push(widget::text::body(fl!("selected-count", count = selection.len())).width(Length::Fill))
+        .push(widget::button::text(fl!("export")).on_press_maybe(any.then(|| on_action(Action::Export))))
+        .push(
+            widget::button::destructive(fl!("delete"))
+                .on_press_maybe(any.then(|| on_action(Action::Delete))),
+        )
+        .push(widget::button::text(fl!("cancel")).on_press(on_action(Action::Cancel)))
+        .align_y(Vertical::Center)
+        .spacing(space_s)
+        .into()
+}
+
+/// Puts a checkbox selecting row `idx` before `row`, while selection mode is on.
+pub fn selectable<'a, Message: Clone + 'static>(
+    selection: &Selection,
+    idx: usize,
+    row: impl Into<Element<'a, Message>>,
+    on_action: impl Fn(Action) -> Message + 'a,
+) -> Element<'a, Message> {
+    if !selection.is_active() {
+        return row.into();
+    }
+
+    widget::row::with_capacity(2)
+        .push(
+            widget::checkbox("", selection.is_selected(idx))
+                .on_toggle(move |selected| on_action(Action::Toggle(idx, selected))),
+        )
+        .push(row)
+        .align_y(Vertical::Center)
+        .spacing(cosmic::theme::spacing().space_s)
+        .into()
+}
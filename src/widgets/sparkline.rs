@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A small chart of values over time, built from containers like the bar chart.
+
+use crate::contrast;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::{Alignment, Border, Length};
+use cosmic::widget;
+use cosmic::Element;
+
+/// Height of the chart, in logical pixels.
+const SPARKLINE_HEIGHT: f32 = 48.0;
+/// Height given to the lowest value, so that every point is visible.
+const MIN_POINT_HEIGHT: f32 = 2.0;
+
+/// Draws `values` from left to right as thin columns, scaled so that the
+/// lowest value sits at the bottom and the highest reaches the top.
+pub fn sparkline<'a, Message: 'static>(values: &[i64]) -> Element<'a, Message> {
+    let low = values.iter().copied().min().unwrap_or(0);
+    let high = values.iter().copied().max().unwrap_or(0);
+    let span = high.abs_diff(low) as f32;
+
+    let columns = values.iter().map(|&value| {
+        let height = if span > 0.0 {
+            MIN_POINT_HEIGHT + value.abs_diff(low) as f32 / span * (SPARKLINE_HEIGHT - MIN_POINT_HEIGHT)
+        } else {
+            SPARKLINE_HEIGHT / 2.0
+        };
+
+        let fill = widget::container(widget::row::<Message>::new())
+            .width(Length::Fill)
+            .height(Length::Fixed(height))
+            .class(cosmic::theme::Container::custom(|theme| {
+                let cosmic = theme.cosmic();
+
+                // Outline columns so they stand out from the background in high contrast.
+                let width = if contrast::is_high_contrast(theme) { 1.0 } else { 0.0 };
+
+                widget::container::Style {
+                    background: Some(cosmic::iced::Color::from(cosmic.accent_color()).into()),
+                    border: Border {
+                        color: cosmic.background.on.into(),
+                        width,
+                        radius: 0.0.into(),
+                    },
+                    ..Default::default()
+                }
+            }));
+
+        widget::container(fill)
+            .height(Length::Fixed(SPARKLINE_HEIGHT))
+            .width(Length::Fill)
+            .align_y(Vertical::Bottom)
+            .into()
+    });
+
+    widget::row::with_children(columns.collect())
+        .align_y(Alignment::End)
+        .spacing(1)
+        .width(Length::Fill)
+        .into()
+}
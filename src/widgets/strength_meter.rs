@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A colored bar and label rating a password's estimated strength.
+
+use crate::contrast;
+use crate::fl;
+use crate::generators::strength::{self, Strength};
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::{Border, Length};
+use cosmic::widget;
+use cosmic::Element;
+
+/// Height of the meter's bar, in logical pixels.
+const METER_HEIGHT: f32 = 6.0;
+/// Number of portions the bar is divided into when splitting filled from empty.
+const METER_STEPS: u16 = 100;
+
+/// Rates `password`, filling the bar in proportion to its estimated entropy.
+///
+/// With `explicit` set, the rating starts with a symbol for its tier and the
+/// fill is always outlined, so neither depends on its color being told apart.
+pub fn strength_meter<'a, Message: 'static>(password: &str, explicit: bool) -> Element<'a, Message> {
+    let spacing = cosmic::theme::spacing();
+    let bits = strength::entropy_bits(password);
+    let strength = Strength::from_bits(bits);
+    let filled = (bits / Strength::FULL_BITS).clamp(0.0, 1.0);
+    let filled = (filled * f64::from(METER_STEPS)).round() as u16;
+
+    let fill = widget::container(widget::row::<Message>::new())
+        .width(Length::FillPortion(filled.max(1)))
+        .height(Length::Fixed(METER_HEIGHT))
+        .class(cosmic::theme::Container::custom(move |theme| {
+            let cosmic = theme.cosmic();
+
+            let color = match strength {
+                Strength::VeryWeak | Strength::Weak => cosmic.destructive_color(),
+                Strength::Fair => cosmic.warning_color(),
+                Strength::Strong | Strength::VeryStrong => cosmic.success_color(),
+            };
+
+            // Outline the fill so its end is visible against the track in high contrast.
+            let width = if explicit || contrast::is_high_contrast(theme) { 1.0 } else { 0.0 };
+
+            widget::container::Style {
+                background: Some(cosmic::iced::Color::from(color).into()),
+                border: Border {
+                    color: cosmic.background.on.into(),
+                    width,
+                    radius: cosmic.corner_radii.radius_xs.into(),
+                },
+                ..Default::default()
+            }
+        }));
+
+    let mut bar = widget::row::with_capacity(2).push(if password.is_empty() {
+        widget::horizontal_space().width(Length::FillPortion(1)).into()
+    } else {
+        Element::from(fill)
+    });
+
+    if filled < METER_STEPS {
+        bar = bar.push(widget::horizontal_space().width(Length::FillPortion(METER_STEPS - filled)));
+    }
+
+    let track = widget::container(bar)
+        .width(Length::Fill)
+        .height(Length::Fixed(METER_HEIGHT))
+        .class(cosmic::theme::Container::custom(|theme| {
+            let cosmic = theme.cosmic();
+            let high_contrast = contrast::is_high_contrast(theme);
+
+            widget::container::Style {
+                background: Some(cosmic::iced::Color::from(cosmic.background.divider).into()),
+                border: Border {
+                    color: cosmic.background.on.into(),
+                    width: if high_contrast { 1.0 } else { 0.0 },
+                    radius: cosmic.corner_radii.radius_xs.into(),
+                },
+                ..Default::default()
+            }
+        }));
+
+    let label = if password.is_empty() {
+        fl!("strength-none")
+    } else {
+        let rating = match strength {
+            Strength::VeryWeak => fl!("strength-very-weak"),
+            Strength::Weak => fl!("strength-weak"),
+            Strength::Fair => fl!("strength-fair"),
+            Strength::Strong => fl!("strength-strong"),
+            Strength::VeryStrong => fl!("strength-very-strong"),
+        };
+
+        let label = fl!("strength-rating", rating = rating, bits = bits.round() as u32);
+
+        if explicit {
+            let symbol = match strength {
+                Strength::VeryWeak | Strength::Weak => '✕',
+                Strength::Fair => '!',
+                Strength::Strong | Strength::VeryStrong => '✓',
+            };
+
+            format!("{symbol} {label}")
+        } else {
+            label
+        }
+    };
+
+    widget::row::with_capacity(2)
+        .push(track)
+        .push(widget::text::caption(label))
+        .align_y(Vertical::Center)
+        .spacing(spacing.space_s)
+        .into()
+}
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: GPL-3
+
+//! A row of controls that wraps onto more lines rather than overflowing.
+
+use cosmic::widget;
+use cosmic::Element;
+
+/// Lays out `children` left to right, starting a new line whenever the next
+/// one would not fit, as happens in narrow windows or with large text.
+pub fn wrapping_row<'a, Message: 'static>(children: Vec<Element<'a, Message>>) -> Element<'a, Message> {
+    let space_s = cosmic::theme::spacing().space_s;
+
+    widget::flex_row(children)
+        .row_spacing(space_s)
+        .column_spacing(space_s)
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmic::iced::advanced::layout::{Layout, Limits};
+    use cosmic::iced::advanced::widget::Tree;
+    use cosmic::iced::{Font, Pixels, Size};
+
+    /// Width of a narrow window, in logical pixels at 100% scale.
+    const WINDOW_WIDTH: f32 = 360.0;
+    /// Labels as long as those on the pages' rows of buttons.
+    const LABELS: [&str; 6] = ["New game", "Campaign", "Daily challenge", "Timed", "Give up", "Reset all"];
+
+    /// A renderer that measures text without a GPU.
+    fn renderer() -> cosmic::Renderer {
+        cosmic::Renderer::Secondary(cosmic::iced_renderer::tiny_skia::Renderer::new(
+            Font::default(),
+            Pixels(14.0),
+        ))
+    }
+
+    #[test]
+    fn stays_within_the_window_at_large_scale_factors() {
+        let renderer = renderer();
+
+        for scale in [1.0, 1.5, 2.0] {
+            // Scaling everything up in a window lays out like the usual
+            // scale in a window that many times narrower.
+            let width = WINDOW_WIDTH / scale;
+
+            let mut row: Element<'_, ()> =
+                wrapping_row(LABELS.iter().map(|&label| widget::button::standard(label).into()).collect());
+            let mut tree = Tree::new(&row);
+            let limits = Limits::new(Size::ZERO, Size::new(width, f32::INFINITY));
+            let node = row.as_widget_mut().layout(&mut tree, &renderer, &limits);
+            let layout = Layout::new(&node);
+
+            assert!(layout.bounds().width <= width, "the row is wider than {width} at {scale}x");
+            assert_eq!(layout.children().count(), LABELS.len());
+
+            for child in layout.children() {
+                let bounds = child.bounds();
+                assert!(bounds.x >= 0.0 && bounds.x + bounds.width <= width + 0.5, "{bounds:?} overflows at {scale}x");
+            }
+
+            if scale >= 2.0 {
+                let mut lines: Vec<f32> = layout.children().map(|child| child.bounds().y).collect();
+                lines.dedup();
+                assert!(lines.len() > 1, "the buttons did not wrap at {scale}x");
+            }
+        }
+    }
+}
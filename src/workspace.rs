@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: GPL-3
+
+//! Following which workspace the main window is on, through the compositor's
+//! workspace and toplevel info protocols.
+//!
+//! The compositor lists every window as a toplevel with its app ID, title, and
+//! the workspaces it is on, and names each workspace. The app finds its own
+//! window among them by app ID and title, since the protocols do not tell
+//! clients which toplevel is theirs.
+
+use cosmic::cctk::sctk::output::{OutputHandler, OutputState};
+use cosmic::cctk::sctk::registry::{ProvidesRegistryState, RegistryState};
+use cosmic::cctk::toplevel_info::{ToplevelInfoHandler, ToplevelInfoState};
+use cosmic::cctk::wayland_client::globals::registry_queue_init;
+use cosmic::cctk::wayland_client::protocol::wl_output::WlOutput;
+use cosmic::cctk::wayland_client::{Connection, QueueHandle};
+use cosmic::cctk::wayland_protocols::ext::foreign_toplevel_list::v1::client::ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1;
+use cosmic::cctk::workspace::{WorkspaceHandler, WorkspaceState};
+use cosmic::cctk::{self, sctk};
+use cosmic::iced::Subscription;
+use cosmic::iced_futures;
+use futures_util::SinkExt;
+use tokio::sync::mpsc;
+
+/// Emits the name of the workspace the window with `app_id`, whose title
+/// starts with `title`, is on, each time it moves to another.
+pub fn watch(app_id: String, title: String) -> Subscription<String> {
+    Subscription::run_with_id(
+        ("workspace", app_id.clone(), title.clone()),
+        iced_futures::stream::channel(1, |mut emitter| async move {
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+
+            // The protocols are read on a thread of their own, which blocks
+            // waiting for the compositor's events.
+            std::thread::spawn(move || {
+                if let Err(why) = run(app_id, title, sender) {
+                    eprintln!("workspace tracking unavailable: {why}");
+                }
+            });
+
+            while let Some(name) = receiver.recv().await {
+                _ = emitter.send(name).await;
+            }
+        }),
+    )
+}
+
+fn run(app_id: String, title: String, sender: mpsc::UnboundedSender<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let connection = Connection::connect_to_env()?;
+    let (globals, mut queue) = registry_queue_init(&connection)?;
+    let qh = queue.handle();
+    let registry_state = RegistryState::new(&globals);
+
+    let mut state = State {
+        output_state: OutputState::new(&globals, &qh),
+        workspace_state: WorkspaceState::new(&registry_state, &qh),
+        toplevel_info_state: ToplevelInfoState::new(&registry_state, &qh),
+        registry_state,
+        app_id,
+        title,
+        workspace: None,
+        sender,
+    };
+
+    loop {
+        queue.blocking_dispatch(&mut state)?;
+
+        // The app stopped listening, as when the setting was turned off.
+        if state.sender.is_closed() {
+            return Ok(());
+        }
+    }
+}
+
+struct State {
+    registry_state: RegistryState,
+    output_state: OutputState,
+    workspace_state: WorkspaceState,
+    toplevel_info_state: ToplevelInfoState,
+    app_id: String,
+    title: String,
+    /// The workspace last reported, so moves are only sent once.
+    workspace: Option<String>,
+    sender: mpsc::UnboundedSender<String>,
+}
+
+impl State {
+    /// Sends the name of the main window's workspace, if it changed.
+    fn report(&mut self) {
+        let ours = self.toplevel_info_state.toplevels().find(|(_, info)| {
+            info.as_ref()
+                .is_some_and(|info| info.app_id == self.app_id && info.title.starts_with(&self.title))
+        });
+
+        let Some((_, Some(info))) = ours else {
+            return;
+        };
+
+        // A window shown on every workspace is on several; the first by name is kept.
+        let name = self
+            .workspace_state
+            .workspaces()
+            .filter(|workspace| info.workspace.contains(&workspace.handle))
+            .map(|workspace| workspace.name.clone())
+            .min();
+
+        if name.is_some() && name != self.workspace {
+            self.workspace = name.clone();
+            _ = self.sender.send(name.unwrap_or_default());
+        }
+    }
+}
+
+impl ProvidesRegistryState for State {
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry_state
+    }
+
+    sctk::registry_handlers!(OutputState);
+}
+
+impl OutputHandler for State {
+    fn output_state(&mut self) -> &mut OutputState {
+        &mut self.output_state
+    }
+
+    fn new_output(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _output: WlOutput) {}
+
+    fn update_output(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _output: WlOutput) {}
+
+    fn output_destroyed(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _output: WlOutput) {}
+}
+
+impl WorkspaceHandler for State {
+    fn workspace_state(&mut self) -> &mut WorkspaceState {
+        &mut self.workspace_state
+    }
+
+    /// Workspaces may be named after the window was placed on them.
+    fn done(&mut self) {
+        self.report();
+    }
+}
+
+impl ToplevelInfoHandler for State {
+    fn toplevel_info_state(&mut self) -> &mut ToplevelInfoState {
+        &mut self.toplevel_info_state
+    }
+
+    fn new_toplevel(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _toplevel: &ExtForeignToplevelHandleV1) {
+        self.report();
+    }
+
+    fn update_toplevel(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _toplevel: &ExtForeignToplevelHandleV1) {
+        self.report();
+    }
+
+    fn toplevel_closed(&mut self, _conn: &Connection, _qh: &QueueHandle<Self>, _toplevel: &ExtForeignToplevelHandleV1) {}
+}
+
+sctk::delegate_output!(State);
+sctk::delegate_registry!(State);
+cctk::delegate_toplevel_info!(State);
+cctk::delegate_workspace!(State);